@@ -2,8 +2,7 @@ use dotenv::dotenv;
 use hues::{
     prelude::*,
     service::{
-        CIEColor, ColorFeatureBasic, EffectType, LightAction, SceneAction, SceneBuilder,
-        SceneColorTempState, SceneEffectState, ScenePalette, ScenePaletteColor, SceneStatus,
+        CIEColor, EffectType, LightAction, SceneAction, SceneBuilder, ScenePalette, SceneStatus,
         Schedule, SignalType, SmartScene, TimeslotStart, Weekday, Zone, ZoneArchetype,
     },
 };
@@ -143,7 +142,7 @@ async fn change_room_type(
             let _ = room
                 .send(&[ZoneCommand::Metadata {
                     name: None,
-                    archetype: Some(archetype),
+                    archetype: Some(archetype.clone()),
                 }])
                 .await?;
         }
@@ -230,17 +229,9 @@ async fn create_scene(bridge: &Bridge, name: impl Into<String>) -> Result<(), Hu
                         .into_iter()
                         .map(|light| {
                             let action = if light.supports_color() {
-                                LightAction {
-                                    color: Some(ColorFeatureBasic::xy(0.3, 0.4)),
-                                    ..Default::default()
-                                }
+                                LightAction::default().color_xy(0.3, 0.4)
                             } else {
-                                LightAction {
-                                    color_temperature: Some(SceneColorTempState {
-                                        mirek: Some(153),
-                                    }),
-                                    ..Default::default()
-                                }
+                                LightAction::default().color_temp(153)
                             };
 
                             SceneAction {
@@ -250,16 +241,12 @@ async fn create_scene(bridge: &Bridge, name: impl Into<String>) -> Result<(), Hu
                         })
                         .collect::<Vec<_>>(),
                 )
-                .palette(ScenePalette {
-                    color: vec![
-                        ScenePaletteColor::xyb(0.1, 0.3, 70.0),
-                        ScenePaletteColor::xyb(0.3, 0.5, 70.0),
-                    ],
-                    effects: vec![SceneEffectState {
-                        effect: Some(EffectType::Candle),
-                    }],
-                    ..Default::default()
-                }),
+                .palette(
+                    ScenePalette::default()
+                        .add_color(0.1, 0.3, 70.0)
+                        .add_color(0.3, 0.5, 70.0)
+                        .add_effect(EffectType::Candle),
+                ),
         )
         .await?;
 
@@ -293,26 +280,28 @@ async fn identify_all_lights(bridge: &Bridge) -> Result<(), HueAPIError> {
 async fn randomize_all_lights(bridge: &Bridge) -> Result<(), HueAPIError> {
     let mut rng = rand::thread_rng();
     loop {
-        for light in bridge.lights() {
-            if light.data().color.is_some() {
-                let _ = light
-                    .send(&[
+        let batch = bridge
+            .lights()
+            .iter()
+            .map(|light| {
+                let commands = if light.data().color.is_some() {
+                    vec![
                         LightCommand::Dim(rng.gen_range(70.0..=100.0)),
                         LightCommand::Color {
                             x: rng.gen_range(0.0..=0.5),
                             y: rng.gen_range(0.0..=0.4),
                         },
-                    ])
-                    .await;
-            } else {
-                let _ = light
-                    .send(&[
+                    ]
+                } else {
+                    vec![
                         LightCommand::Dim(80.0),
                         LightCommand::ColorTemp(rng.gen_range(350..=500)),
-                    ])
-                    .await;
-            }
-        }
+                    ]
+                };
+                (light.rid(), commands)
+            })
+            .collect::<Vec<_>>();
+        let _ = bridge.send_batch(&batch, 4).await;
         std::thread::sleep(Duration::from_millis(2000));
     }
 }
@@ -23,17 +23,15 @@ async fn main() {
     dbg!(&script);
 
     todo!();
-    let instance = BehaviorInstance::builder(
-        script.id(),
-        serde_json::json!({
+    let instance = BehaviorInstance::builder(script.id())
+        .configuration(serde_json::json!({
             "where": [
                 { "group": { "rid": "d14bacd9-a352-4f90-912b-6e6f272ff059", "rtype": "room" } }
             ],
             "device": { "rid": bridge.data().unwrap().id, "rtype": ResourceType::Bridge },
-        }),
-    )
-    .name("TEST NIGHTY NIGHT")
-    .enabled(true);
+        }))
+        .name("TEST NIGHTY NIGHT")
+        .enabled(true);
     let instance = bridge.create_behavior_instance(instance).await;
     dbg!(&instance);
 
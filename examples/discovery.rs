@@ -12,7 +12,7 @@ async fn main() {
         .build()
         .poll(Duration::from_secs(30))
         .await;
-    let key = bridge.create_app("my_app", "my_instance").await.unwrap();
+    let credentials = bridge.create_app("my_app", "my_instance").await.unwrap();
 
     for light in bridge.lights() {
         let _ = light.identify().await;
@@ -50,12 +50,11 @@ async fn main() {
 
         let mut bridge = Bridge::discover().await.unwrap().build();
 
-        // This is your App Key, save it for later!
+        // These are your App Key and Client Key, save them for later!
 
-        let key = bridge
+        let credentials = bridge
             .create_app("my_hue_app", "my_instance_name")
             .await
-            .unwrap()
-            .to_owned();
+            .unwrap();
     }
 }
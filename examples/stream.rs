@@ -1,8 +1,5 @@
 use dotenv::dotenv;
-use hues::{
-    command::EntertainmentConfigurationCommand,
-    service::{Bridge, ResouceType},
-};
+use hues::service::{Bridge, ResourceType};
 use std::{net::IpAddr, time::Duration};
 
 #[tokio::main]
@@ -32,15 +29,17 @@ async fn main() {
 
     let ents = bridge.entertainment_configurations();
     let ent = ents.get(0).unwrap();
-    // dbg!(
-    //     ent.send(&[EntertainmentConfigurationCommand::Action(
-    //         hues::EntertainmentAction::Start,
-    //     )])
-    //     .await
-    // );
-    // dbg!(ent.open_stream().await);
 
-    dbg!(bridge.initialize_streaming(ent.id()).await);
-
-    loop {}
+    let mut session = bridge.initialize_streaming(ent.id()).await.unwrap();
+    let mut red = 0u8;
+    loop {
+        for channel in &ent.data().channels {
+            session.set_channel(channel.channel_id, [red, 0, 255 - red]);
+        }
+        if let Err(e) = session.flush().await {
+            eprintln!("{e:?}");
+        }
+        red = red.wrapping_add(5);
+        tokio::time::sleep(Duration::from_millis(40)).await;
+    }
 }
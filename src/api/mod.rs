@@ -1,9 +1,17 @@
+mod transport;
 mod v1;
 mod v2;
 
+use crate::service::ResourceIdentifier;
 use serde::Deserialize;
 
-pub(crate) use v2::BridgeClient;
+pub use reqwest::Certificate;
+#[cfg(test)]
+pub(crate) use transport::mock::MockTransport;
+pub use transport::{HueTransport, TransportFuture, TransportRequest, TransportResponse};
+pub(crate) use v2::{
+    BridgeClient, ResourceListing, DEFAULT_RETRY_BACKOFF, DEFAULT_RETRY_MAX_BACKOFF,
+};
 
 #[derive(Debug, Deserialize)]
 pub(crate) struct HueAPIResponse<D> {
@@ -14,19 +22,50 @@ pub(crate) struct HueAPIResponse<D> {
 #[derive(Debug, Deserialize)]
 pub(crate) struct HueAPIErrorMessage {
     /// A human-readable explanation specific to this occurrence of the problem
-    pub description: serde_json::Value,
+    pub description: String,
 }
 
 /// Possible errors related to communication with the Hue Bridge.
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum HueAPIError {
+    /// The request could not be sent.
     BadRequest,
+    /// The response body could not be read.
     BadResponse,
-    BadDeserialize,
+    /// The bridge responded with a body that could not be deserialized.
+    BadDeserialize {
+        /// The deserialization error message.
+        error: String,
+        /// The raw response body.
+        body: String,
+    },
     NotFound,
-    HueBridgeError(serde_json::Value),
-    ServerSentEvent,
-    Streaming,
+    /// The bridge rejected the request.
+    HueBridgeError {
+        /// The HTTP status code of the response.
+        status: u16,
+        /// Every error message the bridge reported.
+        descriptions: Vec<String>,
+    },
+    /// The bridge could not be reached, with the reason.
+    Connect(String),
+    /// The request timed out.
+    Timeout,
+    /// The event stream could not be opened, with the reason.
+    ServerSentEvent(String),
+    /// An entertainment stream could not be established, with the reason.
+    Streaming(String),
+    /// A request was rejected locally, before being sent to the bridge.
+    InvalidInput(String),
+    /// A task sending a request panicked or was cancelled, with the reason.
+    Task(String),
+    /// A scene was recalled on a group that it does not apply to.
+    SceneGroupMismatch {
+        /// The scene being recalled.
+        scene: ResourceIdentifier,
+        /// The group it was recalled on.
+        group: ResourceIdentifier,
+    },
 }
 
 /// The protol used by the Hue Bridge, currently only [`Version::V2`] is supported.
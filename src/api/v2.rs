@@ -1,17 +1,27 @@
 use crate::{
-    api::{v1::RegisterResponse, HueAPIError, HueAPIResponse},
+    api::{
+        v1::RegisterResponse, HueAPIError, HueAPIResponse, HueTransport, TransportFuture,
+        TransportRequest, TransportResponse,
+    },
     service::{
         BehaviorInstanceData, BehaviorScriptData, BridgeData, ButtonData, ContactData, DeviceData,
         DevicePowerData, EntertainmentConfigurationData, EntertainmentData, GeofenceClientData,
-        GeolocationData, GroupData, HomeData, HomeKitData, LightData, LightLevelData, MatterData,
-        MatterFabricData, MotionData, RelativeRotaryData, Resource, ResourceIdentifier, SceneData,
-        SmartSceneData, TamperData, TemperatureData, ZGPConnectivityData, ZigbeeConnectivityData,
+        GeolocationData, GroupData, GroupedLightLevelData, GroupedMotionData, HomeData,
+        HomeKitData, LightData, LightLevelData, MatterData, MatterFabricData, MotionData,
+        RelativeRotaryData, Resource, ResourceIdentifier, SceneData, SendOutcome, SmartSceneData,
+        TamperData, TemperatureData, ZGPConnectivityData, ZigbeeConnectivityData,
         ZigbeeDeviceDiscoveryData, ZoneData,
     },
 };
 
-use reqwest::{Certificate, Client as ReqwestClient, IntoUrl, Method};
-use std::net::IpAddr;
+use reqwest::{
+    header::RETRY_AFTER, Certificate, Client as ReqwestClient, IntoUrl, Method, RequestBuilder, Url,
+};
+use std::{
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
 
 #[cfg(feature = "sse")]
 use reqwest_eventsource::EventSource;
@@ -22,6 +32,17 @@ use rustls::{pki_types::CertificateDer, RootCertStore};
 const V2_PREFIX: &'static str = "/clip/v2";
 #[allow(dead_code)]
 const UDP_PORT: usize = 2100;
+pub(crate) const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(250);
+pub(crate) const DEFAULT_RETRY_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Every resource listed by the bridge in a single fetch.
+#[derive(Debug, Default)]
+pub(crate) struct ResourceListing {
+    pub resources: Vec<Resource>,
+    /// The type and id of each resource which is of a type this crate does not
+    /// model, or which could not be deserialized.
+    pub unmodeled: Vec<(String, String)>,
+}
 
 #[derive(Clone, Debug)]
 pub struct BridgeClient {
@@ -29,6 +50,16 @@ pub struct BridgeClient {
     app_key: String,
     client_key: Option<String>,
     client: ReqwestClient,
+    timeout: Option<Duration>,
+    max_retries: u8,
+    retry_backoff: Duration,
+    retry_max_backoff: Duration,
+    retry_writes: bool,
+    connect_timeout: Option<Duration>,
+    root_certificate: Option<Certificate>,
+    bridge_id: Option<String>,
+    accept_invalid_certs: bool,
+    transport: Option<Arc<dyn HueTransport>>,
     #[cfg(feature = "streaming")]
     root_store: RootCertStore,
 }
@@ -40,14 +71,17 @@ impl BridgeClient {
             addr: addr.into(),
             app_key: app_key.into(),
             client_key: None,
-            client: ReqwestClient::builder()
-                .add_root_certificate(
-                    Certificate::from_pem(include_bytes!("../../hue.pem")).unwrap(),
-                )
-                // FIXME: why cert :(
-                .danger_accept_invalid_certs(true)
-                .build()
-                .unwrap(),
+            client: http_client(None, None, None, true),
+            timeout: None,
+            max_retries: 0,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+            retry_max_backoff: DEFAULT_RETRY_MAX_BACKOFF,
+            retry_writes: false,
+            connect_timeout: None,
+            root_certificate: None,
+            bridge_id: None,
+            accept_invalid_certs: true,
+            transport: None,
             #[cfg(feature = "streaming")]
             root_store: {
                 let cert = CertificateDer::from(include_bytes!("../../hue.pem").to_vec());
@@ -68,14 +102,17 @@ impl BridgeClient {
             addr: addr.into(),
             app_key: app_key.into(),
             client_key: Some(client_key.into()),
-            client: ReqwestClient::builder()
-                .add_root_certificate(
-                    Certificate::from_pem(include_bytes!("../../hue.pem")).unwrap(),
-                )
-                // FIXME: why cert :(
-                .danger_accept_invalid_certs(true)
-                .build()
-                .unwrap(),
+            client: http_client(None, None, None, true),
+            timeout: None,
+            max_retries: 0,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+            retry_max_backoff: DEFAULT_RETRY_MAX_BACKOFF,
+            retry_writes: false,
+            connect_timeout: None,
+            root_certificate: None,
+            bridge_id: None,
+            accept_invalid_certs: true,
+            transport: None,
             #[cfg(feature = "streaming")]
             root_store: {
                 let cert = CertificateDer::from(include_bytes!("../../hue.der").to_vec());
@@ -94,26 +131,109 @@ impl BridgeClient {
         &self.app_key
     }
 
-    #[allow(dead_code)]
     pub fn client_key(&self) -> Option<&str> {
         self.client_key.as_deref()
     }
 
+    /// Bound each request by `timeout` and each connection attempt by
+    /// `connect_timeout`. The request timeout is applied per request rather
+    /// than on the client, so that the event stream stays open indefinitely.
+    pub(crate) fn set_timeouts(
+        &mut self,
+        timeout: Option<Duration>,
+        connect_timeout: Option<Duration>,
+    ) {
+        self.timeout = timeout;
+        self.connect_timeout = connect_timeout;
+        self.rebuild_client();
+    }
+
+    /// Verify the bridge's certificate against `root_certificate`, or the
+    /// bundled Hue root CA if [None], and against the name `bridge_id` rather
+    /// than the bridge's IP address. Verification is skipped entirely while
+    /// `accept_invalid_certs` is set.
+    pub(crate) fn set_verification(
+        &mut self,
+        root_certificate: Option<Certificate>,
+        bridge_id: Option<String>,
+        accept_invalid_certs: bool,
+    ) {
+        self.root_certificate = root_certificate;
+        self.bridge_id = bridge_id.map(|id| id.to_lowercase());
+        self.accept_invalid_certs = accept_invalid_certs;
+        self.rebuild_client();
+    }
+
+    fn rebuild_client(&mut self) {
+        self.client = http_client(
+            self.connect_timeout,
+            self.root_certificate.as_ref(),
+            self.bridge_id.as_deref().map(|id| (id, self.addr)),
+            self.accept_invalid_certs,
+        );
+    }
+
+    /// Whether certificates failing verification are accepted.
+    #[cfg(test)]
+    pub(crate) fn accepts_invalid_certs(&self) -> bool {
+        self.accept_invalid_certs
+    }
+
+    /// The host requests are addressed to: the bridge ID when verifying it,
+    /// so that it matches the certificate, or otherwise the IP address.
+    fn host(&self) -> String {
+        match &self.bridge_id {
+            Some(id) => id.clone(),
+            None => self.addr.to_string(),
+        }
+    }
+
+    /// Send requests through `transport` rather than over HTTPS.
+    pub(crate) fn set_transport(&mut self, transport: Arc<dyn HueTransport>) {
+        self.transport = Some(transport);
+    }
+
+    pub(crate) fn set_retries(
+        &mut self,
+        max_retries: u8,
+        backoff: Duration,
+        max_backoff: Duration,
+        writes: bool,
+    ) {
+        self.max_retries = max_retries;
+        self.retry_backoff = backoff;
+        self.retry_max_backoff = max_backoff;
+        self.retry_writes = writes;
+    }
+
+    #[cfg(feature = "streaming")]
+    pub(crate) fn set_client_key(&mut self, client_key: impl Into<String>) {
+        self.client_key = Some(client_key.into());
+    }
+
+    fn request(&self, method: Method, url: impl IntoUrl) -> RequestBuilder {
+        let req = self.client.request(method, url);
+        match self.timeout {
+            Some(timeout) => req.timeout(timeout),
+            None => req,
+        }
+    }
+
     fn api_url(&self) -> String {
-        format!("https://{}{}", &self.addr, V2_PREFIX)
+        format!("https://{}{}", self.host(), V2_PREFIX)
     }
 
     fn api_v1_url(&self) -> String {
-        format!("https://{}/api", &self.addr)
+        format!("https://{}/api", self.host())
     }
 
     #[allow(dead_code)]
     fn auth_url(&self) -> String {
-        format!("https://{}/auth/v1", &self.addr)
+        format!("https://{}/auth/v1", self.host())
     }
 
     fn event_stream_url(&self) -> String {
-        format!("https://{}/eventstream{}", &self.addr, V2_PREFIX)
+        format!("https://{}/eventstream{}", self.host(), V2_PREFIX)
     }
 
     #[allow(dead_code)]
@@ -121,6 +241,9 @@ impl BridgeClient {
         format!("{}:{}", &self.addr, UDP_PORT)
     }
 
+    /// Send a request, retrying transient failures according to the
+    /// configured retry policy. GETs are always eligible for retry, PUTs and
+    /// DELETEs only when write retries are enabled.
     async fn make_request<Body: serde::Serialize, Return>(
         &self,
         url: impl IntoUrl,
@@ -130,46 +253,106 @@ impl BridgeClient {
     where
         Return: serde::de::DeserializeOwned + std::fmt::Debug,
     {
-        match self
-            .client
-            .request(method, url)
-            .header("hue-application-key", &self.app_key)
-            .json(&body)
-            .send()
+        self.make_request_inner(url, method, body, false)
             .await
-        {
-            Ok(res) => match res.json::<HueAPIResponse<Return>>().await {
-                Ok(res) => {
-                    if res.errors.is_empty() && res.data.is_some() {
-                        Ok(res.data.unwrap())
-                    } else {
-                        Err(HueAPIError::HueBridgeError(serde_json::json!(res
-                            .errors
-                            .into_iter()
-                            .map(|e| serde_json::from_str::<serde_json::Value>(
-                                e.description.as_str().unwrap(),
-                            )
-                            .unwrap())
-                            .collect::<Vec<_>>())))
-                    }
-                }
-                Err(e) => {
-                    log::error!("{e}");
-                    Err(HueAPIError::BadDeserialize)
+            .map(|(data, _)| data)
+    }
+
+    /// Like [BridgeClient::make_request], but a response carrying both data
+    /// and errors succeeds, with the error descriptions returned alongside
+    /// the data.
+    async fn make_partial_request<Body: serde::Serialize, Return>(
+        &self,
+        url: impl IntoUrl,
+        method: Method,
+        body: Option<Body>,
+    ) -> Result<(Return, Vec<String>), HueAPIError>
+    where
+        Return: serde::de::DeserializeOwned + std::fmt::Debug,
+    {
+        self.make_request_inner(url, method, body, true).await
+    }
+
+    async fn make_request_inner<Body: serde::Serialize, Return>(
+        &self,
+        url: impl IntoUrl,
+        method: Method,
+        body: Option<Body>,
+        allow_partial: bool,
+    ) -> Result<(Return, Vec<String>), HueAPIError>
+    where
+        Return: serde::de::DeserializeOwned + std::fmt::Debug,
+    {
+        let url = url.into_url().map_err(|_| HueAPIError::BadRequest)?;
+        let retryable_method = method == Method::GET
+            || (self.retry_writes && (method == Method::PUT || method == Method::DELETE));
+        let mut attempt = 0;
+        loop {
+            let (res, retry_after) = self
+                .make_request_once(url.clone(), method.clone(), &body, allow_partial)
+                .await;
+            match res {
+                Err(e) if retryable_method && attempt < self.max_retries && is_transient(&e) => {
+                    let delay = retry_after
+                        .unwrap_or(
+                            self.retry_backoff
+                                .saturating_mul(2u32.saturating_pow(attempt.into())),
+                        )
+                        .min(self.retry_max_backoff);
+                    log::debug!("retrying {method} {url} in {delay:?} after {e:?}");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
                 }
-            },
-            _ => Err(HueAPIError::BadRequest),
+                res => return res,
+            }
         }
     }
 
+    /// Send a request once, returning the outcome along with the delay
+    /// requested by a `Retry-After` header, if any. With `allow_partial`, a
+    /// successful response carrying both data and errors is not a failure.
+    async fn make_request_once<Body: serde::Serialize, Return>(
+        &self,
+        url: Url,
+        method: Method,
+        body: &Option<Body>,
+        allow_partial: bool,
+    ) -> (Result<(Return, Vec<String>), HueAPIError>, Option<Duration>)
+    where
+        Return: serde::de::DeserializeOwned + std::fmt::Debug,
+    {
+        let body = match body.as_ref().map(serde_json::to_value).transpose() {
+            Ok(body) => body,
+            Err(_) => return (Err(HueAPIError::BadRequest), None),
+        };
+        let request = TransportRequest {
+            method,
+            url,
+            app_key: self.app_key.clone(),
+            body,
+        };
+        let res = match &self.transport {
+            Some(transport) => transport.send(request).await,
+            None => HueTransport::send(self, request).await,
+        };
+        let TransportResponse {
+            status,
+            retry_after,
+            body,
+        } = match res {
+            Ok(res) => res,
+            Err(e) => return (Err(e), None),
+        };
+        (parse_response(status, body, allow_partial), retry_after)
+    }
+
     pub(crate) async fn create_app(
         &mut self,
         app_name: impl Into<String>,
         instance_name: impl Into<String>,
     ) -> Result<&str, HueAPIError> {
         match self
-            .client
-            .post(self.api_v1_url())
+            .request(Method::POST, self.api_v1_url())
             .json(&serde_json::json!({
                "devicetype": format!("{}#{}", app_name.into(), instance_name.into()),
                "generateclientkey": true
@@ -177,54 +360,80 @@ impl BridgeClient {
             .send()
             .await
         {
-            Ok(res) => match res.json::<Vec<super::v1::RegisterResponse>>().await {
-                Ok(successes_or_errors) => {
-                    for item in successes_or_errors {
-                        match item {
-                            RegisterResponse::Success { success } => {
-                                self.app_key = success.username;
-                                self.client_key = Some(success.clientkey);
-                                return Ok(&self.app_key);
-                            }
-                            RegisterResponse::Error { error } => {
-                                return Err(HueAPIError::HueBridgeError(serde_json::Value::from(
-                                    error.description,
-                                )))
+            Ok(res) => {
+                let status = res.status().as_u16();
+                let body = res.text().await.map_err(request_error)?;
+                match serde_json::from_str::<Vec<super::v1::RegisterResponse>>(&body) {
+                    Ok(successes_or_errors) => {
+                        for item in successes_or_errors {
+                            match item {
+                                RegisterResponse::Success { success } => {
+                                    self.app_key = success.username;
+                                    self.client_key = Some(success.clientkey);
+                                    return Ok(&self.app_key);
+                                }
+                                RegisterResponse::Error { error } => {
+                                    return Err(HueAPIError::HueBridgeError {
+                                        status,
+                                        descriptions: vec![error.description],
+                                    })
+                                }
                             }
                         }
+                        return Err(HueAPIError::HueBridgeError {
+                            status,
+                            descriptions: vec!["received no events".into()],
+                        });
                     }
-                    return Err(HueAPIError::HueBridgeError("received no events".into()));
+                    Err(e) => Err(HueAPIError::BadDeserialize {
+                        error: e.to_string(),
+                        body,
+                    }),
                 }
-                _ => Err(HueAPIError::BadDeserialize),
-            },
-            _ => Err(HueAPIError::BadRequest),
+            }
+            Err(e) => Err(request_error(e)),
         }
     }
 
     pub(crate) async fn delete_app(&self, app_key: impl Into<String>) -> Result<(), HueAPIError> {
         match self
-            .client
-            .delete(
+            .request(
+                Method::DELETE,
                 self.api_v1_url() + "/" + &self.app_key + "/config/whitelist/" + &app_key.into(),
             )
             .send()
             .await
         {
-            Ok(res) => match res.json::<Vec<super::v1::UnregisterResponse>>().await {
-                Ok(successes_or_errors) => match successes_or_errors.into_iter().next().unwrap() {
-                    super::v1::UnregisterResponse::Success(_message) => Ok(()),
-                    super::v1::UnregisterResponse::Error(message) => Err(
-                        HueAPIError::HueBridgeError(serde_json::Value::from(message)),
-                    ),
-                },
-                _ => Err(HueAPIError::BadDeserialize),
-            },
-            _ => Err(HueAPIError::BadRequest),
+            Ok(res) => {
+                let status = res.status().as_u16();
+                let body = res.text().await.map_err(request_error)?;
+                match serde_json::from_str::<Vec<super::v1::UnregisterResponse>>(&body) {
+                    Ok(successes_or_errors) => {
+                        match successes_or_errors.into_iter().next().unwrap() {
+                            super::v1::UnregisterResponse::Success(_message) => Ok(()),
+                            super::v1::UnregisterResponse::Error(message) => {
+                                Err(HueAPIError::HueBridgeError {
+                                    status,
+                                    descriptions: vec![message],
+                                })
+                            }
+                        }
+                    }
+                    Err(e) => Err(HueAPIError::BadDeserialize {
+                        error: e.to_string(),
+                        body,
+                    }),
+                }
+            }
+            Err(e) => Err(request_error(e)),
         }
     }
 
     #[cfg(feature = "streaming")]
-    pub(crate) async fn open_stream(&self, ent_id: impl Into<String>) -> Result<(), HueAPIError> {
+    pub(crate) async fn open_stream(
+        &self,
+        ent_id: impl Into<String>,
+    ) -> Result<std::sync::Arc<dyn webrtc_util::Conn + Send + Sync>, HueAPIError> {
         use std::sync::Arc;
         use tokio::net::UdpSocket;
         use webrtc_dtls::cipher_suite::CipherSuiteId;
@@ -245,25 +454,42 @@ impl BridgeClient {
         {
             Ok(res) => match res.headers().get("hue-application-id") {
                 Some(app_id) => {
-                    let hue_app_id = app_id.to_str().unwrap().to_owned();
-
-                    dbg!(self
-                        .put_entertainment_configuration(
-                            id.clone(),
-                            &serde_json::json!({ "action": "start" })
-                        )
-                        .await
-                        .unwrap());
-
-                    let conn = Arc::new(UdpSocket::bind("0.0.0.0:0").await.unwrap());
-                    conn.connect(self.entertainment_url()).await.unwrap();
-                    println!("connecting..");
-
-                    let client_key = self.client_key.clone().unwrap();
+                    let hue_app_id = app_id
+                        .to_str()
+                        .map_err(|e| {
+                            HueAPIError::Streaming(format!("invalid hue-application-id: {e}"))
+                        })?
+                        .to_owned();
+
+                    self.put_entertainment_configuration(
+                        id.clone(),
+                        &serde_json::json!({ "action": "start" }),
+                    )
+                    .await
+                    .map_err(|e| {
+                        HueAPIError::Streaming(format!(
+                            "failed to start entertainment configuration {id}: {e:?}"
+                        ))
+                    })?;
+
+                    let conn = Arc::new(UdpSocket::bind("0.0.0.0:0").await.map_err(|e| {
+                        HueAPIError::Streaming(format!("failed to bind UDP socket: {e}"))
+                    })?);
+                    conn.connect(self.entertainment_url()).await.map_err(|e| {
+                        HueAPIError::Streaming(format!(
+                            "failed to connect to {}: {e}",
+                            self.entertainment_url()
+                        ))
+                    })?;
+                    log::debug!("connecting to entertainment stream");
+
+                    let client_key = self.client_key.clone().ok_or_else(|| {
+                        HueAPIError::Streaming("no client key configured".to_owned())
+                    })?;
                     let config = Config {
-                        insecure_skip_verify: true,
+                        insecure_skip_verify: self.accept_invalid_certs,
                         psk: Some(Arc::new(move |hint: &[u8]| -> Result<Vec<u8>, Error> {
-                            println!("Client's hint: {}", String::from_utf8(hint.to_vec())?);
+                            log::debug!("client's hint: {}", String::from_utf8(hint.to_vec())?);
                             Ok(client_key.as_bytes().to_vec())
                         })),
                         // certificates: vec![
@@ -278,28 +504,11 @@ impl BridgeClient {
                     std::thread::sleep(std::time::Duration::from_millis(2000));
 
                     let dtls_conn: Arc<dyn Conn + Send + Sync> =
-                        Arc::new(DTLSConn::new(conn, config, true, None).await.unwrap());
-
-                    let mut bytes: Vec<u8> = vec![];
-                    bytes.extend("HueStream".as_bytes()); // protocol
-                    bytes.extend(&[0x02, 0x00]); // version 2.0
-                    bytes.push(0x07); // sequence 7
-                    bytes.extend(&[0x00, 0x00]); // reserved
-                    bytes.push(0x00); // color mode RGB
-                    bytes.push(0x00); // reserved
-                    bytes.extend(id.as_bytes()); // entertainment configuration id
-
-                    bytes.push(0x00); // channel 0
-                    bytes.extend(&[0xff, 0xff, 0x00, 0x00, 0x00, 0x00]); // red
-
-                    bytes.push(0x00); // channel 1
-                    bytes.extend(&[0x00, 0x00, 0x00, 0x00, 0xff, 0xff]); // red
-
-                    println!("{:x?}", &bytes);
-
-                    let res = dtls_conn.send(&bytes).await.unwrap();
+                        Arc::new(DTLSConn::new(conn, config, true, None).await.map_err(|e| {
+                            HueAPIError::Streaming(format!("DTLS handshake failed: {e}"))
+                        })?);
 
-                    Ok(())
+                    Ok(dtls_conn)
                 }
                 None => Err(HueAPIError::BadResponse),
             },
@@ -321,7 +530,7 @@ impl BridgeClient {
 
         match EventSource::new(req) {
             Ok(es) => Ok(es),
-            Err(_) => Err(HueAPIError::ServerSentEvent),
+            Err(e) => Err(HueAPIError::ServerSentEvent(e.to_string())),
         }
     }
 
@@ -361,9 +570,27 @@ impl BridgeClient {
         self.make_request(url, Method::GET, None::<()>).await
     }
 
-    pub(crate) async fn get_resources(&self) -> Result<Vec<Resource>, HueAPIError> {
+    /// Resources which cannot be deserialized, e.g. due to values unknown to
+    /// this crate, are logged and listed as unmodeled rather than failing the
+    /// whole request.
+    pub(crate) async fn get_resources(&self) -> Result<ResourceListing, HueAPIError> {
         let url = self.api_url() + "/resource";
-        self.make_request(url, Method::GET, None::<()>).await
+        let resources: Vec<serde_json::Value> =
+            self.make_request(url, Method::GET, None::<()>).await?;
+        let mut listing = ResourceListing::default();
+        for res in resources {
+            let field = |key: &str| res[key].as_str().unwrap_or_default().to_owned();
+            let (rtype, id) = (field("type"), field("id"));
+            match serde_json::from_value::<Resource>(res) {
+                Ok(res) if res.is_modeled() => listing.resources.push(res),
+                Ok(_) => listing.unmodeled.push((rtype, id)),
+                Err(e) => {
+                    log::warn!("skipping {rtype} resource: {e}");
+                    listing.unmodeled.push((rtype, id));
+                }
+            }
+        }
+        Ok(listing)
     }
 
     pub(crate) async fn get_behavior_script(
@@ -386,7 +613,16 @@ impl BridgeClient {
         id: impl Into<String>,
     ) -> Result<BehaviorInstanceData, HueAPIError> {
         let url = self.api_url() + "/resource/behavior_instance/" + &id.into();
-        self.make_request(url, Method::GET, None::<()>).await
+        match self
+            .make_request::<(), Vec<BehaviorInstanceData>>(url, Method::GET, None::<()>)
+            .await
+        {
+            Ok(data) => match data.into_iter().nth(0) {
+                Some(first) => Ok(first),
+                None => Err(HueAPIError::NotFound),
+            },
+            Err(e) => Err(e),
+        }
     }
 
     pub(crate) async fn get_behavior_instances(
@@ -419,7 +655,10 @@ impl BridgeClient {
             .await?;
         match rids.into_iter().nth(0) {
             Some(rid) => Ok(rid),
-            None => Err(HueAPIError::BadDeserialize),
+            None => Err(HueAPIError::BadDeserialize {
+                error: "response contained no resource identifier".into(),
+                body: String::new(),
+            }),
         }
     }
 
@@ -436,7 +675,16 @@ impl BridgeClient {
         id: impl Into<String>,
     ) -> Result<EntertainmentConfigurationData, HueAPIError> {
         let url = self.api_url() + "/resource/entertainment_configuration/" + &id.into();
-        self.make_request(url, Method::GET, None::<()>).await
+        match self
+            .make_request::<(), Vec<EntertainmentConfigurationData>>(url, Method::GET, None::<()>)
+            .await
+        {
+            Ok(data) => match data.into_iter().nth(0) {
+                Some(first) => Ok(first),
+                None => Err(HueAPIError::NotFound),
+            },
+            Err(e) => Err(e),
+        }
     }
 
     pub(crate) async fn get_entertainment_configurations(
@@ -469,7 +717,10 @@ impl BridgeClient {
             .await?;
         match rids.into_iter().nth(0) {
             Some(rid) => Ok(rid),
-            None => Err(HueAPIError::BadDeserialize),
+            None => Err(HueAPIError::BadDeserialize {
+                error: "response contained no resource identifier".into(),
+                body: String::new(),
+            }),
         }
     }
 
@@ -507,6 +758,15 @@ impl BridgeClient {
         self.make_request(url, Method::GET, None::<()>).await
     }
 
+    pub(crate) async fn put_button(
+        &self,
+        id: impl Into<String>,
+        payload: &serde_json::Value,
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        let url = self.api_url() + "/resource/button/" + &id.into();
+        self.make_request(url, Method::PUT, Some(payload)).await
+    }
+
     pub(crate) async fn get_contact(
         &self,
         id: impl Into<String>,
@@ -613,7 +873,10 @@ impl BridgeClient {
             .await?;
         match rids.into_iter().nth(0) {
             Some(rid) => Ok(rid),
-            None => Err(HueAPIError::BadDeserialize),
+            None => Err(HueAPIError::BadDeserialize {
+                error: "response contained no resource identifier".into(),
+                body: String::new(),
+            }),
         }
     }
 
@@ -893,6 +1156,70 @@ impl BridgeClient {
         self.make_request(url, Method::PUT, Some(payload)).await
     }
 
+    pub(crate) async fn get_grouped_light_level(
+        &self,
+        id: impl Into<String>,
+    ) -> Result<GroupedLightLevelData, HueAPIError> {
+        let url = self.api_url() + "/resource/grouped_light_level/" + &id.into();
+        match self
+            .make_request::<(), Vec<GroupedLightLevelData>>(url, Method::GET, None::<()>)
+            .await
+        {
+            Ok(data) => match data.into_iter().nth(0) {
+                Some(first) => Ok(first),
+                None => Err(HueAPIError::NotFound),
+            },
+            Err(e) => Err(e),
+        }
+    }
+
+    pub(crate) async fn get_grouped_light_levels(
+        &self,
+    ) -> Result<Vec<GroupedLightLevelData>, HueAPIError> {
+        let url = self.api_url() + "/resource/grouped_light_level";
+        self.make_request(url, Method::GET, None::<()>).await
+    }
+
+    pub(crate) async fn put_grouped_light_level(
+        &self,
+        id: impl Into<String>,
+        payload: &serde_json::Value,
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        let url = self.api_url() + "/resource/grouped_light_level/" + &id.into();
+        self.make_request(url, Method::PUT, Some(payload)).await
+    }
+
+    pub(crate) async fn get_grouped_motion(
+        &self,
+        id: impl Into<String>,
+    ) -> Result<GroupedMotionData, HueAPIError> {
+        let url = self.api_url() + "/resource/grouped_motion/" + &id.into();
+        match self
+            .make_request::<(), Vec<GroupedMotionData>>(url, Method::GET, None::<()>)
+            .await
+        {
+            Ok(data) => match data.into_iter().nth(0) {
+                Some(first) => Ok(first),
+                None => Err(HueAPIError::NotFound),
+            },
+            Err(e) => Err(e),
+        }
+    }
+
+    pub(crate) async fn get_grouped_motions(&self) -> Result<Vec<GroupedMotionData>, HueAPIError> {
+        let url = self.api_url() + "/resource/grouped_motion";
+        self.make_request(url, Method::GET, None::<()>).await
+    }
+
+    pub(crate) async fn put_grouped_motion(
+        &self,
+        id: impl Into<String>,
+        payload: &serde_json::Value,
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        let url = self.api_url() + "/resource/grouped_motion/" + &id.into();
+        self.make_request(url, Method::PUT, Some(payload)).await
+    }
+
     pub(crate) async fn get_light(&self, id: impl Into<String>) -> Result<LightData, HueAPIError> {
         let url = self.api_url() + "/resource/light/" + &id.into();
         match self
@@ -916,9 +1243,11 @@ impl BridgeClient {
         &self,
         id: impl Into<String>,
         payload: &serde_json::Value,
-    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+    ) -> Result<SendOutcome, HueAPIError> {
         let url = self.api_url() + "/resource/light/" + &id.into();
-        self.make_request(url, Method::PUT, Some(payload)).await
+        self.make_partial_request(url, Method::PUT, Some(payload))
+            .await
+            .map(|(rids, warnings)| SendOutcome { rids, warnings })
     }
 
     pub(crate) async fn get_room(&self, id: impl Into<String>) -> Result<ZoneData, HueAPIError> {
@@ -963,7 +1292,10 @@ impl BridgeClient {
             .await?;
         match rids.into_iter().nth(0) {
             Some(rid) => Ok(rid),
-            None => Err(HueAPIError::BadDeserialize),
+            None => Err(HueAPIError::BadDeserialize {
+                error: "response contained no resource identifier".into(),
+                body: String::new(),
+            }),
         }
     }
 
@@ -1017,7 +1349,10 @@ impl BridgeClient {
             .await?;
         match rids.into_iter().nth(0) {
             Some(rid) => Ok(rid),
-            None => Err(HueAPIError::BadDeserialize),
+            None => Err(HueAPIError::BadDeserialize {
+                error: "response contained no resource identifier".into(),
+                body: String::new(),
+            }),
         }
     }
 
@@ -1074,7 +1409,10 @@ impl BridgeClient {
             .await?;
         match rids.into_iter().nth(0) {
             Some(rid) => Ok(rid),
-            None => Err(HueAPIError::BadDeserialize),
+            None => Err(HueAPIError::BadDeserialize {
+                error: "response contained no resource identifier".into(),
+                body: String::new(),
+            }),
         }
     }
 
@@ -1190,7 +1528,10 @@ impl BridgeClient {
             .await?;
         match rids.into_iter().nth(0) {
             Some(rid) => Ok(rid),
-            None => Err(HueAPIError::BadDeserialize),
+            None => Err(HueAPIError::BadDeserialize {
+                error: "response contained no resource identifier".into(),
+                body: String::new(),
+            }),
         }
     }
 
@@ -1206,7 +1547,7 @@ impl BridgeClient {
         &self,
         id: impl Into<String>,
     ) -> Result<ZGPConnectivityData, HueAPIError> {
-        let url = self.api_url() + "/resource/zgp_connectivity" + &id.into();
+        let url = self.api_url() + "/resource/zgp_connectivity/" + &id.into();
         match self
             .make_request::<(), Vec<ZGPConnectivityData>>(url, Method::GET, None::<()>)
             .await
@@ -1230,7 +1571,7 @@ impl BridgeClient {
         &self,
         id: impl Into<String>,
     ) -> Result<ZigbeeConnectivityData, HueAPIError> {
-        let url = self.api_url() + "/resource/zigbee_connectivity" + &id.into();
+        let url = self.api_url() + "/resource/zigbee_connectivity/" + &id.into();
         match self
             .make_request::<(), Vec<ZigbeeConnectivityData>>(url, Method::GET, None::<()>)
             .await
@@ -1263,7 +1604,7 @@ impl BridgeClient {
         &self,
         id: impl Into<String>,
     ) -> Result<ZigbeeDeviceDiscoveryData, HueAPIError> {
-        let url = self.api_url() + "/resource/zigbee_device_discovery" + &id.into();
+        let url = self.api_url() + "/resource/zigbee_device_discovery/" + &id.into();
         match self
             .make_request::<(), Vec<ZigbeeDeviceDiscoveryData>>(url, Method::GET, None::<()>)
             .await
@@ -1292,3 +1633,349 @@ impl BridgeClient {
         self.make_request(url, Method::PUT, Some(payload)).await
     }
 }
+
+/// Interpret the `body` of a CLIP response received with `status`. A response
+/// carrying any errors fails with all of their descriptions, unless
+/// `allow_partial` is set and it also carries data, in which case the
+/// descriptions are returned alongside the data. A non-JSON error body is kept
+/// as the only description.
+fn parse_response<Return>(
+    status: u16,
+    body: String,
+    allow_partial: bool,
+) -> Result<(Return, Vec<String>), HueAPIError>
+where
+    Return: serde::de::DeserializeOwned,
+{
+    let success = (200..300).contains(&status);
+    match serde_json::from_str::<HueAPIResponse<Return>>(&body) {
+        Ok(res) => match res.data {
+            Some(data) if success && (allow_partial || res.errors.is_empty()) => Ok((
+                data,
+                res.errors.into_iter().map(|e| e.description).collect(),
+            )),
+            _ => Err(HueAPIError::HueBridgeError {
+                status,
+                descriptions: res.errors.into_iter().map(|e| e.description).collect(),
+            }),
+        },
+        Err(_) if !success => Err(HueAPIError::HueBridgeError {
+            status,
+            descriptions: vec![body],
+        }),
+        Err(e) => {
+            log::error!("{e}");
+            Err(HueAPIError::BadDeserialize {
+                error: e.to_string(),
+                body,
+            })
+        }
+    }
+}
+
+/// Builds the HTTP client. A `root_certificate` is the only root trusted,
+/// while the bundled Hue root CA is trusted alongside the system's roots. With
+/// `verify`, requests to the bridge ID are resolved to its address so the
+/// certificate, which names the bridge ID, can be checked in full.
+fn http_client(
+    connect_timeout: Option<Duration>,
+    root_certificate: Option<&Certificate>,
+    verify: Option<(&str, IpAddr)>,
+    accept_invalid_certs: bool,
+) -> ReqwestClient {
+    let built_in_roots = root_certificate.is_none();
+    let root_certificate = root_certificate
+        .cloned()
+        .unwrap_or_else(|| Certificate::from_pem(include_bytes!("../../hue.pem")).unwrap());
+    let mut builder = ReqwestClient::builder()
+        .tls_built_in_root_certs(built_in_roots)
+        .add_root_certificate(root_certificate)
+        .danger_accept_invalid_certs(accept_invalid_certs);
+    if let Some((bridge_id, addr)) = verify {
+        builder = builder.resolve(bridge_id, SocketAddr::new(addr, 443));
+    }
+    if let Some(timeout) = connect_timeout {
+        builder = builder.connect_timeout(timeout);
+    }
+    builder.build().unwrap()
+}
+
+impl HueTransport for BridgeClient {
+    fn send(&self, request: TransportRequest) -> TransportFuture<'_> {
+        Box::pin(async move {
+            let res = self
+                .request(request.method, request.url)
+                .header("hue-application-key", &request.app_key)
+                .json(&request.body)
+                .send()
+                .await
+                .map_err(request_error)?;
+            let status = res.status().as_u16();
+            let retry_after = res
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse().ok())
+                .map(Duration::from_secs);
+            let body = res.text().await.map_err(request_error)?;
+            Ok(TransportResponse {
+                status,
+                retry_after,
+                body,
+            })
+        })
+    }
+}
+
+/// Whether a failed request is worth retrying.
+fn is_transient(e: &HueAPIError) -> bool {
+    match e {
+        HueAPIError::BadResponse | HueAPIError::Connect(_) | HueAPIError::Timeout => true,
+        HueAPIError::HueBridgeError { status, .. } => *status == 429 || *status == 503,
+        _ => false,
+    }
+}
+
+/// Classify a transport-level [reqwest::Error].
+fn request_error(e: reqwest::Error) -> HueAPIError {
+    if e.is_timeout() {
+        HueAPIError::Timeout
+    } else if e.is_connect() {
+        HueAPIError::Connect(e.to_string())
+    } else if e.is_body() || e.is_decode() {
+        HueAPIError::BadResponse
+    } else {
+        HueAPIError::BadRequest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::MockTransport;
+    use serde_json::json;
+
+    fn client(mock: &MockTransport) -> BridgeClient {
+        let mut client = BridgeClient::new([0, 0, 0, 0], "test-app-key");
+        client.set_transport(Arc::new(mock.clone()));
+        client
+    }
+
+    async fn put_light(client: &BridgeClient) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        let url = client.api_url() + "/resource/light/l1";
+        client
+            .make_request(url, Method::PUT, Some(json!({ "on": { "on": true } })))
+            .await
+    }
+
+    #[tokio::test]
+    async fn multi_status_reports_every_error() {
+        let mock = MockTransport::new();
+        let body = json!({
+            "errors": [
+                { "description": "device (light) is \"soft off\", command (.on) may not have effect" },
+                { "description": "invalid value, 900.0, for parameter, dimming.brightness" },
+            ],
+            "data": [],
+        });
+        mock.respond(Method::PUT, "/resource/light/l1", 207, body.to_string());
+
+        assert_eq!(
+            put_light(&client(&mock)).await,
+            Err(HueAPIError::HueBridgeError {
+                status: 207,
+                descriptions: vec![
+                    "device (light) is \"soft off\", command (.on) may not have effect".to_owned(),
+                    "invalid value, 900.0, for parameter, dimming.brightness".to_owned(),
+                ],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn partial_success_keeps_applied_rids_and_warnings() {
+        let mock = MockTransport::new();
+        let body = json!({
+            "errors": [
+                { "description": "invalid value, 900.0, for parameter, dimming.brightness" },
+            ],
+            "data": [{ "rid": "l1", "rtype": "light" }],
+        });
+        mock.respond(Method::PUT, "/resource/light/l1", 207, body.to_string());
+
+        let payload = json!({ "on": { "on": true }, "dimming": { "brightness": 900.0 } });
+        let outcome = client(&mock).put_light("l1", &payload).await.unwrap();
+
+        assert_eq!(
+            outcome,
+            SendOutcome {
+                rids: vec![ResourceIdentifier {
+                    rid: "l1".into(),
+                    rtype: crate::service::ResourceType::Light,
+                }],
+                warnings: vec!["invalid value, 900.0, for parameter, dimming.brightness".into()],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn status_is_kept_for_non_json_errors() {
+        let mock = MockTransport::new();
+        mock.respond(Method::PUT, "/resource/light/l1", 429, "Too Many Requests");
+
+        assert_eq!(
+            put_light(&client(&mock)).await,
+            Err(HueAPIError::HueBridgeError {
+                status: 429,
+                descriptions: vec!["Too Many Requests".to_owned()],
+            })
+        );
+    }
+
+    fn retrying_client(mock: &MockTransport, max_backoff: Duration) -> BridgeClient {
+        let mut client = client(mock);
+        client.set_retries(2, Duration::from_millis(1), max_backoff, true);
+        client
+    }
+
+    #[tokio::test]
+    async fn transient_failures_are_retried() {
+        let mock = MockTransport::new();
+        mock.respond(
+            Method::PUT,
+            "/resource/light/l1",
+            503,
+            "Service Unavailable",
+        );
+        mock.respond_data(
+            Method::PUT,
+            "/resource/light/l1",
+            json!([{ "rid": "l1", "rtype": "light" }]),
+        );
+
+        let client = retrying_client(&mock, DEFAULT_RETRY_MAX_BACKOFF);
+
+        assert_eq!(put_light(&client).await.unwrap().len(), 1);
+        assert_eq!(mock.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn client_errors_fail_fast() {
+        let mock = MockTransport::new();
+        mock.respond(Method::PUT, "/resource/light/l1", 400, "Bad Request");
+
+        let client = retrying_client(&mock, DEFAULT_RETRY_MAX_BACKOFF);
+
+        assert!(put_light(&client).await.is_err());
+        assert_eq!(mock.requests().len(), 1);
+        assert!(!is_transient(&HueAPIError::BadRequest));
+    }
+
+    #[tokio::test]
+    async fn retry_after_is_capped_at_max_backoff() {
+        let mock = MockTransport::new();
+        let throttled = TransportResponse {
+            status: 429,
+            retry_after: Some(Duration::from_secs(3600)),
+            body: "Too Many Requests".to_owned(),
+        };
+        mock.respond_with(Method::PUT, "/resource/light/l1", throttled);
+        mock.respond_data(
+            Method::PUT,
+            "/resource/light/l1",
+            json!([{ "rid": "l1", "rtype": "light" }]),
+        );
+
+        let client = retrying_client(&mock, Duration::from_millis(10));
+        let res = tokio::time::timeout(Duration::from_secs(5), put_light(&client)).await;
+
+        assert!(res.expect("retry waited for Retry-After").is_ok());
+    }
+
+    #[tokio::test]
+    async fn unresponsive_bridge_times_out() {
+        // Accepts connections into its backlog but never answers them
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = BridgeClient::new([127, 0, 0, 1], "test-app-key");
+        client.set_timeouts(Some(Duration::from_millis(200)), None);
+        let request = TransportRequest {
+            method: Method::GET,
+            url: format!(
+                "https://{}/clip/v2/resource",
+                listener.local_addr().unwrap()
+            )
+            .parse()
+            .unwrap(),
+            app_key: client.app_key().to_owned(),
+            body: None,
+        };
+
+        let res = HueTransport::send(&client, request).await;
+
+        assert!(matches!(res, Err(HueAPIError::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn data_without_errors_is_returned() {
+        let mock = MockTransport::new();
+        let body = json!({ "errors": [], "data": [{ "rid": "l1", "rtype": "light" }] });
+        mock.respond(Method::PUT, "/resource/light/l1", 200, body.to_string());
+
+        assert_eq!(
+            put_light(&client(&mock)).await,
+            Ok(vec![ResourceIdentifier {
+                rid: "l1".into(),
+                rtype: crate::service::ResourceType::Light,
+            }])
+        );
+    }
+
+    #[test]
+    fn certificates_parse() {
+        assert!(Certificate::from_pem(include_bytes!("../../hue.pem")).is_ok());
+        let pem = crate::service::fixtures::BRIDGE_CERTIFICATE;
+        assert!(Certificate::from_pem(pem.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn verification_addresses_the_bridge_id() {
+        let mut client = BridgeClient::new([192, 168, 1, 2], "test-app-key");
+        assert_eq!(client.host(), "192.168.1.2");
+
+        client.set_verification(None, Some("001788FFFE6A1B2C".into()), false);
+
+        assert_eq!(client.host(), "001788fffe6a1b2c");
+        assert_eq!(client.api_url(), "https://001788fffe6a1b2c/clip/v2");
+        assert!(!client.accepts_invalid_certs());
+    }
+
+    #[tokio::test]
+    async fn by_id_urls_separate_type_and_id() {
+        let mock = MockTransport::new();
+        let client = client(&mock);
+        let payload = json!({});
+
+        // Nothing is mocked, so each request fails after being recorded
+        let _ = client.get_zgp_connectivity("abc123").await;
+        let _ = client.get_zigbee_connectivity("abc123").await;
+        let _ = client.put_zigbee_connectivity("abc123", &payload).await;
+        let _ = client.get_zigbee_device_discovery("abc123").await;
+        let _ = client.put_zigbee_device_discovery("abc123", &payload).await;
+
+        let paths: Vec<String> = mock
+            .requests()
+            .iter()
+            .map(|r| r.url.path().to_owned())
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "/clip/v2/resource/zgp_connectivity/abc123",
+                "/clip/v2/resource/zigbee_connectivity/abc123",
+                "/clip/v2/resource/zigbee_connectivity/abc123",
+                "/clip/v2/resource/zigbee_device_discovery/abc123",
+                "/clip/v2/resource/zigbee_device_discovery/abc123",
+            ]
+        );
+    }
+}
@@ -1,12 +1,17 @@
-use crate::service::{
-    AlertEffectType, CIEColor, ColorFeatureBasic, EffectType, GradientMode, GroupDimmingState,
-    OnState, ParseColorError, PowerupOnState, PowerupPresetType, ProductArchetype,
-    ResourceIdentifier, SceneAction, ScenePalette, SceneStatus, Schedule, SignalType,
-    TimedEffectType, ZigbeeChannel, ZoneArchetype,
+use crate::{
+    api::HueAPIError,
+    service::{
+        kelvin_to_mirek, AlertEffectType, CIEColor, ColorFeatureBasic, EffectType, GradientMode,
+        GradientPoint, GroupDimmingState, OnState, ParseColorError, PowerupOnState,
+        PowerupPresetType, ProductArchetype, ResourceIdentifier, SceneAction, ScenePalette,
+        SceneStatus, Schedule, SignalType, TimedEffectType, ZigbeeChannel, ZoneArchetype,
+        MIREK_MAXIMUM, MIREK_MINIMUM,
+    },
 };
 use json_patch::merge;
 use serde::{ser::SerializeMap, Serialize};
 use serde_json::json;
+use std::{borrow::Borrow, fmt};
 
 /// A helper function to merge types serializeable to a JSON object.
 pub fn merge_commands<S: Serialize>(commands: &[S]) -> serde_json::Value {
@@ -29,6 +34,8 @@ pub enum CommandType {
     GeofenceClient(GeofenceClientCommand),
     Geolocation(GeolocationCommand),
     GroupedLight(GroupCommand),
+    GroupedLightLevel(BasicCommand),
+    GroupedMotion(BasicCommand),
     HomeKit(HomeKitCommand),
     Light(String, LightCommand),
     LightLevel(BasicCommand),
@@ -72,7 +79,36 @@ pub enum BehaviorInstanceCommand {
 
 pub struct BridgeCommand;
 
-pub struct ButtonCommand;
+/// Commands for a [Button](crate::service::Button).
+#[derive(Debug)]
+pub enum ButtonCommand {
+    /// Interval in ms between [ButtonEvent::Repeat](crate::service::ButtonEvent::Repeat)
+    /// events while the button is held.
+    RepeatInterval(usize),
+}
+
+impl ButtonCommand {
+    /// The JSON body [Button::send](crate::service::Button::send) would put
+    /// for `commands`, without sending it.
+    pub fn preview(commands: &[ButtonCommand]) -> serde_json::Value {
+        merge_commands(commands)
+    }
+}
+
+impl Serialize for ButtonCommand {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        match self {
+            Self::RepeatInterval(interval) => {
+                map.serialize_entry("button", &json!({ "repeat_interval": interval }))?;
+            }
+        }
+        map.end()
+    }
+}
 
 pub struct CameraMotionCommand;
 
@@ -94,6 +130,14 @@ pub enum DeviceCommand {
     UserTest(bool),
 }
 
+impl DeviceCommand {
+    /// The JSON body [Device::send](crate::service::Device::send) would put
+    /// for `commands`, without sending it.
+    pub fn preview(commands: &[DeviceCommand]) -> serde_json::Value {
+        merge_commands(commands)
+    }
+}
+
 impl Serialize for DeviceCommand {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -105,7 +149,14 @@ impl Serialize for DeviceCommand {
                 map.serialize_entry("identify", &json!({ "action": "identify" }))?;
             }
             Self::Metadata { name, archetype } => {
-                map.serialize_entry("metadata", &json!({ "name": name, "archetype": archetype }))?;
+                let mut metadata = json!({});
+                if let Some(name) = name {
+                    metadata["name"] = json!(name);
+                }
+                if let Some(archetype) = archetype {
+                    metadata["archetype"] = json!(archetype);
+                }
+                map.serialize_entry("metadata", &metadata)?;
             }
             Self::UserTest(u) => {
                 map.serialize_entry("usertest", &json!({ "usertest": u }))?;
@@ -115,6 +166,8 @@ impl Serialize for DeviceCommand {
     }
 }
 
+/// Commands for a [DevicePower](crate::service::DevicePower). The resource
+/// exposes no writable properties, so there is nothing to send.
 pub struct DevicePowerCommand;
 
 /// Commands for an [EntertainmentConfiguration](crate::service::EntertainmentConfiguration).
@@ -159,7 +212,7 @@ pub enum GeolocationCommand {
 pub enum GroupCommand {
     /// Sets the alert effect for all members.
     Alert(AlertEffectType),
-    /// CIE XY gamut position
+    /// CIE XY gamut position. Out-of-range coordinates are clamped when sent.
     Color {
         /// X position in color gamut (`0.0`, `1.0`)
         x: f32,
@@ -192,6 +245,19 @@ pub enum GroupCommand {
     Dynamics {
         /// Duration of a light transition or timed effects in ms.
         duration: Option<usize>,
+        /// Speed of dynamic palette or effect. Omitted from the request when
+        /// [None], as not all bridge versions accept it for groups.
+        speed: Option<f32>,
+    },
+    /// Effect applied to every member. Members that do not support the effect
+    /// ignore it, and older bridge firmware may reject it for groups.
+    Effect(EffectType),
+    /// Gradient applied to every member that supports gradients. For control
+    /// of the gradient points through a PUT a minimum of 2 points need to be
+    /// provided.
+    Gradient {
+        points: Vec<CIEColor>,
+        mode: Option<GradientMode>,
     },
     /// Joined power state of this group.
     On(bool),
@@ -206,9 +272,58 @@ pub enum GroupCommand {
         /// List of colors (1 or 2) to apply to the signal (not supported by all signals).
         colors: Option<SignalColor>,
     },
+    /// Timed effect applied to every member, as with
+    /// [LightCommand::TimedEffect].
+    TimedEffect {
+        effect: TimedEffectType,
+        /// Duration in ms, mandatory except for [TimedEffectType::NoEffect].
+        duration: Option<usize>,
+    },
 }
 
 impl GroupCommand {
+    /// The JSON body [Group::send](crate::service::Group::send) would put
+    /// for `commands`, without sending it.
+    pub fn preview(commands: &[GroupCommand]) -> serde_json::Value {
+        merge_commands(commands)
+    }
+
+    /// Out-of-range coordinates are clamped to \[`0.0`, `1.0`\].
+    pub fn color(x: f32, y: f32) -> GroupCommand {
+        let cie = CIEColor::new(x, y);
+        GroupCommand::Color { x: cie.x, y: cie.y }
+    }
+
+    /// Color temperature in Kelvin, converted to mirek and clamped to
+    /// \[`153`, `500`\] mirek, or about \[`2000`, `6536`\] K.
+    pub fn color_temp_kelvin(kelvin: u32) -> GroupCommand {
+        GroupCommand::ColorTemp(kelvin_to_mirek(kelvin, MIREK_MINIMUM, MIREK_MAXIMUM))
+    }
+
+    /// Rejects command slices whose merged result would be undefined, namely
+    /// [GroupCommand::Signaling] combined with [GroupCommand::Color],
+    /// [GroupCommand::ColorTemp], or [GroupCommand::ColorTempDelta], since
+    /// signaling drives its own colors.
+    pub fn validate<C: Borrow<GroupCommand>>(commands: &[C]) -> Result<(), HueAPIError> {
+        let signaling = commands
+            .iter()
+            .any(|c| matches!(c.borrow(), GroupCommand::Signaling { .. }));
+        let color = commands.iter().any(|c| {
+            matches!(
+                c.borrow(),
+                GroupCommand::Color { .. }
+                    | GroupCommand::ColorTemp(_)
+                    | GroupCommand::ColorTempDelta { .. }
+            )
+        });
+        if signaling && color {
+            return Err(HueAPIError::InvalidInput(
+                "signaling cannot be combined with color or color temperature".to_owned(),
+            ));
+        }
+        Ok(())
+    }
+
     pub fn color_from_rgb(rgb: [u8; 3]) -> GroupCommand {
         let cie = CIEColor::from_rgb(rgb);
         GroupCommand::Color { x: cie.x, y: cie.y }
@@ -222,6 +337,14 @@ impl GroupCommand {
     }
 }
 
+/// Formats the command as the JSON sent to the bridge.
+impl fmt::Display for GroupCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
 impl Serialize for GroupCommand {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -233,7 +356,8 @@ impl Serialize for GroupCommand {
                 map.serialize_entry("alert", &json!({ "action": effect }))?;
             }
             Self::Color { x, y } => {
-                map.serialize_entry("color", &json!({ "xy": { "x": x, "y": y } }))?;
+                let xy = CIEColor::new(*x, *y);
+                map.serialize_entry("color", &json!({ "xy": { "x": xy.x, "y": xy.y } }))?;
             }
             Self::ColorTemp(mirek) => {
                 map.serialize_entry("color_temperature", &json!({ "mirek": mirek }))?;
@@ -259,8 +383,24 @@ impl Serialize for GroupCommand {
                     &json!({ "action": action, "brightness_delta": brightness_delta }),
                 )?;
             }
-            Self::Dynamics { duration } => {
-                map.serialize_entry("dynamics", &json!({ "duration": duration }))?;
+            Self::Dynamics { duration, speed } => {
+                let mut dynamics = json!({ "duration": duration });
+                if let Some(speed) = speed {
+                    dynamics["speed"] = json!(speed);
+                }
+                map.serialize_entry("dynamics", &dynamics)?;
+            }
+            Self::Effect(effect) => {
+                map.serialize_entry("effects", &json!({ "effect": effect }))?;
+            }
+            Self::Gradient { points, mode } => {
+                let points = points
+                    .iter()
+                    .map(|xy| GradientPoint {
+                        color: ColorFeatureBasic { xy: *xy },
+                    })
+                    .collect::<Vec<GradientPoint>>();
+                map.serialize_entry("gradient", &json!({ "points": points, "mode": mode }))?;
             }
             Self::Signaling {
                 signal,
@@ -279,6 +419,12 @@ impl Serialize for GroupCommand {
             Self::On(on) => {
                 map.serialize_entry("on", &OnState { on: *on })?;
             }
+            Self::TimedEffect { effect, duration } => {
+                map.serialize_entry(
+                    "timed_effects",
+                    &json!({ "effect": effect, "duration": duration }),
+                )?;
+            }
         }
         map.end()
     }
@@ -308,7 +454,7 @@ impl Serialize for HomeKitCommand {
 pub enum LightCommand {
     /// Sets the alert effect for this light.
     Alert(AlertEffectType),
-    /// CIE XY gamut position
+    /// CIE XY gamut position. Out-of-range coordinates are clamped when sent.
     Color {
         /// X position in color gamut (`0`, `1`)
         x: f32,
@@ -401,6 +547,25 @@ pub enum LightCommand {
 }
 
 impl LightCommand {
+    /// The JSON body [Light::send](crate::service::Light::send) would put
+    /// for `commands`, without sending it.
+    pub fn preview(commands: &[LightCommand]) -> serde_json::Value {
+        merge_commands(commands)
+    }
+
+    /// Out-of-range coordinates are clamped to \[`0.0`, `1.0`\].
+    pub fn color(x: f32, y: f32) -> LightCommand {
+        let cie = CIEColor::new(x, y);
+        LightCommand::Color { x: cie.x, y: cie.y }
+    }
+
+    /// Color temperature in Kelvin, converted to mirek and clamped to
+    /// \[`153`, `500`\] mirek, or about \[`2000`, `6536`\] K. Use [Light::set_color_temp_kelvin](crate::service::Light::set_color_temp_kelvin)
+    /// to clamp to the range a specific light supports instead.
+    pub fn color_temp_kelvin(kelvin: u32) -> LightCommand {
+        LightCommand::ColorTemp(kelvin_to_mirek(kelvin, MIREK_MINIMUM, MIREK_MAXIMUM))
+    }
+
     pub fn color_from_rgb(rgb: [u8; 3]) -> LightCommand {
         let cie = CIEColor::from_rgb(rgb);
         LightCommand::Color { x: cie.x, y: cie.y }
@@ -433,7 +598,7 @@ impl Serialize for PowerupColor {
         let mut map = serializer.serialize_map(None)?;
         map.serialize_entry("mode", &self.mode)?;
         if let Some(xy) = &self.color {
-            map.serialize_entry("color", &ColorFeatureBasic { xy: xy.clone() })?;
+            map.serialize_entry("color", &ColorFeatureBasic { xy: *xy })?;
         }
         if let Some(temp) = self.color_temperature {
             map.serialize_entry("color_temperature", &json!({ "mirek": temp }))?;
@@ -501,21 +666,23 @@ impl Serialize for SignalColor {
         S: serde::Serializer,
     {
         match self {
-            SignalColor::One(inner) => {
-                serializer.collect_seq([ColorFeatureBasic { xy: inner.clone() }])
-            }
+            SignalColor::One(inner) => serializer.collect_seq([ColorFeatureBasic { xy: *inner }]),
             SignalColor::Two(inner_a, inner_b) => serializer.collect_seq([
-                ColorFeatureBasic {
-                    xy: inner_a.clone(),
-                },
-                ColorFeatureBasic {
-                    xy: inner_b.clone(),
-                },
+                ColorFeatureBasic { xy: *inner_a },
+                ColorFeatureBasic { xy: *inner_b },
             ]),
         }
     }
 }
 
+/// Formats the command as the JSON sent to the bridge.
+impl fmt::Display for LightCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let json = serde_json::to_string(self).map_err(|_| fmt::Error)?;
+        f.write_str(&json)
+    }
+}
+
 impl Serialize for LightCommand {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -527,7 +694,8 @@ impl Serialize for LightCommand {
                 map.serialize_entry("alert", &json!({ "action": effect }))?;
             }
             Self::Color { x, y } => {
-                map.serialize_entry("color", &json!({ "xy": { "x": x, "y": y } }))?;
+                let xy = CIEColor::new(*x, *y);
+                map.serialize_entry("color", &json!({ "xy": { "x": xy.x, "y": xy.y } }))?;
             }
             Self::ColorTemp(mirek) => {
                 map.serialize_entry("color_temperature", &json!({ "mirek": mirek }))?;
@@ -562,15 +730,24 @@ impl Serialize for LightCommand {
             Self::Gradient { points, mode } => {
                 let points = points
                     .iter()
-                    .map(|xy| ColorFeatureBasic { xy: xy.clone() })
-                    .collect::<Vec<ColorFeatureBasic>>();
+                    .map(|xy| GradientPoint {
+                        color: ColorFeatureBasic { xy: *xy },
+                    })
+                    .collect::<Vec<GradientPoint>>();
                 map.serialize_entry("gradient", &json!({ "points": points, "mode": mode }))?;
             }
             Self::Identify => {
                 map.serialize_entry("identify", &json!({ "action": "identify" }))?;
             }
             Self::Metadata { name, archetype } => {
-                map.serialize_entry("metadata", &json!({ "name": name, "archetype": archetype }))?;
+                let mut metadata = json!({});
+                if let Some(name) = name {
+                    metadata["name"] = json!(name);
+                }
+                if let Some(archetype) = archetype {
+                    metadata["archetype"] = json!(archetype);
+                }
+                map.serialize_entry("metadata", &metadata)?;
             }
             Self::On(on) => {
                 map.serialize_entry("on", &OnState { on: *on })?;
@@ -659,6 +836,14 @@ pub enum MotionCommand {
     Sensitivity(usize),
 }
 
+impl MotionCommand {
+    /// The JSON body [Motion::send](crate::service::Motion::send) would put
+    /// for `commands`, without sending it.
+    pub fn preview(commands: &[MotionCommand]) -> serde_json::Value {
+        merge_commands(commands)
+    }
+}
+
 impl Serialize for MotionCommand {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -690,6 +875,14 @@ pub enum ZoneCommand {
     },
 }
 
+impl ZoneCommand {
+    /// The JSON body [Zone::send](crate::service::Zone::send) would put
+    /// for `commands`, without sending it.
+    pub fn preview(commands: &[ZoneCommand]) -> serde_json::Value {
+        merge_commands(commands)
+    }
+}
+
 impl Serialize for ZoneCommand {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -701,7 +894,14 @@ impl Serialize for ZoneCommand {
                 map.serialize_entry("children", rids)?;
             }
             Self::Metadata { name, archetype } => {
-                map.serialize_entry("metadata", &json!({ "name": name, "archetype": archetype }))?;
+                let mut metadata = json!({});
+                if let Some(name) = name {
+                    metadata["name"] = json!(name);
+                }
+                if let Some(archetype) = archetype {
+                    metadata["archetype"] = json!(archetype);
+                }
+                map.serialize_entry("metadata", &metadata)?;
             }
         }
         map.end()
@@ -739,6 +939,14 @@ pub enum SceneCommand {
     Speed(f32),
 }
 
+impl SceneCommand {
+    /// The JSON body [Scene::send](crate::service::Scene::send) would put
+    /// for `commands`, without sending it.
+    pub fn preview(commands: &[SceneCommand]) -> serde_json::Value {
+        merge_commands(commands)
+    }
+}
+
 impl Serialize for SceneCommand {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -792,6 +1000,12 @@ pub enum SmartSceneCommand {
 }
 
 impl SmartSceneCommand {
+    /// The JSON body [SmartScene::send](crate::service::SmartScene::send) would put
+    /// for `commands`, without sending it.
+    pub fn preview(commands: &[SmartSceneCommand]) -> serde_json::Value {
+        merge_commands(commands)
+    }
+
     pub fn create_schedule() -> Schedule {
         Schedule::new()
     }
@@ -880,3 +1094,93 @@ impl Serialize for ZigbeeDeviceDiscoveryCommand {
         map.end()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn to_json(command: impl Serialize) -> Value {
+        serde_json::to_value(command).unwrap()
+    }
+
+    #[test]
+    fn group_effect_matches_light_effect() {
+        let group = to_json(GroupCommand::Effect(EffectType::Candle));
+        assert_eq!(group, json!({ "effects": { "effect": "candle" } }));
+        assert_eq!(group, to_json(LightCommand::Effect(EffectType::Candle)));
+    }
+
+    #[test]
+    fn group_timed_effect_matches_light_timed_effect() {
+        let group = to_json(GroupCommand::TimedEffect {
+            effect: TimedEffectType::Sunrise,
+            duration: Some(60_000),
+        });
+        let light = to_json(LightCommand::TimedEffect {
+            effect: TimedEffectType::Sunrise,
+            duration: Some(60_000),
+        });
+        assert_eq!(
+            group,
+            json!({ "timed_effects": { "effect": "sunrise", "duration": 60000 } })
+        );
+        assert_eq!(group, light);
+    }
+
+    #[test]
+    fn group_gradient_matches_light_gradient() {
+        let points = vec![CIEColor::new(0.5, 0.25), CIEColor::new(0.25, 0.5)];
+        let group = to_json(GroupCommand::Gradient {
+            points: points.clone(),
+            mode: Some(GradientMode::InterpolatedPaletteMirrored),
+        });
+        let light = to_json(LightCommand::Gradient {
+            points,
+            mode: Some(GradientMode::InterpolatedPaletteMirrored),
+        });
+        assert_eq!(
+            group,
+            json!({
+                "gradient": {
+                    "points": [
+                        { "color": { "xy": { "x": 0.5, "y": 0.25 } } },
+                        { "color": { "xy": { "x": 0.25, "y": 0.5 } } },
+                    ],
+                    "mode": "interpolated_palette_mirrored",
+                }
+            })
+        );
+        assert_eq!(group, light);
+    }
+
+    #[tokio::test]
+    async fn group_effects_are_put_to_the_grouped_light() {
+        use crate::service::fixtures;
+        use reqwest::Method;
+
+        let (mock, bridge) = fixtures::refreshed_bridge(vec![fixtures::grouped_light(
+            "g1",
+            fixtures::rid("room", "r1"),
+            true,
+        )])
+        .await;
+        mock.respond_data(
+            Method::PUT,
+            "/resource/grouped_light/g1",
+            fixtures::written("grouped_light", "g1"),
+        );
+
+        let group = bridge.group("g1").unwrap();
+        group
+            .send([GroupCommand::Effect(EffectType::Candle)])
+            .await
+            .unwrap();
+
+        let sent = mock.requests_to(Method::PUT, "/resource/grouped_light/g1");
+        assert_eq!(
+            sent[0].body,
+            Some(to_json(LightCommand::Effect(EffectType::Candle)))
+        );
+    }
+}
@@ -24,7 +24,7 @@ pub enum HueEventType {
 pub enum HueEventData {
     #[serde(rename = "auth_v1")]
     AuthV1,
-    BehaviorInstance,
+    BehaviorInstance(serde_json::Value),
     BehaviorScript(serde_json::Value),
     Bridge(serde_json::Value),
     BridgeHome(serde_json::Value),
@@ -41,6 +41,8 @@ pub enum HueEventData {
     Geolocation(serde_json::Value),
     #[serde(rename = "grouped_light")]
     Group(serde_json::Value),
+    GroupedLightLevel(serde_json::Value),
+    GroupedMotion(serde_json::Value),
     #[serde(rename = "homekit")]
     HomeKit(serde_json::Value),
     Light(serde_json::Value),
@@ -49,6 +51,7 @@ pub enum HueEventData {
     MatterFabric(serde_json::Value),
     Motion(serde_json::Value),
     PublicImage,
+    Recipe,
     RelativeRotary(serde_json::Value),
     Room(serde_json::Value),
     Scene(serde_json::Value),
@@ -57,6 +60,7 @@ pub enum HueEventData {
     #[serde(rename = "taurus_7455")]
     Taurus7455,
     Temperature(serde_json::Value),
+    #[serde(rename = "zgp_connectivity")]
     ZGPConnectivity(serde_json::Value),
     ZigbeeBridgeConnectivity,
     ZigbeeConnectivity(serde_json::Value),
@@ -13,6 +13,7 @@
 //! - App key creation
 //! - Light, Group, and Scene control
 //! - Schedule and Smart Scene management
+//! - Typed timestamps on sensor and light state using [chrono](https://docs.rs/chrono/0.4), requires the `chrono` feature
 //!
 //! It does not yet support the following features:
 //!
@@ -65,10 +66,10 @@
 //!         .build()
 //!         .poll(Duration::from_secs(30))
 //!         .await;
-//!     // This is your App Key, it should be saved for future sessions
+//!     // These are your App Key and Client Key, they should be saved for future sessions
 //!     // NOTE: press the `Link Button` on the Hues Bridge before attempting
 //!     // to create new app credentials.
-//!     let key = bridge.create_app("my_app", "my_instance").await.unwrap();
+//!     let credentials = bridge.create_app("my_app", "my_instance").await.unwrap();
 //!
 //!     // Blink each light to confirm you're registered!
 //!     for light in bridge.lights() {
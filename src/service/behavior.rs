@@ -4,17 +4,18 @@ use crate::{
     service::{BasicMetadata, Bridge, ResourceIdentifier, ResourceType},
 };
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 /// A static template for scripting behavior that can be instantiated as a
 /// [BehaviorInstance].
 #[derive(Debug)]
 pub struct BehaviorScript {
-    data: BehaviorScriptData,
+    data: Arc<BehaviorScriptData>,
 }
 
 impl BehaviorScript {
-    pub fn new(data: BehaviorScriptData) -> Self {
-        BehaviorScript { data }
+    pub fn new(data: impl Into<Arc<BehaviorScriptData>>) -> Self {
+        BehaviorScript { data: data.into() }
     }
 
     pub fn data(&self) -> &BehaviorScriptData {
@@ -36,10 +37,109 @@ impl BehaviorScript {
     pub fn category(&self) -> &BehaviorScriptType {
         &self.data.metadata.category
     }
+
+    pub fn metadata(&self) -> &BehaviorScriptMetadata {
+        &self.data.metadata
+    }
+
+    /// Schema for the configuration of a [BehaviorInstance] of this script.
+    pub fn configuration_schema(&self) -> &BehaviorSchema {
+        &self.data.configuration_schema
+    }
+
+    /// Schema for the triggers accepted by a [BehaviorInstance] of this script.
+    pub fn trigger_schema(&self) -> &BehaviorSchema {
+        &self.data.trigger_schema
+    }
+
+    /// Schema for the state reported by a [BehaviorInstance] of this script.
+    pub fn state_schema(&self) -> &BehaviorSchema {
+        &self.data.state_schema
+    }
+
+    /// Checks `configuration` against the script's configuration schema before
+    /// it is sent to the bridge, returning a description of each problem found.
+    ///
+    /// Only `type`, `enum`, `required`, `properties`, and `items` are checked,
+    /// and a schema given as a [BehaviorSchema::Ref] is not resolved, so
+    /// passing this check does not guarantee the bridge will accept it.
+    pub fn validate_configuration(
+        &self,
+        configuration: &serde_json::Value,
+    ) -> Result<(), Vec<String>> {
+        let BehaviorSchema::Lit(schema) = &self.data.configuration_schema else {
+            return Ok(());
+        };
+        let mut errors = vec![];
+        check_schema(schema, configuration, "configuration", &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn check_schema(
+    schema: &serde_json::Value,
+    value: &serde_json::Value,
+    path: &str,
+    errors: &mut Vec<String>,
+) {
+    use serde_json::Value;
+
+    let type_matches = |t: &Value| match t.as_str() {
+        Some("object") => value.is_object(),
+        Some("array") => value.is_array(),
+        Some("string") => value.is_string(),
+        Some("number") => value.is_number(),
+        Some("integer") => value.is_i64() || value.is_u64(),
+        Some("boolean") => value.is_boolean(),
+        Some("null") => value.is_null(),
+        _ => true,
+    };
+    let valid_type = match schema.get("type") {
+        Some(Value::Array(types)) => types.iter().any(type_matches),
+        Some(t) => type_matches(t),
+        None => true,
+    };
+    if !valid_type {
+        errors.push(format!("{path}: expected type {}", schema["type"]));
+        return;
+    }
+
+    if let Some(Value::Array(options)) = schema.get("enum") {
+        if !options.contains(value) {
+            errors.push(format!("{path}: {value} is not one of {}", schema["enum"]));
+        }
+    }
+
+    if let Value::Object(fields) = value {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !fields.contains_key(name) {
+                    errors.push(format!("{path}: missing required property \"{name}\""));
+                }
+            }
+        }
+        if let Some(Value::Object(properties)) = schema.get("properties") {
+            for (name, field) in fields {
+                if let Some(property) = properties.get(name) {
+                    check_schema(property, field, &format!("{path}.{name}"), errors);
+                }
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (i, item) in items.iter().enumerate() {
+            check_schema(item_schema, item, &format!("{path}[{i}]"), errors);
+        }
+    }
 }
 
 /// Internal representation of a [BehaviorScript].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct BehaviorScriptData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -71,27 +171,27 @@ impl BehaviorScriptData {
     }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct BehaviorScriptMetadata {
     /// Human readable name of a resource.
     pub name: Option<String>,
     pub category: BehaviorScriptType,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum BehaviorSchema {
     Ref(SchemaRef),
     Lit(serde_json::Value),
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SchemaRef {
     #[serde(rename = "$ref")]
     pub sref: String,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum BehaviorScriptType {
     Automation,
@@ -113,12 +213,15 @@ pub enum BehaviorScriptType {
 /// under given conditions and on given schedules.
 pub struct BehaviorInstance<'a> {
     bridge: &'a Bridge,
-    data: BehaviorInstanceData,
+    data: Arc<BehaviorInstanceData>,
 }
 
 impl<'a> BehaviorInstance<'a> {
-    pub fn new(bridge: &'a Bridge, data: BehaviorInstanceData) -> Self {
-        BehaviorInstance { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<BehaviorInstanceData>>) -> Self {
+        BehaviorInstance {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &BehaviorInstanceData {
@@ -137,11 +240,36 @@ impl<'a> BehaviorInstance<'a> {
         self.data.enabled
     }
 
-    pub fn builder(
-        script_id: impl Into<String>,
+    pub async fn enable(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.send(&[BehaviorInstanceCommand::Enabled(true)]).await
+    }
+
+    pub async fn disable(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.send(&[BehaviorInstanceCommand::Enabled(false)]).await
+    }
+
+    /// Replaces the script configuration, which the bridge validates against
+    /// the [BehaviorScript]'s configuration schema.
+    pub async fn set_configuration(
+        &self,
         configuration: serde_json::Value,
-    ) -> BehaviorInstanceBuilder {
-        BehaviorInstanceBuilder::new(script_id, configuration)
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.send(&[BehaviorInstanceCommand::Configuration(configuration)])
+            .await
+    }
+
+    /// Asks the script to take an action, which the bridge validates against
+    /// the [BehaviorScript]'s trigger schema.
+    pub async fn trigger(
+        &self,
+        trigger: serde_json::Value,
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.send(&[BehaviorInstanceCommand::Trigger(trigger)])
+            .await
+    }
+
+    pub fn builder(script_id: impl Into<String>) -> BehaviorInstanceBuilder {
+        BehaviorInstanceBuilder::new(script_id)
     }
 
     pub async fn send(
@@ -157,7 +285,7 @@ impl<'a> BehaviorInstance<'a> {
 }
 
 /// Internal representation of a [BehaviorInstance].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct BehaviorInstanceData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -191,20 +319,20 @@ impl BehaviorInstanceData {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ResourceDependee {
     target: ResourceIdentifier,
     level: ResourceDependeeImportance,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ResourceDependeeImportance {
     Critical,
     NonCritical,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum BehaviorInstanceStatus {
     Initializing,
@@ -225,16 +353,25 @@ pub struct BehaviorInstanceBuilder {
 }
 
 impl BehaviorInstanceBuilder {
-    pub fn new(script_id: impl Into<String>, configuration: serde_json::Value) -> Self {
+    /// A disabled, unnamed instance of the script `script_id`, with an empty
+    /// configuration until [BehaviorInstanceBuilder::configuration] is set.
+    pub fn new(script_id: impl Into<String>) -> Self {
         BehaviorInstanceBuilder {
             script_id: script_id.into(),
             enabled: false,
-            configuration,
+            configuration: serde_json::json!({}),
             metadata: BasicMetadata { name: None },
             migrated_from: None,
         }
     }
 
+    /// Script configuration, validated by the bridge against the
+    /// [BehaviorScript]'s configuration schema.
+    pub fn configuration(mut self, configuration: serde_json::Value) -> Self {
+        self.configuration = configuration;
+        self
+    }
+
     pub fn name(mut self, name: impl Into<String>) -> Self {
         self.metadata.name = Some(name.into());
         self
@@ -250,3 +387,222 @@ impl BehaviorInstanceBuilder {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::fixtures;
+    use reqwest::Method;
+    use serde_json::{json, Value};
+
+    const WAKE_UP_SCRIPT: &str = "ff8957e3-2eb9-4699-a0c8-ad2cb3ede704";
+
+    /// Wakes the bedroom with a 30 minute sunrise on weekdays at 7:00.
+    fn wake_up() -> Value {
+        json!({
+            "end_brightness": 100.0,
+            "fade_in_duration": { "seconds": 1800 },
+            "style": "sunrise",
+            "turn_lights_off_after": { "seconds": 7200 },
+            "when": {
+                "recurrence_days": ["monday", "tuesday", "wednesday", "thursday", "friday"],
+                "time_point": { "type": "time", "time": { "hour": 7, "minute": 0 } },
+            },
+            "where": [{ "group": fixtures::rid("room", "r1") }],
+        })
+    }
+
+    fn instance(id: &str, configuration: Value) -> Value {
+        json!({
+            "type": "behavior_instance",
+            "id": id,
+            "script_id": WAKE_UP_SCRIPT,
+            "enabled": true,
+            "configuration": configuration,
+            "dependees": [],
+            "status": "running",
+            "metadata": { "name": "Weekday wake up" },
+        })
+    }
+
+    #[test]
+    fn builder_serializes_a_wake_up_automation() {
+        let builder = BehaviorInstanceBuilder::new(WAKE_UP_SCRIPT)
+            .configuration(wake_up())
+            .name("Weekday wake up")
+            .enabled(true);
+
+        assert_eq!(
+            serde_json::to_value(builder).unwrap(),
+            json!({
+                "script_id": WAKE_UP_SCRIPT,
+                "enabled": true,
+                "configuration": wake_up(),
+                "metadata": { "name": "Weekday wake up" },
+            })
+        );
+    }
+
+    #[test]
+    fn builder_defaults_to_disabled_and_unnamed() {
+        let json = serde_json::to_value(BehaviorInstance::builder(WAKE_UP_SCRIPT)).unwrap();
+
+        assert_eq!(json["enabled"], false);
+        assert_eq!(json["configuration"], json!({}));
+        assert_eq!(json["metadata"], json!({}));
+        assert!(json.get("migrated_from").is_none());
+    }
+
+    #[tokio::test]
+    async fn create_posts_the_builder_and_caches_the_result() {
+        let (mock, bridge) = fixtures::refreshed_bridge(vec![]).await;
+        mock.respond_data(
+            Method::POST,
+            "/resource/behavior_instance",
+            fixtures::written("behavior_instance", "bi1"),
+        );
+        mock.respond_data(
+            Method::GET,
+            "/resource/behavior_instance/bi1",
+            json!([instance("bi1", wake_up())]),
+        );
+
+        let builder = BehaviorInstance::builder(WAKE_UP_SCRIPT)
+            .configuration(wake_up())
+            .enabled(true);
+        let created = bridge.create_behavior_instance(builder).await.unwrap();
+
+        assert_eq!(created.id(), "bi1");
+        assert!(bridge.behavior_instance("bi1").is_some());
+        let posted = mock.requests_to(Method::POST, "/resource/behavior_instance");
+        assert_eq!(posted[0].body.as_ref().unwrap()["configuration"], wake_up());
+    }
+
+    #[tokio::test]
+    async fn set_configuration_puts_the_new_configuration() {
+        let (mock, bridge) = fixtures::refreshed_bridge(vec![instance("bi1", wake_up())]).await;
+        mock.respond_data(
+            Method::PUT,
+            "/resource/behavior_instance/bi1",
+            fixtures::written("behavior_instance", "bi1"),
+        );
+        let mut configuration = wake_up();
+        configuration["end_brightness"] = json!(60.0);
+
+        bridge
+            .behavior_instance("bi1")
+            .unwrap()
+            .set_configuration(configuration.clone())
+            .await
+            .unwrap();
+
+        let sent = mock.requests_to(Method::PUT, "/resource/behavior_instance/bi1");
+        assert_eq!(
+            sent[0].body,
+            Some(json!({ "configuration": configuration }))
+        );
+    }
+
+    /// A wake up script, with its schemas trimmed to a few properties.
+    fn wake_up_script() -> BehaviorScript {
+        let data = serde_json::from_value(json!({
+            "id": WAKE_UP_SCRIPT,
+            "description": "Get ready for nice wake up",
+            "configuration_schema": {
+                "type": "object",
+                "required": ["where", "fade_in_duration", "style"],
+                "properties": {
+                    "end_brightness": { "type": "number" },
+                    "fade_in_duration": {
+                        "type": "object",
+                        "required": ["seconds"],
+                        "properties": { "seconds": { "type": "integer" } },
+                    },
+                    "style": { "type": "string", "enum": ["sunrise", "basic"] },
+                    "turn_lights_off_after": { "type": "object" },
+                    "when": { "type": "object" },
+                    "where": {
+                        "type": "array",
+                        "items": { "type": "object", "required": ["group"] },
+                    },
+                },
+            },
+            "trigger_schema": { "$ref": "trigger.json#" },
+            "state_schema": {},
+            "version": "0.0.1",
+            "metadata": { "name": "Basic wake up routine", "category": "automation" },
+            "supported_features": ["style_sunrise", "intensity"],
+            "max_number_instances": 10,
+        }))
+        .unwrap();
+        BehaviorScript::new(Arc::new(data))
+    }
+
+    #[test]
+    fn script_exposes_schemas_and_metadata() {
+        let script = wake_up_script();
+
+        assert_eq!(script.name(), Some("Basic wake up routine"));
+        assert_eq!(script.category(), &BehaviorScriptType::Automation);
+        assert!(matches!(
+            script.configuration_schema(),
+            BehaviorSchema::Lit(schema) if schema["required"][0] == "where"
+        ));
+        assert_eq!(
+            script.trigger_schema(),
+            &BehaviorSchema::Ref(SchemaRef {
+                sref: "trigger.json#".into()
+            })
+        );
+        assert_eq!(script.state_schema(), &BehaviorSchema::Lit(json!({})));
+    }
+
+    #[test]
+    fn valid_configuration_passes() {
+        assert_eq!(wake_up_script().validate_configuration(&wake_up()), Ok(()));
+    }
+
+    #[test]
+    fn invalid_configuration_reports_each_problem() {
+        let mut configuration = wake_up();
+        configuration["style"] = json!("sunset");
+        configuration["fade_in_duration"] = json!({ "seconds": "1800" });
+        configuration["where"] = json!([{ "zone": fixtures::rid("zone", "z1") }]);
+        configuration
+            .as_object_mut()
+            .unwrap()
+            .remove("end_brightness");
+
+        let errors = wake_up_script()
+            .validate_configuration(&configuration)
+            .unwrap_err();
+
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(errors.iter().any(|e| e.starts_with("configuration.style:")));
+        assert!(errors
+            .iter()
+            .any(|e| e.starts_with("configuration.fade_in_duration.seconds: expected type")));
+        assert!(errors
+            .iter()
+            .any(|e| e == "configuration.where[0]: missing required property \"group\""));
+    }
+
+    #[test]
+    fn configuration_of_the_wrong_type_is_rejected_once() {
+        let errors = wake_up_script()
+            .validate_configuration(&json!([]))
+            .unwrap_err();
+        assert_eq!(errors, ["configuration: expected type \"object\""]);
+    }
+
+    #[test]
+    fn referenced_schemas_are_not_checked() {
+        let mut data = wake_up_script().data().clone();
+        data.configuration_schema = BehaviorSchema::Ref(SchemaRef {
+            sref: "configuration.json#".into(),
+        });
+        let script = BehaviorScript::new(data);
+
+        assert_eq!(script.validate_configuration(&json!(42)), Ok(()));
+    }
+}
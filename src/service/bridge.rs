@@ -1,25 +1,35 @@
 #[cfg(feature = "sse")]
 use crate::event::HueEvent;
+#[cfg(feature = "streaming")]
+use crate::service::StreamSession;
 use crate::{
-    api::{BridgeClient, HueAPIError, Version},
+    api::{
+        BridgeClient, Certificate, HueAPIError, HueTransport, ResourceListing, Version,
+        DEFAULT_RETRY_BACKOFF, DEFAULT_RETRY_MAX_BACKOFF,
+    },
+    command::{merge_commands, LightCommand, SceneCommand, ZoneCommand},
     service::{
         BehaviorInstance, BehaviorInstanceBuilder, BehaviorInstanceData, BehaviorScript,
-        BehaviorScriptData, Button, ButtonData, CameraMotion, Contact, ContactData, Device,
-        DeviceData, DevicePower, DevicePowerData, DeviceSoftwareUpdateData, Entertainment,
-        EntertainmentConfiguration, EntertainmentConfigurationData, EntertainmentData,
-        GeofenceClient, GeofenceClientBuilder, GeofenceClientData, Geolocation, GeolocationData,
-        Group, GroupData, Home, HomeData, HomeKit, HomeKitData, Light, LightData, LightLevel,
-        LightLevelData, Matter, MatterData, MatterFabric, MatterFabricData, Motion, MotionData,
+        BehaviorScriptData, Button, ButtonData, CameraMotion, ColorFeatureBasic, Contact,
+        ContactData, DeleteOutcome, Device, DeviceData, DevicePower, DevicePowerData,
+        DeviceSoftwareUpdate, DeviceSoftwareUpdateData, EffectType, Entertainment,
+        EntertainmentConfiguration, EntertainmentConfigurationBuilder,
+        EntertainmentConfigurationData, EntertainmentData, GeofenceClient, GeofenceClientBuilder,
+        GeofenceClientData, Geolocation, GeolocationData, Group, GroupData, GroupedLightLevel,
+        GroupedLightLevelData, GroupedMotion, GroupedMotionData, Home, HomeData, HomeKit,
+        HomeKitData, Light, LightAction, LightData, LightLevel, LightLevelData, Matter, MatterData,
+        MatterFabric, MatterFabricData, Motion, MotionData, PowerupPresetType, ProductArchetype,
         RelativeRotary, RelativeRotaryData, Resource, ResourceIdentifier, ResourceType, Room,
-        Scene, SceneBuilder, SceneData, SmartScene, SmartSceneBuilder, SmartSceneData, TamperData,
-        Temperature, TemperatureData, ZGPConnectivity, ZGPConnectivityData, ZigbeeConnectivity,
-        ZigbeeConnectivityData, ZigbeeDeviceDiscovery, ZigbeeDeviceDiscoveryData, Zone,
-        ZoneBuilder, ZoneData,
+        Scene, SceneAction, SceneBuilder, SceneColorTempState, SceneData, SceneStatus, Schedule,
+        SendOutcome, SmartScene, SmartSceneBuilder, SmartSceneData, SoftwareUpdateStatus,
+        TamperData, Temperature, TemperatureData, TimedEffectType, ZGPConnectivity,
+        ZGPConnectivityData, ZigbeeConnectivity, ZigbeeConnectivityData, ZigbeeDeviceDiscovery,
+        ZigbeeDeviceDiscoveryData, Zone, ZoneArchetype, ZoneBuilder, ZoneData,
     },
 };
-use serde::Deserialize;
 #[cfg(feature = "sse")]
-use serde::{de::DeserializeOwned, Serialize};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
     sync::Arc,
@@ -36,12 +46,28 @@ pub enum BridgeDiscoveryError {
     NotFound,
     MDNSUnavailable,
     HTTPUnavailable,
+    /// Every discovery method failed, with the reason for each.
+    AllMethodsFailed {
+        mdns: Box<BridgeDiscoveryError>,
+        http: Box<BridgeDiscoveryError>,
+    },
 }
 
 #[derive(Debug)]
 pub enum BridgeBuildError {
     NoIp,
     NoAppKey,
+    UnresolvedHost,
+}
+
+/// Credentials issued by the bridge when registering an app with
+/// [Bridge::create_app].
+#[derive(Clone, Debug)]
+pub struct AppCredentials {
+    /// Authenticates requests to the bridge API.
+    pub app_key: String,
+    /// Authenticates entertainment streaming sessions.
+    pub client_key: String,
 }
 
 #[derive(Debug)]
@@ -49,6 +75,11 @@ pub enum BridgeUserError {
     UnableToCreate,
 }
 
+#[cfg(feature = "sse")]
+const SSE_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+#[cfg(feature = "sse")]
+const DEFAULT_SSE_MAX_BACKOFF: Duration = Duration::from_secs(60);
+
 /// Core structure representing a Hue Bridge device interface.
 #[derive(Debug)]
 pub struct Bridge {
@@ -57,6 +88,15 @@ pub struct Bridge {
     poll_handle: Option<JoinHandle<()>>,
     #[cfg(feature = "sse")]
     listen_handle: Option<JoinHandle<()>>,
+    #[cfg(feature = "sse")]
+    stall_timeout: Option<Duration>,
+    #[cfg(feature = "sse")]
+    sse_reconnect: bool,
+    #[cfg(feature = "sse")]
+    sse_max_backoff: Duration,
+    optimistic: bool,
+    /// Held while a full resource fetch is in flight, storing its outcome.
+    in_flight: Arc<tokio::sync::Mutex<Result<(), HueAPIError>>>,
 }
 
 impl Bridge {
@@ -68,6 +108,14 @@ impl Bridge {
             poll_handle: None,
             #[cfg(feature = "sse")]
             listen_handle: None,
+            #[cfg(feature = "sse")]
+            stall_timeout: None,
+            #[cfg(feature = "sse")]
+            sse_reconnect: true,
+            #[cfg(feature = "sse")]
+            sse_max_backoff: DEFAULT_SSE_MAX_BACKOFF,
+            optimistic: false,
+            in_flight: Arc::new(tokio::sync::Mutex::new(Ok(()))),
         }
     }
 
@@ -84,6 +132,14 @@ impl Bridge {
             poll_handle: None,
             #[cfg(feature = "sse")]
             listen_handle: None,
+            #[cfg(feature = "sse")]
+            stall_timeout: None,
+            #[cfg(feature = "sse")]
+            sse_reconnect: true,
+            #[cfg(feature = "sse")]
+            sse_max_backoff: DEFAULT_SSE_MAX_BACKOFF,
+            optimistic: false,
+            in_flight: Arc::new(tokio::sync::Mutex::new(Ok(()))),
         }
     }
 
@@ -94,6 +150,14 @@ impl Bridge {
             poll_handle: None,
             #[cfg(feature = "sse")]
             listen_handle: None,
+            #[cfg(feature = "sse")]
+            stall_timeout: None,
+            #[cfg(feature = "sse")]
+            sse_reconnect: true,
+            #[cfg(feature = "sse")]
+            sse_max_backoff: DEFAULT_SSE_MAX_BACKOFF,
+            optimistic: false,
+            in_flight: Arc::new(tokio::sync::Mutex::new(Ok(()))),
         }
     }
 
@@ -101,12 +165,16 @@ impl Bridge {
         BridgeBuilder::discover().await
     }
 
+    /// Refreshes the cache every `heartbeat` in a background task. The initial
+    /// fetch is skipped if the cache is already [populated](BridgeCache::is_populated).
     pub async fn poll(mut self, heartbeat: Duration) -> Self {
         let api = self.api.clone();
         let cache = self.cache.clone();
+        let in_flight = self.in_flight.clone();
 
-        if let Ok(data) = api.get_resources().await {
-            insert_to_cache(&mut cache.lock().unwrap(), data)
+        // A cache restored from a snapshot is kept until the next update
+        if !cache.lock().expect("lock cache").is_populated() {
+            let _ = refresh_cache(&api, &cache, &in_flight).await;
         }
 
         self.poll_handle = Some(tokio::spawn(async move {
@@ -118,9 +186,7 @@ impl Bridge {
                 if first_tick {
                     first_tick = false;
                 } else {
-                    if let Ok(data) = api.get_resources().await {
-                        insert_to_cache(&mut cache.lock().unwrap(), data)
-                    }
+                    let _ = refresh_cache(&api, &cache, &in_flight).await;
                 }
                 interval.tick().await;
             }
@@ -129,6 +195,18 @@ impl Bridge {
         self
     }
 
+    /// When enabled, successful [Light::send] calls immediately apply their
+    /// on, dimming, and color changes to the cache rather than waiting for the
+    /// next poll or event. The cache may briefly disagree with the bridge.
+    ///
+    /// Sends the bridge only partly applies, reporting
+    /// [SendOutcome::warnings], are left to the next poll or event, since the
+    /// warnings do not say which changes were rejected.
+    pub fn optimistic(mut self, enabled: bool) -> Self {
+        self.optimistic = enabled;
+        self
+    }
+
     pub fn unpoll(&mut self) {
         if let Some(handle) = &self.poll_handle {
             handle.abort();
@@ -136,49 +214,74 @@ impl Bridge {
         self.poll_handle = None;
     }
 
+    /// The task started by [Bridge::poll], if any, e.g. to check whether it
+    /// has exited.
+    pub fn poll_handle(&self) -> Option<&JoinHandle<()>> {
+        self.poll_handle.as_ref()
+    }
+
+    /// Whether a [Bridge::poll] task is running.
+    pub fn is_polling(&self) -> bool {
+        self.poll_handle.as_ref().is_some_and(|h| !h.is_finished())
+    }
+
+    /// Sets how long [Bridge::listen] waits without receiving any event before
+    /// treating the event stream as stalled and reconnecting. Disabled by
+    /// default: the bridge's keep-alive comments are not passed through to the
+    /// stream, so a quiet home can go a long time without an event. Choose a
+    /// timeout longer than the usual gap between events, such as the reports of
+    /// a motion or temperature sensor.
+    #[cfg(feature = "sse")]
+    pub fn stall_timeout(mut self, timeout: Duration) -> Self {
+        self.stall_timeout = Some(timeout);
+        self
+    }
+
+    /// Subscribes to the bridge event stream, keeping the cache up to date and
+    /// calling `cb` with the identifiers of updated resources. If `cb` panics,
+    /// the panic is logged and listening continues. The initial fetch is skipped
+    /// if the cache is already [populated](BridgeCache::is_populated).
     #[cfg(feature = "sse")]
-    pub async fn listen<C>(mut self, cb: C) -> Self
+    pub async fn listen<C>(self, cb: C) -> Self
     where
         C: Fn(HashSet<ResourceIdentifier>) + Send + 'static,
+    {
+        self.listen_events(move |events| {
+            cb(events
+                .into_iter()
+                .filter_map(|event| match event {
+                    HueEventKind::Updated { rid, .. } => Some(rid),
+                    _ => None,
+                })
+                .collect())
+        })
+        .await
+    }
+
+    /// Like [Bridge::listen], but calls `cb` with every addition, update, and
+    /// deletion, including the typed data before and after each update.
+    #[cfg(feature = "sse")]
+    pub async fn listen_events<C>(mut self, cb: C) -> Self
+    where
+        C: Fn(Vec<HueEventKind>) + Send + 'static,
     {
         let api = self.api.clone();
         let cache = self.cache.clone();
+        let in_flight = self.in_flight.clone();
+        let options = ListenOptions {
+            stall_timeout: self.stall_timeout,
+            reconnect: self.sse_reconnect,
+            max_backoff: self.sse_max_backoff,
+        };
 
-        if let Ok(data) = api.get_resources().await {
-            insert_to_cache(&mut cache.lock().expect("lock cache"), data)
+        // A cache restored from a snapshot is kept until the next update
+        if !cache.lock().expect("lock cache").is_populated() {
+            let _ = refresh_cache(&api, &cache, &in_flight).await;
         }
 
         let fut = async move {
-            use futures_util::StreamExt;
-            use reqwest_eventsource::Event;
-
-            match api.get_event_stream().await {
-                Ok(mut es) => {
-                    while let Some(event) = es.next().await {
-                        match event {
-                            Ok(Event::Open) => {}
-                            Ok(Event::Message(message)) => {
-                                match serde_json::from_str::<Vec<HueEvent>>(&message.data) {
-                                    Ok(data) => {
-                                        let mut cache = cache.lock().expect("lock cache");
-                                        let changes = upsert_to_cache(&mut cache, data);
-                                        cb(changes);
-                                    }
-                                    Err(e) => {
-                                        log::error!("{e}");
-                                    }
-                                }
-                            }
-                            Err(e) => {
-                                log::error!("{e}");
-                            }
-                        }
-                    }
-                }
-                Err(e) => {
-                    log::error!("{e:?}");
-                }
-            }
+            let connect = || api.get_event_stream();
+            read_event_streams(connect, &api, &cache, &in_flight, options, cb).await
         };
 
         self.listen_handle = Some(tokio::spawn(fut));
@@ -192,12 +295,36 @@ impl Bridge {
         }
     }
 
+    /// The task started by [Bridge::listen], if any. With reconnection
+    /// disabled through [BridgeBuilder::sse_reconnect], the task exits when the
+    /// event stream closes or cannot be opened, so supervisors can check it
+    /// and call [Bridge::listen] again.
+    #[cfg(feature = "sse")]
+    pub fn listen_handle(&self) -> Option<&JoinHandle<()>> {
+        self.listen_handle.as_ref()
+    }
+
+    /// Whether a [Bridge::listen] task is running.
+    #[cfg(feature = "sse")]
+    pub fn is_listening(&self) -> bool {
+        self.listen_handle
+            .as_ref()
+            .is_some_and(|h| !h.is_finished())
+    }
+
+    /// Registers a new app with the bridge, which must have its link button
+    /// pressed beforehand. The returned keys are used by this bridge from now on,
+    /// and should be saved for future sessions.
     pub async fn create_app(
         &mut self,
         app_name: impl Into<String>,
         instance_name: impl Into<String>,
-    ) -> Result<&str, HueAPIError> {
-        self.api.create_app(app_name, instance_name).await
+    ) -> Result<AppCredentials, HueAPIError> {
+        self.api.create_app(app_name, instance_name).await?;
+        Ok(AppCredentials {
+            app_key: self.api.app_key().to_owned(),
+            client_key: self.api.client_key().unwrap_or_default().to_owned(),
+        })
     }
 
     #[deprecated = "only available via web interface with bridges running >=1.31.0"]
@@ -214,16 +341,216 @@ impl Bridge {
             .map(|d| d.clone())
     }
 
-    pub async fn refresh(&self) -> Result<(), HueAPIError> {
-        let data = self.api.get_resources().await?;
-        let mut cache = self.cache.lock().expect("lock cache");
-        insert_to_cache(&mut cache, data);
+    /// Software version of the bridge, as reported by the [Device] which owns it.
+    /// Returns `None` until the cache has been populated.
+    pub fn software_version(&self) -> Option<String> {
+        let cache = self.cache.lock().expect("lock cache");
+        let owner = &cache.data.as_ref()?.owner;
+        cache
+            .devices
+            .get(&owner.rid)
+            .map(|d| d.product_data.software_version.clone())
+    }
+
+    /// Whether the bridge software version is at least `major.minor`.
+    /// Returns `false` when the version is unknown or cannot be parsed.
+    pub fn software_version_at_least(&self, major: u32, minor: u32) -> bool {
+        self.software_version()
+            .and_then(|v| {
+                let mut parts = v.split('.').map(|p| p.parse::<u32>());
+                match (parts.next(), parts.next()) {
+                    (Some(Ok(maj)), Some(Ok(min))) => Some((maj, min) >= (major, minor)),
+                    _ => None,
+                }
+            })
+            .unwrap_or(false)
+    }
+
+    /// Whether the bridge supports [SmartScene] resources (software version 1.53 or later).
+    pub fn supports_smart_scenes(&self) -> bool {
+        self.software_version_at_least(1, 53)
+    }
+
+    /// Whether the bridge supports light effects and timed effects (software version 1.50 or later).
+    pub fn supports_effects(&self) -> bool {
+        self.software_version_at_least(1, 50)
+    }
+
+    /// Locks the cache for reading, e.g. to mirror its contents into an external
+    /// store. Polling and listening cannot update the cache while the guard is held.
+    pub fn cache(&self) -> MutexGuard<'_, BridgeCache> {
+        self.cache.lock().expect("lock cache")
+    }
+
+    /// The cached data of any resource, such as a light's owner or a scene's
+    /// group, or [None] if it is not cached.
+    pub fn resource(&self, rid: &ResourceIdentifier) -> Option<Resource> {
+        self.cache.lock().expect("lock cache").resource(rid)
+    }
+
+    /// Serializes the cache as JSON, to be restored with [Bridge::import_cache]
+    /// or [BridgeBuilder::with_cache] so that a new [Bridge] can serve reads
+    /// before its first [Bridge::refresh].
+    pub fn export_cache(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(&*self.cache.lock().expect("lock cache"))
+    }
+
+    /// Replaces the cache with one produced by [Bridge::export_cache]. The
+    /// restored data may be out of date until the next refresh or event.
+    pub fn import_cache(&self, json: &str) -> Result<(), serde_json::Error> {
+        let restored = serde_json::from_str(json)?;
+        *self.cache.lock().expect("lock cache") = restored;
         Ok(())
     }
 
+    /// Takes an immutable copy of the cache, which can be read without holding
+    /// the lock. Resource data is shared rather than cloned, but every map in
+    /// the cache is copied, so a single read is cheaper through accessors such
+    /// as [Bridge::lights]. Later updates to the cache are not reflected in the
+    /// copy.
+    pub fn snapshot(&self) -> Arc<BridgeCache> {
+        Arc::new(self.cache.lock().expect("lock cache").clone())
+    }
+
+    /// Finds references to resources which are no longer present, such as a
+    /// [Scene] action targeting a removed light. Walks scene groups and actions,
+    /// room and zone children, and smart scene groups and timeslots.
+    pub fn validate_references(&self) -> Vec<DanglingReference> {
+        let cache = self.cache.lock().expect("lock cache");
+        let mut refs: Vec<(ResourceIdentifier, &ResourceIdentifier)> = vec![];
+
+        for scene in cache.scenes.values() {
+            refs.push((scene.rid(), &scene.group));
+            refs.extend(scene.actions.iter().map(|a| (scene.rid(), &a.target)));
+        }
+        for room in cache.rooms.values() {
+            let rid = ResourceIdentifier::new(&room.id, ResourceType::Room);
+            refs.extend(room.children.iter().map(|c| (rid.clone(), c)));
+        }
+        for zone in cache.zones.values() {
+            refs.extend(zone.children.iter().map(|c| (zone.rid(), c)));
+        }
+        for smart_scene in cache.smart_scenes.values() {
+            refs.push((smart_scene.rid(), &smart_scene.group));
+            refs.extend(
+                smart_scene
+                    .week_timeslots
+                    .iter()
+                    .flat_map(|s| &s.timeslots)
+                    .map(|t| (smart_scene.rid(), &t.target)),
+            );
+        }
+
+        refs.into_iter()
+            .filter(|(_, target)| cache.contains(target) == Some(false))
+            .map(|(source, target)| DanglingReference {
+                source,
+                target: target.clone(),
+            })
+            .collect()
+    }
+
+    /// Summarizes values and resource types reported by the bridge that this
+    /// crate does not recognize.
+    pub fn diagnostics(&self) -> Diagnostics {
+        let cache = self.cache.lock().expect("lock cache");
+        let mut diagnostics = Diagnostics {
+            unmodeled_resources: cache
+                .unmodeled
+                .iter()
+                .map(|(rtype, ids)| (rtype.clone(), ids.len()))
+                .collect(),
+            deserialize_failures: cache.deserialize_failures,
+            ..Default::default()
+        };
+
+        for zone in cache.rooms.values().chain(cache.zones.values()) {
+            if let ZoneArchetype::Unknown(a) = &zone.metadata.archetype {
+                *diagnostics
+                    .unknown_zone_archetypes
+                    .entry(a.clone())
+                    .or_default() += 1;
+            }
+        }
+        for device in cache.devices.values() {
+            for archetype in [
+                &device.product_data.product_archetype,
+                &device.metadata.archetype,
+            ] {
+                if let ProductArchetype::Unknown(a) = archetype {
+                    *diagnostics
+                        .unknown_product_archetypes
+                        .entry(a.clone())
+                        .or_default() += 1;
+                }
+            }
+        }
+        diagnostics.lights_with_unknown_effects = cache
+            .lights
+            .values()
+            .filter(|l| {
+                l.effects
+                    .as_ref()
+                    .is_some_and(|e| e.effect_values.contains(&EffectType::Unknown))
+                    || l.timed_effects
+                        .as_ref()
+                        .is_some_and(|e| e.effect_values.contains(&TimedEffectType::Unknown))
+            })
+            .count();
+        diagnostics.unknown_software_update_states = cache
+            .swu
+            .values()
+            .filter(|u| u.state == SoftwareUpdateStatus::Unknown)
+            .count();
+
+        diagnostics
+    }
+
+    /// Fetches all resources from the bridge into the cache, dropping any the
+    /// bridge no longer reports. If a fetch is already in flight, from another
+    /// call or from [Bridge::poll], this waits for it to finish and shares its
+    /// result instead of fetching again.
+    pub async fn refresh(&self) -> Result<(), HueAPIError> {
+        refresh_cache(&self.api, &self.cache, &self.in_flight).await
+    }
+
+    /// Fetches all resources from the bridge into the cache like
+    /// [Bridge::refresh], and reports which resources were added, removed, or
+    /// changed since the cache was last updated.
+    pub async fn refresh_diff(&self) -> Result<BridgeDiff, HueAPIError> {
+        let mut outcome = self.in_flight.lock().await;
+        let listing = match self.api.get_resources().await {
+            Ok(listing) => listing,
+            Err(e) => {
+                *outcome = Err(e.clone());
+                return Err(e);
+            }
+        };
+        *outcome = Ok(());
+        Ok(apply_listing(
+            &mut self.cache.lock().expect("lock cache"),
+            listing,
+        ))
+    }
+
+    /// Adds a client key to a bridge created with only an app key, enabling
+    /// [Bridge::initialize_streaming] without discarding the cache or any
+    /// running [Bridge::poll] or [Bridge::listen] tasks.
     #[cfg(feature = "streaming")]
-    pub async fn initialize_streaming(&self, ent_id: impl Into<String>) -> Result<(), HueAPIError> {
-        self.api.open_stream(ent_id).await
+    pub fn with_client_key(&mut self, client_key: impl Into<String>) {
+        self.api.set_client_key(client_key);
+    }
+
+    /// Starts the entertainment configuration `ent_id` and opens a DTLS
+    /// connection for streaming to it. Requires a client key.
+    #[cfg(feature = "streaming")]
+    pub async fn initialize_streaming(
+        &self,
+        ent_id: impl Into<String>,
+    ) -> Result<StreamSession, HueAPIError> {
+        let ent_id = ent_id.into();
+        let conn = self.api.open_stream(&ent_id).await?;
+        Ok(StreamSession::new(conn, ent_id))
     }
 
     pub fn addr(&self) -> &IpAddr {
@@ -234,6 +561,11 @@ impl Bridge {
         self.api.app_key()
     }
 
+    /// The client key used for entertainment streaming, if known.
+    pub fn client_key(&self) -> Option<&str> {
+        self.api.client_key()
+    }
+
     pub fn behavior_script(&self, id: impl Into<String>) -> Option<BehaviorScript> {
         self.cache
             .lock()
@@ -253,7 +585,12 @@ impl Bridge {
             .collect()
     }
 
+    #[deprecated = "use `n_behavior_scripts`"]
     pub fn n_behavior_scrips(&self) -> usize {
+        self.n_behavior_scripts()
+    }
+
+    pub fn n_behavior_scripts(&self) -> usize {
         self.cache
             .lock()
             .expect("lock cache")
@@ -284,7 +621,7 @@ impl Bridge {
         self.cache
             .lock()
             .expect("lock cache")
-            .behavior_scripts
+            .behavior_instances
             .len()
     }
 
@@ -296,7 +633,7 @@ impl Bridge {
             .api
             .post_behavior_instance(serde_json::to_value(builder).unwrap())
             .await?;
-        let data = self.api.get_behavior_instance(rid.rid).await?;
+        let data = Arc::new(self.api.get_behavior_instance(rid.rid).await?);
         self.cache
             .lock()
             .expect("lock cache")
@@ -308,10 +645,10 @@ impl Bridge {
     pub async fn delete_behavior_instance(
         &self,
         id: impl Into<String>,
-    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
-        let res = self.api.delete_behavior_instance(id).await?;
-        delete_from_cache(&mut self.cache.lock().expect("lock cache"), &res);
-        Ok(res)
+    ) -> Result<DeleteOutcome, HueAPIError> {
+        let rids = self.api.delete_behavior_instance(id).await?;
+        let removed = self.evict_from_cache(&rids);
+        Ok(DeleteOutcome { rids, removed })
     }
 
     pub fn entertainment_configuration(
@@ -369,13 +706,13 @@ impl Bridge {
 
     pub async fn create_entertainment_configuration(
         &self,
-        builder: (),
-    ) -> Result<EntertainmentConfiguration, HueAPIError> {
+        builder: EntertainmentConfigurationBuilder,
+    ) -> Result<EntertainmentConfiguration<'_>, HueAPIError> {
         let rid = self
             .api
             .post_entertainment_configuration(serde_json::to_value(builder).unwrap())
             .await?;
-        let data = self.api.get_entertainment_configuration(rid.rid).await?;
+        let data = Arc::new(self.api.get_entertainment_configuration(rid.rid).await?);
         self.cache
             .lock()
             .expect("lock cache")
@@ -387,28 +724,28 @@ impl Bridge {
     pub async fn delete_entertainment_configuration(
         &self,
         id: impl Into<String>,
-    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
-        let res = self.api.delete_entertainment_configuration(id).await?;
-        delete_from_cache(&mut self.cache.lock().expect("lock cache"), &res);
-        Ok(res)
+    ) -> Result<DeleteOutcome, HueAPIError> {
+        let rids = self.api.delete_entertainment_configuration(id).await?;
+        let removed = self.evict_from_cache(&rids);
+        Ok(DeleteOutcome { rids, removed })
     }
 
-    pub fn button(&self, id: impl Into<String>) -> Option<Button> {
+    pub fn button(&self, id: impl Into<String>) -> Option<Button<'_>> {
         self.cache
             .lock()
             .expect("lock cache")
             .buttons
             .get(&id.into())
-            .map(|data| Button::new(data.clone()))
+            .map(|data| Button::new(self, data.clone()))
     }
 
-    pub fn buttons(&self) -> Vec<Button> {
+    pub fn buttons(&self) -> Vec<Button<'_>> {
         self.cache
             .lock()
             .expect("lock cache")
             .buttons
             .iter()
-            .map(|(_, data)| Button::new(data.clone()))
+            .map(|(_, data)| Button::new(self, data.clone()))
             .collect()
     }
 
@@ -520,7 +857,7 @@ impl Bridge {
             .api
             .post_geofence_client(serde_json::to_value(builder).unwrap())
             .await?;
-        let data = self.api.get_geofence_client(rid.rid).await?;
+        let data = Arc::new(self.api.get_geofence_client(rid.rid).await?);
         self.cache
             .lock()
             .expect("lock cache")
@@ -532,10 +869,10 @@ impl Bridge {
     pub async fn delete_geofence_client(
         &self,
         id: impl Into<String>,
-    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
-        let res = self.api.delete_geofence_client(id).await?;
-        delete_from_cache(&mut self.cache.lock().expect("lock cache"), &res);
-        Ok(res)
+    ) -> Result<DeleteOutcome, HueAPIError> {
+        let rids = self.api.delete_geofence_client(id).await?;
+        let removed = self.evict_from_cache(&rids);
+        Ok(DeleteOutcome { rids, removed })
     }
 
     pub fn homekit(&self, id: impl Into<String>) -> Option<HomeKit> {
@@ -610,10 +947,10 @@ impl Bridge {
     pub async fn delete_matter_fabric(
         &mut self,
         id: impl Into<String>,
-    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
-        let res = self.api.delete_matter_fabric(id).await?;
-        delete_from_cache(&mut self.cache.lock().expect("lock cache"), &res);
-        Ok(res)
+    ) -> Result<DeleteOutcome, HueAPIError> {
+        let rids = self.api.delete_matter_fabric(id).await?;
+        let removed = self.evict_from_cache(&rids);
+        Ok(DeleteOutcome { rids, removed })
     }
 
     pub fn device(&self, id: impl Into<String>) -> Option<Device> {
@@ -642,10 +979,10 @@ impl Bridge {
     pub async fn delete_device(
         &mut self,
         id: impl Into<String>,
-    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
-        let res = self.api.delete_device(id).await?;
-        delete_from_cache(&mut self.cache.lock().expect("lock cache"), &res);
-        Ok(res)
+    ) -> Result<DeleteOutcome, HueAPIError> {
+        let rids = self.api.delete_device(id).await?;
+        let removed = self.evict_from_cache(&rids);
+        Ok(DeleteOutcome { rids, removed })
     }
 
     pub fn device_power(&self, id: impl Into<String>) -> Option<DevicePower> {
@@ -671,6 +1008,40 @@ impl Bridge {
         self.cache.lock().expect("lock cache").power.len()
     }
 
+    pub fn device_software_update(&self, id: impl Into<String>) -> Option<DeviceSoftwareUpdate> {
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .swu
+            .get(&id.into())
+            .map(|data| DeviceSoftwareUpdate::new(data.clone()))
+    }
+
+    pub fn device_software_updates(&self) -> Vec<DeviceSoftwareUpdate> {
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .swu
+            .values()
+            .map(|data| DeviceSoftwareUpdate::new(data.clone()))
+            .collect()
+    }
+
+    pub fn n_device_software_updates(&self) -> usize {
+        self.cache.lock().expect("lock cache").swu.len()
+    }
+
+    /// Devices with a firmware update available but not yet installed.
+    pub fn pending_updates(&self) -> Vec<Device<'_>> {
+        let owners = self
+            .device_software_updates()
+            .into_iter()
+            .filter(|swu| swu.is_update_available())
+            .map(|swu| swu.owner().rid.clone())
+            .collect::<Vec<_>>();
+        owners.iter().filter_map(|id| self.device(id)).collect()
+    }
+
     pub fn group(&self, id: impl Into<String>) -> Option<Group> {
         self.cache
             .lock()
@@ -694,22 +1065,72 @@ impl Bridge {
         self.cache.lock().expect("lock cache").groups.len()
     }
 
-    pub fn home(&self, id: impl Into<String>) -> Option<Home> {
+    pub fn grouped_light_level(&self, id: impl Into<String>) -> Option<GroupedLightLevel<'_>> {
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .grouped_light_levels
+            .get(&id.into())
+            .map(|data| GroupedLightLevel::new(self, data.clone()))
+    }
+
+    pub fn grouped_light_levels(&self) -> Vec<GroupedLightLevel<'_>> {
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .grouped_light_levels
+            .values()
+            .map(|data| GroupedLightLevel::new(self, data.clone()))
+            .collect()
+    }
+
+    pub fn n_grouped_light_levels(&self) -> usize {
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .grouped_light_levels
+            .len()
+    }
+
+    pub fn grouped_motion(&self, id: impl Into<String>) -> Option<GroupedMotion<'_>> {
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .grouped_motions
+            .get(&id.into())
+            .map(|data| GroupedMotion::new(self, data.clone()))
+    }
+
+    pub fn grouped_motions(&self) -> Vec<GroupedMotion<'_>> {
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .grouped_motions
+            .values()
+            .map(|data| GroupedMotion::new(self, data.clone()))
+            .collect()
+    }
+
+    pub fn n_grouped_motions(&self) -> usize {
+        self.cache.lock().expect("lock cache").grouped_motions.len()
+    }
+
+    pub fn home(&self, id: impl Into<String>) -> Option<Home<'_>> {
         self.cache
             .lock()
             .expect("lock cache")
             .homes
             .get(&id.into())
-            .map(|data| Home::new(data.clone()))
+            .map(|data| Home::new(self, data.clone()))
     }
 
-    pub fn homes(&self) -> Vec<Home> {
+    pub fn homes(&self) -> Vec<Home<'_>> {
         self.cache
             .lock()
             .expect("lock cache")
             .homes
             .iter()
-            .map(|(_, data)| Home::new(data.clone()))
+            .map(|(_, data)| Home::new(self, data.clone()))
             .collect()
     }
 
@@ -736,10 +1157,226 @@ impl Bridge {
             .collect()
     }
 
+    /// The [Light] whose owning [Device] is named exactly `name`.
+    pub fn light_by_name(&self, name: &str) -> Option<Light<'_>> {
+        self.find_light(|owner| owner == name)
+    }
+
+    /// The [Light] whose owning [Device] is named `name`, ignoring case.
+    pub fn light_by_name_ci(&self, name: &str) -> Option<Light<'_>> {
+        let name = name.to_lowercase();
+        self.find_light(|owner| owner.to_lowercase() == name)
+    }
+
+    fn find_light(&self, matches: impl Fn(&str) -> bool) -> Option<Light<'_>> {
+        let cache = self.cache.lock().expect("lock cache");
+        cache
+            .lights
+            .values()
+            .find(|l| {
+                cache
+                    .devices
+                    .get(&l.owner.rid)
+                    .is_some_and(|d| matches(&d.metadata.name))
+            })
+            .map(|data| Light::new(self, data.clone()))
+    }
+
     pub fn n_lights(&self) -> usize {
         self.cache.lock().expect("lock cache").lights.len()
     }
 
+    /// Visits the data of every cached [Light] without cloning it. The cache is
+    /// locked for the duration of the call, so `f` should not call back into
+    /// this [Bridge].
+    pub fn for_each_light(&self, mut f: impl FnMut(&LightData)) {
+        let cache = self.cache.lock().expect("lock cache");
+        for data in cache.lights.values() {
+            f(data);
+        }
+    }
+
+    pub fn count_lights_where(&self, mut predicate: impl FnMut(&LightData) -> bool) -> usize {
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .lights
+            .values()
+            .filter(|data| predicate(data))
+            .count()
+    }
+
+    /// Applies a powerup `preset` to every [Light] that supports powerup
+    /// behavior through [Bridge::send_batch], with at most `concurrency`
+    /// requests in flight at once. Each light is returned with its result, so
+    /// those which did not receive the preset can be retried.
+    ///
+    /// [PowerupPresetType::Custom] requires additional properties per light, so
+    /// is better applied with [LightCommand::PowerUp] directly.
+    pub async fn set_powerup_all(
+        &self,
+        preset: PowerupPresetType,
+        concurrency: usize,
+    ) -> Vec<(ResourceIdentifier, Result<SendOutcome, HueAPIError>)> {
+        let batch = self
+            .cache
+            .lock()
+            .expect("lock cache")
+            .lights
+            .values()
+            .filter(|data| data.powerup.is_some())
+            .map(|data| {
+                let command = LightCommand::PowerUp {
+                    preset,
+                    on: None,
+                    dimming: None,
+                    color: None,
+                };
+                (data.rid(), vec![command])
+            })
+            .collect::<Vec<_>>();
+
+        let results = self.send_batch(&batch, concurrency).await;
+        batch.into_iter().map(|(rid, _)| rid).zip(results).collect()
+    }
+
+    /// Sends each set of commands to its [Light] concurrently, with at most
+    /// `concurrency` requests in flight at once. Results are returned in the
+    /// order of `batch`, so partial failures can be matched to their light.
+    /// Identifiers that do not refer to a light fail with
+    /// [HueAPIError::InvalidInput].
+    pub async fn send_batch(
+        &self,
+        batch: &[(ResourceIdentifier, Vec<LightCommand>)],
+        concurrency: usize,
+    ) -> Vec<Result<SendOutcome, HueAPIError>> {
+        let permits = Arc::new(tokio::sync::Semaphore::new(concurrency.max(1)));
+        let mut results = batch.iter().map(|_| None).collect::<Vec<_>>();
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for (i, (rid, commands)) in batch.iter().enumerate() {
+            if rid.rtype != ResourceType::Light {
+                results[i] = Some(Err(HueAPIError::InvalidInput(format!(
+                    "{} is not a light",
+                    rid.rid
+                ))));
+                continue;
+            }
+            let api = self.api.clone();
+            let permits = permits.clone();
+            let id = rid.rid.clone();
+            let payload = merge_commands(commands);
+            tasks.spawn(async move {
+                let _permit = permits.acquire_owned().await;
+                let res = api.put_light(&id, &payload).await;
+                (i, id, payload, res)
+            });
+        }
+
+        // A task which failed to complete takes its index with it, but is the
+        // only one to leave its result empty
+        let mut failure = String::new();
+        while let Some(res) = tasks.join_next().await {
+            match res {
+                Ok((i, id, payload, res)) => {
+                    if matches!(&res, Ok(outcome) if outcome.warnings.is_empty()) {
+                        self.apply_optimistic_light_update(&id, &payload);
+                    }
+                    results[i] = Some(res);
+                }
+                Err(e) => failure = e.to_string(),
+            }
+        }
+        results
+            .into_iter()
+            .map(|res| res.unwrap_or_else(|| Err(HueAPIError::Task(failure.clone()))))
+            .collect()
+    }
+
+    pub(crate) fn apply_optimistic_light_update(&self, id: &str, payload: &serde_json::Value) {
+        if !self.optimistic {
+            return;
+        }
+        let patch = match payload.as_object() {
+            Some(map) => map
+                .iter()
+                .filter(|(k, v)| {
+                    ["on", "dimming", "color", "color_temperature"].contains(&k.as_str())
+                        && !v.is_null()
+                })
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect::<serde_json::Map<_, _>>(),
+            None => return,
+        };
+        let mut cache = self.cache.lock().expect("lock cache");
+        if let Some(data) = cache.lights.get(id) {
+            let mut json = match serde_json::to_value(data.as_ref()) {
+                Ok(json) => json,
+                Err(_) => return,
+            };
+            json_patch::merge(&mut json, &serde_json::Value::Object(patch));
+            if let Ok(data) = serde_json::from_value::<LightData>(json) {
+                cache.lights.insert(data.id.clone(), Arc::new(data));
+            }
+        }
+    }
+
+    /// Removes the resources deleted on the bridge from the cache.
+    pub(crate) fn evict_from_cache(&self, rids: &[ResourceIdentifier]) -> Vec<Resource> {
+        delete_from_cache(&mut self.cache.lock().expect("lock cache"), rids)
+    }
+
+    /// Applies `update` to the children of the [Room] or [Zone] `rid`, starting
+    /// from the cached list, or from `fallback` if the group is not cached.
+    /// The full list is put since the bridge replaces rather than appends, and
+    /// nothing is sent when `update` reports no change.
+    ///
+    /// On success the cache holds the new children, so that later changes
+    /// build on them without waiting for an event.
+    pub(crate) async fn update_children(
+        &self,
+        rid: &ResourceIdentifier,
+        fallback: &ZoneData,
+        update: impl FnOnce(&mut Vec<ResourceIdentifier>) -> bool,
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        let cached = {
+            let cache = self.cache.lock().expect("lock cache");
+            match rid.rtype {
+                ResourceType::Room => cache.rooms.get(&rid.rid).cloned(),
+                _ => cache.zones.get(&rid.rid).cloned(),
+            }
+        };
+        let mut data = match cached {
+            Some(data) => data.as_ref().clone(),
+            None => fallback.clone(),
+        };
+        if !update(&mut data.children) {
+            return Ok(vec![]);
+        }
+
+        let payload = merge_commands(&[ZoneCommand::Children(data.children.clone())]);
+        let rids = match rid.rtype {
+            ResourceType::Room => self.api.put_room(&rid.rid, &payload).await?,
+            _ => self.api.put_zone(&rid.rid, &payload).await?,
+        };
+        let mut cache = self.cache.lock().expect("lock cache");
+        let groups = match rid.rtype {
+            ResourceType::Room => &mut cache.rooms,
+            _ => &mut cache.zones,
+        };
+        groups.insert(data.id.clone(), Arc::new(data));
+        Ok(rids)
+    }
+
+    pub(crate) fn cached_light_data(&self, id: &str) -> Option<Arc<LightData>> {
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .lights
+            .get(id)
+            .cloned()
+    }
+
     pub fn motion(&self, id: impl Into<String>) -> Option<Motion> {
         self.cache
             .lock()
@@ -805,16 +1442,53 @@ impl Bridge {
             .collect()
     }
 
+    /// The [Room] named exactly `name`.
+    pub fn room_by_name(&self, name: &str) -> Option<Room<'_>> {
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .rooms
+            .values()
+            .find(|data| data.metadata.name == name)
+            .map(|data| Room::new(self, data.clone()))
+    }
+
+    /// The [Room] named `name`, ignoring case.
+    pub fn room_by_name_ci(&self, name: &str) -> Option<Room<'_>> {
+        let name = name.to_lowercase();
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .rooms
+            .values()
+            .find(|data| data.metadata.name.to_lowercase() == name)
+            .map(|data| Room::new(self, data.clone()))
+    }
+
     pub fn n_rooms(&self) -> usize {
         self.cache.lock().expect("lock cache").rooms.len()
     }
 
+    /// The [Room]s with the given [ZoneArchetype], such as all bedrooms. Rooms
+    /// with an archetype not recognized by this crate can be found with
+    /// [ZoneArchetype::Unknown] and the original value.
+    pub fn rooms_with_archetype(&self, archetype: ZoneArchetype) -> Vec<Room<'_>> {
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .rooms
+            .values()
+            .filter(|data| data.metadata.archetype == archetype)
+            .map(|data| Room::new(self, data.clone()))
+            .collect()
+    }
+
     pub async fn create_room(&self, builder: ZoneBuilder) -> Result<Room, HueAPIError> {
         let rid = self
             .api
             .post_room(serde_json::to_value(builder).unwrap())
             .await?;
-        let data = self.api.get_room(rid.rid).await?;
+        let data = Arc::new(self.api.get_room(rid.rid).await?);
         self.cache
             .lock()
             .expect("lock cache")
@@ -823,13 +1497,57 @@ impl Bridge {
         Ok(Room::new(&self, data))
     }
 
-    pub async fn delete_room(
+    pub async fn delete_room(&self, id: impl Into<String>) -> Result<DeleteOutcome, HueAPIError> {
+        let rids = self.api.delete_room(id).await?;
+        let removed = self.evict_from_cache(&rids);
+        Ok(DeleteOutcome { rids, removed })
+    }
+
+    /// Deletes a [Room], then removes the [Scene]s, [SmartScene]s and [Group]
+    /// that depended on it from the cache, as the bridge deletes those alongside
+    /// the room. The data of every resource removed from the cache is returned
+    /// with the identifiers the bridge reported.
+    pub async fn delete_room_cascade(
         &self,
         id: impl Into<String>,
-    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
-        let res = self.api.delete_room(id).await?;
-        delete_from_cache(&mut self.cache.lock().expect("lock cache"), &res);
-        Ok(res)
+    ) -> Result<DeleteOutcome, HueAPIError> {
+        let id = id.into();
+        let room = self
+            .cache
+            .lock()
+            .expect("lock cache")
+            .rooms
+            .get(&id)
+            .cloned();
+        let rids = self.api.delete_room(&id).await?;
+
+        let mut cache = self.cache.lock().expect("lock cache");
+        let mut res = rids.clone();
+        if let Some(room) = room {
+            let room_rid = room.rid();
+            res.extend(
+                room.services
+                    .iter()
+                    .filter(|s| s.rtype == ResourceType::Group)
+                    .cloned(),
+            );
+            res.extend(
+                cache
+                    .scenes
+                    .values()
+                    .filter(|s| s.group == room_rid)
+                    .map(|s| s.rid()),
+            );
+            res.extend(
+                cache
+                    .smart_scenes
+                    .values()
+                    .filter(|s| s.group == room_rid)
+                    .map(|s| s.rid()),
+            );
+        }
+        let removed = delete_from_cache(&mut cache, &res);
+        Ok(DeleteOutcome { rids, removed })
     }
 
     pub fn scene(&self, id: impl Into<String>) -> Option<Scene> {
@@ -851,16 +1569,128 @@ impl Bridge {
             .collect()
     }
 
-    pub fn n_scenes(&self) -> usize {
-        self.cache.lock().expect("lock cache").scenes.len()
+    /// The [Scene] named exactly `name`.
+    pub fn scene_by_name(&self, name: &str) -> Option<Scene<'_>> {
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .scenes
+            .values()
+            .find(|data| data.metadata.name == name)
+            .map(|data| Scene::new(self, data.clone()))
+    }
+
+    /// The [Scene] named `name`, ignoring case.
+    pub fn scene_by_name_ci(&self, name: &str) -> Option<Scene<'_>> {
+        let name = name.to_lowercase();
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .scenes
+            .values()
+            .find(|data| data.metadata.name.to_lowercase() == name)
+            .map(|data| Scene::new(self, data.clone()))
+    }
+
+    pub fn n_scenes(&self) -> usize {
+        self.cache.lock().expect("lock cache").scenes.len()
+    }
+
+    /// The [Scene] most recently activated in the given room or zone, e.g. to
+    /// resume it with [Scene::recall].
+    ///
+    /// Activations are tracked when a scene's status changes between updates of
+    /// the cache, so are most accurate while [Bridge::listen]ing. Until one is
+    /// seen, falls back to the most recent `last_recall` reported by the bridge.
+    pub fn last_active_scene_for(&self, group: &ResourceIdentifier) -> Option<Scene<'_>> {
+        let cache = self.cache.lock().expect("lock cache");
+        let tracked = cache
+            .last_active_scenes
+            .get(&group.rid)
+            .and_then(|id| cache.scenes.get(id));
+        tracked
+            .or_else(|| {
+                cache
+                    .scenes
+                    .values()
+                    .filter(|s| &s.group == group && s.status.last_recall.is_some())
+                    .max_by(|a, b| a.status.last_recall.cmp(&b.status.last_recall))
+            })
+            .map(|data| Scene::new(self, data.clone()))
+    }
+
+    /// Builds a scene from the current cached state of every light in the
+    /// [Room] or [Zone] `group`, to be adjusted and passed to [Bridge::create_scene].
+    ///
+    /// Lights that are off record only `on: false`. Lights in the color
+    /// temperature spectrum record their mirek, and other color lights record
+    /// their XY color.
+    pub fn capture_scene(
+        &self,
+        name: impl Into<String>,
+        group: ResourceIdentifier,
+    ) -> Result<SceneBuilder, HueAPIError> {
+        let cache = self.cache.lock().expect("lock cache");
+        let zone = match group.rtype {
+            ResourceType::Room => cache.rooms.get(&group.rid),
+            ResourceType::Zone => cache.zones.get(&group.rid),
+            _ => None,
+        }
+        .ok_or_else(|| {
+            HueAPIError::InvalidInput(format!("no room or zone with id {}", group.rid))
+        })?;
+
+        let mut actions = cache
+            .lights
+            .values()
+            .filter(|l| zone.children.contains(&l.owner) || zone.children.contains(&l.rid()))
+            .map(|l| {
+                let mut action = LightAction::default().on(l.on.on);
+                if l.on.on {
+                    action = action.dim(l.dimming.brightness);
+                    match (&l.color_temperature, &l.color) {
+                        (ct, _) if ct.mirek_valid && ct.mirek.is_some() => {
+                            action.color_temperature =
+                                Some(SceneColorTempState { mirek: ct.mirek });
+                        }
+                        (_, Some(color)) => {
+                            action.color = Some(ColorFeatureBasic { xy: color.xy });
+                        }
+                        _ => {}
+                    }
+                }
+                SceneAction {
+                    target: l.rid(),
+                    action,
+                }
+            })
+            .collect::<Vec<_>>();
+        actions.sort_by(|a, b| a.target.rid.cmp(&b.target.rid));
+
+        Ok(SceneBuilder::new(name, group).actions(actions))
     }
 
     pub async fn create_scene(&self, builder: SceneBuilder) -> Result<Scene, HueAPIError> {
+        if builder.is_unique_in_group() {
+            let cache = self.cache.lock().expect("lock cache");
+            if let Some(existing) = cache
+                .scenes
+                .values()
+                .find(|s| &s.group == builder.group() && s.metadata.name == builder.name())
+            {
+                return Err(HueAPIError::InvalidInput(format!(
+                    "scene '{}' already exists in group {} as {}",
+                    existing.metadata.name,
+                    builder.group().rid,
+                    existing.id
+                )));
+            }
+        }
         let rid = self
             .api
             .post_scene(serde_json::to_value(builder).unwrap())
             .await?;
-        let data = self.api.get_scene(rid.rid).await?;
+        let data = Arc::new(self.api.get_scene(rid.rid).await?);
         self.cache
             .lock()
             .expect("lock cache")
@@ -869,15 +1699,31 @@ impl Bridge {
         Ok(Scene::new(&self, data))
     }
 
-    // pub async fn update_scene(&mut self, )
-
-    pub async fn delete_scene(
+    /// Apply `commands` to the scene `id`, then re-fetch it and update the
+    /// cache. Commands are merged in order, so a later command overrides an
+    /// earlier one, and [SceneCommand::Actions] replaces the scene's actions
+    /// outright rather than appending to them.
+    pub async fn update_scene(
         &self,
         id: impl Into<String>,
-    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
-        let res = self.api.delete_scene(id).await?;
-        delete_from_cache(&mut self.cache.lock().expect("lock cache"), &res);
-        Ok(res)
+        commands: &[SceneCommand],
+    ) -> Result<Scene<'_>, HueAPIError> {
+        let id = id.into();
+        let payload = merge_commands(commands);
+        self.api.put_scene(&id, &payload).await?;
+        let data = Arc::new(self.api.get_scene(id).await?);
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .scenes
+            .insert(data.id.clone(), data.clone());
+        Ok(Scene::new(self, data))
+    }
+
+    pub async fn delete_scene(&self, id: impl Into<String>) -> Result<DeleteOutcome, HueAPIError> {
+        let rids = self.api.delete_scene(id).await?;
+        let removed = self.evict_from_cache(&rids);
+        Ok(DeleteOutcome { rids, removed })
     }
 
     pub fn smart_scene(&self, id: impl Into<String>) -> Option<SmartScene> {
@@ -907,11 +1753,41 @@ impl Bridge {
         &self,
         builder: SmartSceneBuilder,
     ) -> Result<SmartScene, HueAPIError> {
+        if builder.schedules().is_empty() {
+            return Err(HueAPIError::InvalidInput(
+                "smart scene has no schedule".into(),
+            ));
+        }
+        builder
+            .schedules()
+            .iter()
+            .try_for_each(Schedule::validate)?;
+        {
+            let cache = self.cache.lock().expect("lock cache");
+            for target in builder.targets() {
+                if target.rtype != ResourceType::Scene {
+                    return Err(HueAPIError::InvalidInput(format!(
+                        "timeslot target {} is a {:?}, not a scene",
+                        target.rid, target.rtype
+                    )));
+                }
+                if let Some(scene) = cache.scenes.get(&target.rid) {
+                    if &scene.group != builder.group() {
+                        return Err(HueAPIError::InvalidInput(format!(
+                            "scene '{}' belongs to group {}, not smart scene group {}",
+                            scene.metadata.name,
+                            scene.group.rid,
+                            builder.group().rid
+                        )));
+                    }
+                }
+            }
+        }
         let rid = self
             .api
             .post_smart_scene(serde_json::to_value(builder).unwrap())
             .await?;
-        let data = self.api.get_smart_scene(rid.rid).await?;
+        let data = Arc::new(self.api.get_smart_scene(rid.rid).await?);
         self.cache
             .lock()
             .expect("lock cache")
@@ -923,10 +1799,10 @@ impl Bridge {
     pub async fn delete_smart_scene(
         &self,
         id: impl Into<String>,
-    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
-        let res = self.api.delete_smart_scene(id).await?;
-        delete_from_cache(&mut self.cache.lock().expect("lock cache"), &res);
-        Ok(res)
+    ) -> Result<DeleteOutcome, HueAPIError> {
+        let rids = self.api.delete_smart_scene(id).await?;
+        let removed = self.evict_from_cache(&rids);
+        Ok(DeleteOutcome { rids, removed })
     }
 
     pub fn light_level(&self, id: impl Into<String>) -> Option<LightLevel> {
@@ -1063,16 +1939,51 @@ impl Bridge {
             .collect()
     }
 
+    /// The [Zone] named exactly `name`.
+    pub fn zone_by_name(&self, name: &str) -> Option<Zone<'_>> {
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .zones
+            .values()
+            .find(|data| data.metadata.name == name)
+            .map(|data| Zone::new(self, data.clone()))
+    }
+
+    /// The [Zone] named `name`, ignoring case.
+    pub fn zone_by_name_ci(&self, name: &str) -> Option<Zone<'_>> {
+        let name = name.to_lowercase();
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .zones
+            .values()
+            .find(|data| data.metadata.name.to_lowercase() == name)
+            .map(|data| Zone::new(self, data.clone()))
+    }
+
     pub fn n_zones(&self) -> usize {
         self.cache.lock().expect("lock cache").zones.len()
     }
 
+    /// The [Zone]s with the given [ZoneArchetype], such as all upstairs zones.
+    pub fn zones_with_archetype(&self, archetype: ZoneArchetype) -> Vec<Zone<'_>> {
+        self.cache
+            .lock()
+            .expect("lock cache")
+            .zones
+            .values()
+            .filter(|data| data.metadata.archetype == archetype)
+            .map(|data| Zone::new(self, data.clone()))
+            .collect()
+    }
+
     pub async fn create_zone(&self, builder: ZoneBuilder) -> Result<Zone, HueAPIError> {
         let rid = self
             .api
             .post_zone(serde_json::to_value(builder).unwrap())
             .await?;
-        let data = self.api.get_zone(rid.rid).await?;
+        let data = Arc::new(self.api.get_zone(rid.rid).await?);
         self.cache
             .lock()
             .expect("lock cache")
@@ -1081,18 +1992,15 @@ impl Bridge {
         Ok(Zone::new(&self, data))
     }
 
-    pub async fn delete_zone(
-        &self,
-        id: impl Into<String>,
-    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
-        let res = self.api.delete_zone(id).await?;
-        delete_from_cache(&mut self.cache.lock().expect("lock cache"), &res);
-        Ok(res)
+    pub async fn delete_zone(&self, id: impl Into<String>) -> Result<DeleteOutcome, HueAPIError> {
+        let rids = self.api.delete_zone(id).await?;
+        let removed = self.evict_from_cache(&rids);
+        Ok(DeleteOutcome { rids, removed })
     }
 }
 
 /// Internal representation of a [Bridge].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct BridgeData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -1105,17 +2013,41 @@ pub struct BridgeData {
     pub time_zone: TimeZone,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct TimeZone {
     pub time_zone: String,
 }
 
+/// A bridge as reported by the Hue discovery service.
+#[derive(Debug, Deserialize)]
+struct DiscoveredBridge {
+    id: String,
+    internalipaddress: IpAddr,
+    #[allow(dead_code)]
+    port: Option<u32>,
+}
+
 /// Builder structure representing a [Bridge] that is not yet fully configured.
 pub struct BridgeBuilder {
     addr: Option<IpAddr>,
     app_key: Option<String>,
     client_key: Option<String>,
     version: Version,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    max_retries: u8,
+    retry_backoff: Duration,
+    retry_max_backoff: Duration,
+    retry_writes: bool,
+    #[cfg(feature = "sse")]
+    sse_reconnect: bool,
+    #[cfg(feature = "sse")]
+    sse_max_backoff: Duration,
+    root_certificate: Option<Certificate>,
+    bridge_id: Option<String>,
+    accept_invalid_certs: Option<bool>,
+    cache: Option<BridgeCache>,
+    transport: Option<Arc<dyn HueTransport>>,
 }
 
 impl Default for BridgeBuilder {
@@ -1125,6 +2057,21 @@ impl Default for BridgeBuilder {
             app_key: None,
             client_key: None,
             version: Default::default(),
+            timeout: None,
+            connect_timeout: None,
+            max_retries: 0,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+            retry_max_backoff: DEFAULT_RETRY_MAX_BACKOFF,
+            retry_writes: false,
+            #[cfg(feature = "sse")]
+            sse_reconnect: true,
+            #[cfg(feature = "sse")]
+            sse_max_backoff: DEFAULT_SSE_MAX_BACKOFF,
+            root_certificate: None,
+            bridge_id: None,
+            accept_invalid_certs: None,
+            cache: None,
+            transport: None,
         }
     }
 }
@@ -1134,38 +2081,47 @@ impl BridgeBuilder {
         BridgeBuilder::default()
     }
 
-    async fn discover_http() -> Result<Self, BridgeDiscoveryError> {
-        #[derive(Debug, Deserialize)]
-        struct Discovery {
-            #[allow(dead_code)]
-            id: String,
-            internalipaddress: IpAddr,
-            #[allow(dead_code)]
-            port: u32,
-        }
-
+    async fn discover_http_bridges() -> Result<Vec<DiscoveredBridge>, BridgeDiscoveryError> {
         match reqwest::get("https://discovery.meethue.com").await {
-            Ok(res) => match res.json::<Vec<Discovery>>().await {
-                Ok(devs) => match devs.get(0) {
-                    Some(dev) => Ok(BridgeBuilder {
-                        addr: Some(dev.internalipaddress.into()),
-                        ..Default::default()
-                    }),
-                    _ => Err(BridgeDiscoveryError::NotFound),
-                },
+            Ok(res) => match res.json::<Vec<DiscoveredBridge>>().await {
+                Ok(devs) => Ok(devs),
                 _ => Err(BridgeDiscoveryError::HTTPUnavailable),
             },
             _ => Err(BridgeDiscoveryError::HTTPUnavailable),
         }
     }
 
+    async fn discover_http() -> Result<Self, BridgeDiscoveryError> {
+        match BridgeBuilder::discover_http_bridges().await?.first() {
+            Some(dev) => Ok(BridgeBuilder {
+                addr: Some(dev.internalipaddress),
+                ..Default::default()
+            }),
+            _ => Err(BridgeDiscoveryError::NotFound),
+        }
+    }
+
+    /// Locate a specific bridge through the Hue discovery service, using the
+    /// bridge id printed on the device (case-insensitive).
+    pub async fn discover_by_id(bridge_id: &str) -> Result<Self, BridgeDiscoveryError> {
+        BridgeBuilder::discover_http_bridges()
+            .await?
+            .into_iter()
+            .find(|dev| dev.id.eq_ignore_ascii_case(bridge_id))
+            .map(|dev| BridgeBuilder {
+                addr: Some(dev.internalipaddress),
+                ..Default::default()
+            })
+            .ok_or(BridgeDiscoveryError::NotFound)
+    }
+
     #[cfg(feature = "mdns")]
     async fn discover_mdns() -> Result<Self, BridgeDiscoveryError> {
         use futures_util::{pin_mut, stream::StreamExt};
         const SERVICE_NAME: &'static str = "_hue._tcp.local";
 
         let stream = mdns::discover::all(SERVICE_NAME, Duration::from_secs(15))
-            .unwrap()
+            .map_err(|_| BridgeDiscoveryError::MDNSUnavailable)?
             .listen();
         pin_mut!(stream);
 
@@ -1199,12 +2155,46 @@ impl BridgeBuilder {
         return Err(BridgeDiscoveryError::MDNSUnavailable);
     }
 
+    /// Locate a bridge on the local network via mDNS (with the `mdns` feature),
+    /// falling back to the Hue discovery service. When every method fails, the
+    /// error reports why each one did.
     pub async fn discover() -> Result<Self, BridgeDiscoveryError> {
         #[cfg(feature = "mdns")]
-        if let Ok(bridge) = BridgeBuilder::discover_mdns().await {
-            return Ok(bridge);
+        let mdns = match BridgeBuilder::discover_mdns().await {
+            Ok(bridge) => return Ok(bridge),
+            Err(e) => e,
+        };
+        let res = BridgeBuilder::discover_http().await;
+        #[cfg(feature = "mdns")]
+        let res = res.map_err(|http| BridgeDiscoveryError::AllMethodsFailed {
+            mdns: Box::new(mdns),
+            http: Box::new(http),
+        });
+        res
+    }
+
+    pub fn addr(mut self, addr: impl Into<IpAddr>) -> Self {
+        self.addr = Some(addr.into());
+        self
+    }
+
+    /// Set the bridge address from an IP address or a hostname, resolving the
+    /// hostname if necessary.
+    pub async fn addr_from_str(mut self, host: &str) -> Result<Self, BridgeBuildError> {
+        if let Ok(addr) = host.parse::<IpAddr>() {
+            self.addr = Some(addr);
+            return Ok(self);
+        }
+        match tokio::net::lookup_host((host, 443)).await {
+            Ok(mut addrs) => match addrs.next() {
+                Some(addr) => {
+                    self.addr = Some(addr.ip());
+                    Ok(self)
+                }
+                None => Err(BridgeBuildError::UnresolvedHost),
+            },
+            Err(_) => Err(BridgeBuildError::UnresolvedHost),
         }
-        BridgeBuilder::discover_http().await
     }
 
     pub fn app_key(mut self, key: &str) -> Self {
@@ -1222,10 +2212,125 @@ impl BridgeBuilder {
         self
     }
 
+    /// Fail any request to the bridge that takes longer than `timeout` with
+    /// [HueAPIError::Timeout]. Unbounded by default; 10 seconds is a sensible
+    /// choice for a bridge on the local network. The event stream used by
+    /// `listen` is not affected.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Give up on connecting to the bridge after `timeout`. Unbounded by
+    /// default; 5 seconds is a sensible choice.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Trust only `certificate` as the root CA for the bridge's certificate,
+    /// in place of both the bundled Hue root CA and the system's root
+    /// certificates, and verify certificates strictly.
+    ///
+    /// By default certificates are not verified at all. Bridge certificates
+    /// name the bridge ID rather than its IP address, so this is usually
+    /// combined with [BridgeBuilder::verify_bridge_id].
+    pub fn root_certificate(mut self, certificate: Certificate) -> Self {
+        self.root_certificate = Some(certificate);
+        self
+    }
+
+    /// Verify the bridge's certificate strictly, including that it was issued
+    /// to `bridge_id`, as found in [BridgeData::bridge_id]. Requests are
+    /// addressed to the bridge ID and resolved to the configured IP address.
+    ///
+    /// By default certificates are not verified at all. To migrate, read the
+    /// ID from an existing connection once, store it with the app key, and
+    /// pass it here.
+    pub fn verify_bridge_id(mut self, bridge_id: impl Into<String>) -> Self {
+        self.bridge_id = Some(bridge_id.into());
+        self
+    }
+
+    /// Whether to accept a bridge certificate that fails verification, for both
+    /// HTTPS requests and the streaming DTLS handshake. Enabled by default,
+    /// unless [BridgeBuilder::root_certificate] or
+    /// [BridgeBuilder::verify_bridge_id] is set.
+    ///
+    /// While enabled, anyone able to intercept traffic on the local network
+    /// can impersonate the bridge and read the app key. Disable it when the
+    /// bridge is reached through a proxy presenting a certificate for its own
+    /// hostname, or together with the options above.
+    pub fn accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.accept_invalid_certs = Some(accept);
+        self
+    }
+
+    /// Retry failed requests up to `retries` times when the failure looks
+    /// transient: connection errors, timeouts, and HTTP 429 or 503 responses.
+    /// Only GETs are retried unless [BridgeBuilder::retry_writes] is set.
+    /// Defaults to `0`, meaning requests are never retried.
+    pub fn max_retries(mut self, retries: u8) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// The delay before the first retry, doubled on each subsequent attempt.
+    /// A `Retry-After` header from the bridge takes precedence. Defaults to
+    /// 250ms.
+    pub fn retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// The longest delay before a retry, also capping the delay requested by a
+    /// `Retry-After` header. Defaults to 30 seconds.
+    pub fn retry_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.retry_max_backoff = max_backoff;
+        self
+    }
+
+    /// Also retry PUT and DELETE requests. Defaults to `false`.
+    pub fn retry_writes(mut self, retry_writes: bool) -> Self {
+        self.retry_writes = retry_writes;
+        self
+    }
+
+    /// Whether [Bridge::listen] reconnects when the event stream closes or
+    /// fails, re-fetching all resources so no changes are missed. Reconnects
+    /// back off exponentially from one second. Defaults to `true`.
+    #[cfg(feature = "sse")]
+    pub fn sse_reconnect(mut self, reconnect: bool) -> Self {
+        self.sse_reconnect = reconnect;
+        self
+    }
+
+    /// The longest delay between event stream reconnection attempts. Defaults
+    /// to one minute.
+    #[cfg(feature = "sse")]
+    pub fn sse_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.sse_max_backoff = max_backoff;
+        self
+    }
+
+    /// Starts the [Bridge] with a cache produced by [Bridge::export_cache],
+    /// so reads are served before the first refresh.
+    pub fn with_cache(mut self, json: &str) -> Result<Self, serde_json::Error> {
+        self.cache = Some(serde_json::from_str(json)?);
+        Ok(self)
+    }
+
+    /// Sends requests through `transport` instead of over HTTPS. See
+    /// [HueTransport].
+    pub fn transport(mut self, transport: impl HueTransport + 'static) -> Self {
+        self.transport = Some(Arc::new(transport));
+        self
+    }
+
     pub fn build(self) -> Bridge {
         let addr = self.addr.unwrap_or([0u8, 0, 0, 0].into());
         let app_key = self.app_key.unwrap_or_default();
-        let api = if self.version == Version::V2 {
+        let mut api = if self.version == Version::V2 {
             #[cfg(feature = "streaming")]
             if self.client_key.is_some() {
                 BridgeClient::new_with_streaming(addr, &app_key, self.client_key.unwrap());
@@ -1237,8 +2342,142 @@ impl BridgeBuilder {
         } else {
             todo!()
         };
+        if self.timeout.is_some() || self.connect_timeout.is_some() {
+            api.set_timeouts(self.timeout, self.connect_timeout);
+        }
+        api.set_retries(
+            self.max_retries,
+            self.retry_backoff,
+            self.retry_max_backoff,
+            self.retry_writes,
+        );
+        let verify = self.root_certificate.is_some() || self.bridge_id.is_some();
+        let accept_invalid_certs = self.accept_invalid_certs.unwrap_or(!verify);
+        if verify || !accept_invalid_certs {
+            api.set_verification(self.root_certificate, self.bridge_id, accept_invalid_certs);
+        }
+        if let Some(transport) = self.transport {
+            api.set_transport(transport);
+        }
+
+        Bridge {
+            cache: Arc::new(Mutex::new(self.cache.unwrap_or_default())),
+            #[cfg(feature = "sse")]
+            sse_reconnect: self.sse_reconnect,
+            #[cfg(feature = "sse")]
+            sse_max_backoff: self.sse_max_backoff,
+            ..Bridge::from_api(api)
+        }
+    }
+}
+
+/// How [Bridge::listen] handles the event stream closing or stalling.
+#[cfg(feature = "sse")]
+#[derive(Clone, Copy, Debug)]
+struct ListenOptions {
+    stall_timeout: Option<Duration>,
+    reconnect: bool,
+    max_backoff: Duration,
+}
+
+/// A stream of events from the bridge, as read by [Bridge::listen].
+#[cfg(feature = "sse")]
+trait EventStream:
+    futures_util::Stream<Item = Result<reqwest_eventsource::Event, reqwest_eventsource::Error>> + Unpin
+{
+    fn close(&mut self);
+}
+
+#[cfg(feature = "sse")]
+impl EventStream for reqwest_eventsource::EventSource {
+    fn close(&mut self) {
+        reqwest_eventsource::EventSource::close(self)
+    }
+}
+
+/// Applies the events read from the streams opened by `connect` to `cache`,
+/// calling `cb` with the changes. When a stream closes, fails or stalls, a new
+/// one is opened if `options` allow, and the cache is refreshed in case events
+/// were missed.
+#[cfg(feature = "sse")]
+async fn read_event_streams<S, F, Fut, C>(
+    mut connect: F,
+    api: &BridgeClient,
+    cache: &Mutex<BridgeCache>,
+    in_flight: &tokio::sync::Mutex<Result<(), HueAPIError>>,
+    options: ListenOptions,
+    cb: C,
+) where
+    S: EventStream,
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<S, HueAPIError>>,
+    C: Fn(Vec<HueEventKind>),
+{
+    use futures_util::StreamExt;
+    use reqwest_eventsource::Event;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut backoff = SSE_INITIAL_BACKOFF;
+    loop {
+        let stalled = match connect().await {
+            Ok(mut es) => loop {
+                let next = match options.stall_timeout {
+                    Some(timeout) => tokio::time::timeout(timeout, es.next()).await,
+                    None => Ok(es.next().await),
+                };
+                match next {
+                    Ok(Some(Ok(Event::Open))) => {
+                        backoff = SSE_INITIAL_BACKOFF;
+                    }
+                    Ok(Some(Ok(Event::Message(message)))) => {
+                        match serde_json::from_str::<Vec<HueEvent>>(&message.data) {
+                            Ok(data) => {
+                                // Release the cache before calling back, so that a
+                                // panicking callback cannot poison it
+                                let changes =
+                                    upsert_to_cache(&mut cache.lock().expect("lock cache"), data);
+                                if catch_unwind(AssertUnwindSafe(|| cb(changes))).is_err() {
+                                    log::error!("listen callback panicked");
+                                }
+                            }
+                            Err(e) => {
+                                log::error!("{e}");
+                                cache.lock().expect("lock cache").deserialize_failures += 1;
+                            }
+                        }
+                    }
+                    Ok(Some(Err(e))) => {
+                        log::error!("{e}");
+                        if options.reconnect {
+                            es.close();
+                            break false;
+                        }
+                    }
+                    Ok(None) => break false,
+                    Err(_) => {
+                        log::warn!("event stream stalled, reconnecting");
+                        es.close();
+                        break true;
+                    }
+                }
+            },
+            Err(e) => {
+                log::error!("{e:?}");
+                false
+            }
+        };
+
+        if !stalled {
+            if !options.reconnect {
+                return;
+            }
+            log::warn!("event stream closed, reconnecting in {backoff:?}");
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(options.max_backoff);
+        }
 
-        Bridge::from_api(api)
+        // Events may have been missed while disconnected
+        let _ = refresh_cache(api, cache, in_flight).await;
     }
 }
 
@@ -1246,10 +2485,10 @@ impl BridgeBuilder {
 fn upsert_to_cache(
     cache: &mut MutexGuard<'_, BridgeCache>,
     data: Vec<HueEvent>,
-) -> HashSet<ResourceIdentifier> {
+) -> Vec<HueEventKind> {
     use crate::event::{HueEventData, HueEventType};
 
-    let mut changes: HashSet<ResourceIdentifier> = Default::default();
+    let mut changes = vec![];
 
     for event in data {
         match event.etype {
@@ -1258,59 +2497,137 @@ fn upsert_to_cache(
                     match event_data {
                         HueEventData::Button(patch) => {
                             let id = patch.get("id").expect("no id").as_str().unwrap().to_owned();
-                            if let Some(data) = cache.buttons.get(&id) {
-                                let data: ButtonData = merge_resource_data(data, patch);
-                                changes.insert(data.rid());
-                                cache.buttons.insert(id, data);
+                            if let Some(before) = cache.buttons.get(&id) {
+                                let data: ButtonData = merge_resource_data(before.as_ref(), patch);
+                                changes.push(HueEventKind::Updated {
+                                    rid: data.rid(),
+                                    before: Resource::Button(before.as_ref().clone()),
+                                    after: Resource::Button(data.clone()),
+                                });
+                                cache.buttons.insert(id, Arc::new(data));
+                            }
+                        }
+                        HueEventData::Contact(patch) => {
+                            let id = patch.get("id").expect("no id").as_str().unwrap().to_owned();
+                            if let Some(before) = cache.contacts.get(&id) {
+                                let data: ContactData = merge_resource_data(before.as_ref(), patch);
+                                changes.push(HueEventKind::Updated {
+                                    rid: data.rid(),
+                                    before: Resource::Contact(before.as_ref().clone()),
+                                    after: Resource::Contact(data.clone()),
+                                });
+                                cache.contacts.insert(id, Arc::new(data));
                             }
                         }
                         HueEventData::DevicePower(patch) => {
                             let id = patch.get("id").expect("no id").as_str().unwrap().to_owned();
-                            if let Some(data) = cache.power.get(&id) {
-                                let data: DevicePowerData = merge_resource_data(data, patch);
-                                changes.insert(data.rid());
-                                cache.power.insert(id, data);
+                            if let Some(before) = cache.power.get(&id) {
+                                let data: DevicePowerData =
+                                    merge_resource_data(before.as_ref(), patch);
+                                changes.push(HueEventKind::Updated {
+                                    rid: data.rid(),
+                                    before: Resource::DevicePower(before.as_ref().clone()),
+                                    after: Resource::DevicePower(data.clone()),
+                                });
+                                cache.power.insert(id, Arc::new(data));
                             }
                         }
                         HueEventData::EntertainmentConfiguration(patch) => {
                             let id = patch.get("id").expect("no id").as_str().unwrap().to_owned();
-                            if let Some(data) = cache.entertainment_configurations.get(&id) {
+                            if let Some(before) = cache.entertainment_configurations.get(&id) {
                                 let data: EntertainmentConfigurationData =
-                                    merge_resource_data(data, patch);
-                                changes.insert(data.rid());
-                                cache.entertainment_configurations.insert(id, data);
+                                    merge_resource_data(before.as_ref(), patch);
+                                changes.push(HueEventKind::Updated {
+                                    rid: data.rid(),
+                                    before: Resource::EntertainmentConfiguration(
+                                        before.as_ref().clone(),
+                                    ),
+                                    after: Resource::EntertainmentConfiguration(data.clone()),
+                                });
+                                cache
+                                    .entertainment_configurations
+                                    .insert(id, Arc::new(data));
                             }
                         }
                         HueEventData::Entertainment(patch) => {
                             let id = patch.get("id").expect("no id").as_str().unwrap().to_owned();
-                            if let Some(data) = cache.entertainments.get(&id) {
-                                let data: EntertainmentData = merge_resource_data(data, patch);
-                                changes.insert(data.rid());
-                                cache.entertainments.insert(id, data);
+                            if let Some(before) = cache.entertainments.get(&id) {
+                                let data: EntertainmentData =
+                                    merge_resource_data(before.as_ref(), patch);
+                                changes.push(HueEventKind::Updated {
+                                    rid: data.rid(),
+                                    before: Resource::Entertainment(before.as_ref().clone()),
+                                    after: Resource::Entertainment(data.clone()),
+                                });
+                                cache.entertainments.insert(id, Arc::new(data));
                             }
                         }
                         HueEventData::Group(patch) => {
                             let id = patch.get("id").expect("no id").as_str().unwrap().to_owned();
-                            if let Some(data) = cache.groups.get(&id) {
-                                let data: GroupData = merge_resource_data(data, patch);
-                                changes.insert(data.rid());
-                                cache.groups.insert(id, data);
+                            if let Some(before) = cache.groups.get(&id) {
+                                let data: GroupData = merge_resource_data(before.as_ref(), patch);
+                                changes.push(HueEventKind::Updated {
+                                    rid: data.rid(),
+                                    before: Resource::Group(before.as_ref().clone()),
+                                    after: Resource::Group(data.clone()),
+                                });
+                                cache.groups.insert(id, Arc::new(data));
+                            }
+                        }
+                        HueEventData::GroupedLightLevel(patch) => {
+                            let id = patch.get("id").expect("no id").as_str().unwrap().to_owned();
+                            if let Some(before) = cache.grouped_light_levels.get(&id) {
+                                let data: GroupedLightLevelData =
+                                    merge_resource_data(before.as_ref(), patch);
+                                changes.push(HueEventKind::Updated {
+                                    rid: data.rid(),
+                                    before: Resource::GroupedLightLevel(before.as_ref().clone()),
+                                    after: Resource::GroupedLightLevel(data.clone()),
+                                });
+                                cache.grouped_light_levels.insert(id, Arc::new(data));
+                            }
+                        }
+                        HueEventData::GroupedMotion(patch) => {
+                            let id = patch.get("id").expect("no id").as_str().unwrap().to_owned();
+                            if let Some(before) = cache.grouped_motions.get(&id) {
+                                let data: GroupedMotionData =
+                                    merge_resource_data(before.as_ref(), patch);
+                                changes.push(HueEventKind::Updated {
+                                    rid: data.rid(),
+                                    before: Resource::GroupedMotion(before.as_ref().clone()),
+                                    after: Resource::GroupedMotion(data.clone()),
+                                });
+                                cache.grouped_motions.insert(id, Arc::new(data));
                             }
                         }
                         HueEventData::Light(patch) => {
                             let id = patch.get("id").expect("no id").as_str().unwrap().to_owned();
-                            if let Some(data) = cache.lights.get(&id) {
-                                let data: LightData = merge_resource_data(data, patch);
-                                changes.insert(data.rid());
-                                cache.lights.insert(id, data);
+                            if let Some(before) = cache.lights.get(&id) {
+                                let data: LightData = merge_resource_data(before.as_ref(), patch);
+                                changes.push(HueEventKind::Updated {
+                                    rid: data.rid(),
+                                    before: Resource::Light(before.as_ref().clone()),
+                                    after: Resource::Light(data.clone()),
+                                });
+                                cache.lights.insert(id, Arc::new(data));
                             }
                         }
                         HueEventData::Scene(patch) => {
                             let id = patch.get("id").expect("no id").as_str().unwrap().to_owned();
-                            if let Some(data) = cache.scenes.get(&id) {
-                                let data: SceneData = merge_resource_data(data, patch);
-                                changes.insert(data.rid());
-                                cache.scenes.insert(id, data);
+                            if let Some(before) = cache.scenes.get(&id) {
+                                let data: SceneData = merge_resource_data(before.as_ref(), patch);
+                                let activated = scene_activated(before, &data);
+                                changes.push(HueEventKind::Updated {
+                                    rid: data.rid(),
+                                    before: Resource::Scene(before.as_ref().clone()),
+                                    after: Resource::Scene(data.clone()),
+                                });
+                                if activated {
+                                    cache
+                                        .last_active_scenes
+                                        .insert(data.group.rid.clone(), id.clone());
+                                }
+                                cache.scenes.insert(id, Arc::new(data));
                             }
                         }
                         _ => {
@@ -1325,12 +2642,15 @@ fn upsert_to_cache(
                     .into_iter()
                     .filter_map(|event_data| match event_data {
                         HueEventData::AuthV1
-                        | HueEventData::BehaviorInstance
                         | HueEventData::Geofence
                         | HueEventData::PublicImage
+                        | HueEventData::Recipe
                         | HueEventData::Taurus7455
                         | HueEventData::ZigbeeBridgeConnectivity
                         | HueEventData::Unknown => None,
+                        HueEventData::BehaviorInstance(d) => {
+                            added_resource(d).map(Resource::BehaviorInstance)
+                        }
                         HueEventData::BehaviorScript(d) => {
                             Some(Resource::BehaviorScript(serde_json::from_value(d).unwrap()))
                         }
@@ -1375,6 +2695,12 @@ fn upsert_to_cache(
                         HueEventData::Group(d) => {
                             Some(Resource::Group(serde_json::from_value(d).unwrap()))
                         }
+                        HueEventData::GroupedLightLevel(d) => {
+                            added_resource(d).map(Resource::GroupedLightLevel)
+                        }
+                        HueEventData::GroupedMotion(d) => {
+                            added_resource(d).map(Resource::GroupedMotion)
+                        }
                         HueEventData::HomeKit(d) => {
                             Some(Resource::HomeKit(serde_json::from_value(d).unwrap()))
                         }
@@ -1425,7 +2751,8 @@ fn upsert_to_cache(
                         }
                     })
                     .collect::<Vec<Resource>>();
-                insert_to_cache(cache, resources);
+                insert_to_cache(cache, resources.clone());
+                changes.extend(resources.into_iter().map(HueEventKind::Added));
             }
             HueEventType::Delete => {
                 let rids = event
@@ -1433,12 +2760,19 @@ fn upsert_to_cache(
                     .into_iter()
                     .filter_map(|d| match d {
                         HueEventData::AuthV1
-                        | HueEventData::BehaviorInstance
                         | HueEventData::Geofence
                         | HueEventData::PublicImage
+                        | HueEventData::Recipe
                         | HueEventData::Taurus7455
                         | HueEventData::ZigbeeBridgeConnectivity
                         | HueEventData::Unknown => None,
+                        HueEventData::BehaviorInstance(d) => {
+                            let rid = d.get("id").expect("no id").as_str().unwrap().to_owned();
+                            Some(ResourceIdentifier {
+                                rid,
+                                rtype: ResourceType::BehaviorInstance,
+                            })
+                        }
                         HueEventData::BehaviorScript(d) => {
                             let rid = d.get("id").expect("no id").as_str().unwrap().to_owned();
                             Some(ResourceIdentifier {
@@ -1537,6 +2871,20 @@ fn upsert_to_cache(
                                 rtype: ResourceType::Group,
                             })
                         }
+                        HueEventData::GroupedLightLevel(d) => {
+                            let rid = d.get("id").expect("no id").as_str().unwrap().to_owned();
+                            Some(ResourceIdentifier {
+                                rid,
+                                rtype: ResourceType::GroupedLightLevel,
+                            })
+                        }
+                        HueEventData::GroupedMotion(d) => {
+                            let rid = d.get("id").expect("no id").as_str().unwrap().to_owned();
+                            Some(ResourceIdentifier {
+                                rid,
+                                rtype: ResourceType::GroupedMotion,
+                            })
+                        }
                         HueEventData::HomeKit(d) => {
                             let rid = d.get("id").expect("no id").as_str().unwrap().to_owned();
                             Some(ResourceIdentifier {
@@ -1652,6 +3000,7 @@ fn upsert_to_cache(
                     })
                     .collect::<Vec<ResourceIdentifier>>();
                 delete_from_cache(cache, &rids);
+                changes.extend(rids.into_iter().map(HueEventKind::Deleted));
             }
             HueEventType::Error => {
                 log::warn!("NOT IMPLEMENTED: {:?}", event);
@@ -1662,6 +3011,23 @@ fn upsert_to_cache(
     changes
 }
 
+/// A change to a cached resource, as reported to [Bridge::listen_events].
+#[cfg(feature = "sse")]
+#[derive(Clone, Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum HueEventKind {
+    /// A resource was created.
+    Added(Resource),
+    /// A resource changed, with its data before and after the change.
+    Updated {
+        rid: ResourceIdentifier,
+        before: Resource,
+        after: Resource,
+    },
+    /// A resource was removed.
+    Deleted(ResourceIdentifier),
+}
+
 #[cfg(feature = "sse")]
 fn merge_resource_data<D: DeserializeOwned, S: Serialize>(data: S, patch: serde_json::Value) -> D {
     use json_patch::merge;
@@ -1670,275 +3036,1891 @@ fn merge_resource_data<D: DeserializeOwned, S: Serialize>(data: S, patch: serde_
     serde_json::from_value(json).unwrap()
 }
 
-#[derive(Debug, Default)]
-pub(crate) struct BridgeCache {
+/// Deserializes a resource added by an event, or logs and skips it when it
+/// does not have the modeled shape, e.g. after a firmware update.
+#[cfg(feature = "sse")]
+fn added_resource<D: DeserializeOwned>(data: serde_json::Value) -> Option<D> {
+    serde_json::from_value(data)
+        .map_err(|e| log::warn!("skipping added resource: {e}"))
+        .ok()
+}
+
+/// A summary of bridge data this crate could not fully understand, as returned
+/// by [Bridge::diagnostics]. Useful to attach when reporting API drift.
+#[derive(Clone, Debug, Default)]
+pub struct Diagnostics {
+    /// Unrecognized [ZoneArchetype] values on rooms and zones, with their counts.
+    pub unknown_zone_archetypes: HashMap<String, usize>,
+    /// Unrecognized [ProductArchetype] values on devices, with their counts.
+    pub unknown_product_archetypes: HashMap<String, usize>,
+    /// Number of lights reporting an effect or timed effect this crate does not know.
+    pub lights_with_unknown_effects: usize,
+    /// Number of software updates in a state this crate does not know.
+    pub unknown_software_update_states: usize,
+    /// Resources of types this crate does not model or could not deserialize,
+    /// with their counts, as of the last full fetch.
+    pub unmodeled_resources: HashMap<String, usize>,
+    /// Number of event stream messages which could not be deserialized.
+    pub deserialize_failures: usize,
+}
+
+/// In-memory store of the resources known to a [Bridge], kept up to date by
+/// [Bridge::refresh], [Bridge::poll], and [Bridge::listen].
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct BridgeCache {
     data: Option<BridgeData>,
-    behavior_scripts: HashMap<String, BehaviorScriptData>,
-    behavior_instances: HashMap<String, BehaviorInstanceData>,
-    buttons: HashMap<String, ButtonData>,
-    contacts: HashMap<String, ContactData>,
-    devices: HashMap<String, DeviceData>,
-    entertainment_configurations: HashMap<String, EntertainmentConfigurationData>,
-    entertainments: HashMap<String, EntertainmentData>,
-    geofence_clients: HashMap<String, GeofenceClientData>,
-    geolocations: HashMap<String, GeolocationData>,
-    groups: HashMap<String, GroupData>,
-    homes: HashMap<String, HomeData>,
-    homekits: HashMap<String, HomeKitData>,
-    lights: HashMap<String, LightData>,
-    light_levels: HashMap<String, LightLevelData>,
-    matters: HashMap<String, MatterData>,
-    matter_fabrics: HashMap<String, MatterFabricData>,
-    motions: HashMap<String, MotionData>,
-    motion_cameras: HashMap<String, MotionData>,
-    power: HashMap<String, DevicePowerData>,
-    rooms: HashMap<String, ZoneData>,
-    rotaries: HashMap<String, RelativeRotaryData>,
-    scenes: HashMap<String, SceneData>,
-    smart_scenes: HashMap<String, SmartSceneData>,
-    swu: HashMap<String, DeviceSoftwareUpdateData>,
-    tampers: HashMap<String, TamperData>,
-    temps: HashMap<String, TemperatureData>,
-    zigbee_conns: HashMap<String, ZigbeeConnectivityData>,
-    zigbee_dds: HashMap<String, ZigbeeDeviceDiscoveryData>,
-    zgp_conns: HashMap<String, ZGPConnectivityData>,
-    zones: HashMap<String, ZoneData>,
+    behavior_scripts: HashMap<String, Arc<BehaviorScriptData>>,
+    behavior_instances: HashMap<String, Arc<BehaviorInstanceData>>,
+    buttons: HashMap<String, Arc<ButtonData>>,
+    contacts: HashMap<String, Arc<ContactData>>,
+    devices: HashMap<String, Arc<DeviceData>>,
+    entertainment_configurations: HashMap<String, Arc<EntertainmentConfigurationData>>,
+    entertainments: HashMap<String, Arc<EntertainmentData>>,
+    geofence_clients: HashMap<String, Arc<GeofenceClientData>>,
+    geolocations: HashMap<String, Arc<GeolocationData>>,
+    groups: HashMap<String, Arc<GroupData>>,
+    #[serde(default)]
+    grouped_light_levels: HashMap<String, Arc<GroupedLightLevelData>>,
+    #[serde(default)]
+    grouped_motions: HashMap<String, Arc<GroupedMotionData>>,
+    homes: HashMap<String, Arc<HomeData>>,
+    homekits: HashMap<String, Arc<HomeKitData>>,
+    lights: HashMap<String, Arc<LightData>>,
+    light_levels: HashMap<String, Arc<LightLevelData>>,
+    matters: HashMap<String, Arc<MatterData>>,
+    matter_fabrics: HashMap<String, Arc<MatterFabricData>>,
+    motions: HashMap<String, Arc<MotionData>>,
+    motion_cameras: HashMap<String, Arc<MotionData>>,
+    power: HashMap<String, Arc<DevicePowerData>>,
+    rooms: HashMap<String, Arc<ZoneData>>,
+    rotaries: HashMap<String, Arc<RelativeRotaryData>>,
+    scenes: HashMap<String, Arc<SceneData>>,
+    smart_scenes: HashMap<String, Arc<SmartSceneData>>,
+    swu: HashMap<String, Arc<DeviceSoftwareUpdateData>>,
+    tampers: HashMap<String, Arc<TamperData>>,
+    temps: HashMap<String, Arc<TemperatureData>>,
+    zigbee_conns: HashMap<String, Arc<ZigbeeConnectivityData>>,
+    zigbee_dds: HashMap<String, Arc<ZigbeeDeviceDiscoveryData>>,
+    zgp_conns: HashMap<String, Arc<ZGPConnectivityData>>,
+    zones: HashMap<String, Arc<ZoneData>>,
+    /// Ids of resources this crate does not model, by type, as of the last
+    /// full fetch.
+    #[serde(skip)]
+    unmodeled: HashMap<String, HashSet<String>>,
+    #[serde(skip)]
+    deserialize_failures: usize,
+    /// The most recently activated scene id, by group id.
+    last_active_scenes: HashMap<String, String>,
+}
+
+/// Read-only access to the cached resources.
+impl BridgeCache {
+    /// Whether the cache holds data from the bridge, either fetched or restored.
+    pub fn is_populated(&self) -> bool {
+        self.data.is_some()
+    }
+
+    pub fn data(&self) -> Option<&BridgeData> {
+        self.data.as_ref()
+    }
+
+    /// Replaces the unmodeled resources with those of a new full fetch, given
+    /// by type and id.
+    fn set_unmodeled(&mut self, unmodeled: Vec<(String, String)>) {
+        self.unmodeled.clear();
+        for (rtype, id) in unmodeled {
+            self.unmodeled.entry(rtype).or_default().insert(id);
+        }
+    }
+
+    pub fn behavior_scripts(&self) -> &HashMap<String, Arc<BehaviorScriptData>> {
+        &self.behavior_scripts
+    }
+
+    pub fn behavior_instances(&self) -> &HashMap<String, Arc<BehaviorInstanceData>> {
+        &self.behavior_instances
+    }
+
+    pub fn buttons(&self) -> &HashMap<String, Arc<ButtonData>> {
+        &self.buttons
+    }
+
+    pub fn contacts(&self) -> &HashMap<String, Arc<ContactData>> {
+        &self.contacts
+    }
+
+    pub fn devices(&self) -> &HashMap<String, Arc<DeviceData>> {
+        &self.devices
+    }
+
+    pub fn entertainment_configurations(
+        &self,
+    ) -> &HashMap<String, Arc<EntertainmentConfigurationData>> {
+        &self.entertainment_configurations
+    }
+
+    pub fn entertainments(&self) -> &HashMap<String, Arc<EntertainmentData>> {
+        &self.entertainments
+    }
+
+    pub fn geofence_clients(&self) -> &HashMap<String, Arc<GeofenceClientData>> {
+        &self.geofence_clients
+    }
+
+    pub fn geolocations(&self) -> &HashMap<String, Arc<GeolocationData>> {
+        &self.geolocations
+    }
+
+    pub fn groups(&self) -> &HashMap<String, Arc<GroupData>> {
+        &self.groups
+    }
+
+    pub fn grouped_light_levels(&self) -> &HashMap<String, Arc<GroupedLightLevelData>> {
+        &self.grouped_light_levels
+    }
+
+    pub fn grouped_motions(&self) -> &HashMap<String, Arc<GroupedMotionData>> {
+        &self.grouped_motions
+    }
+
+    pub fn homes(&self) -> &HashMap<String, Arc<HomeData>> {
+        &self.homes
+    }
+
+    pub fn homekits(&self) -> &HashMap<String, Arc<HomeKitData>> {
+        &self.homekits
+    }
+
+    pub fn lights(&self) -> &HashMap<String, Arc<LightData>> {
+        &self.lights
+    }
+
+    pub fn light_levels(&self) -> &HashMap<String, Arc<LightLevelData>> {
+        &self.light_levels
+    }
+
+    pub fn matters(&self) -> &HashMap<String, Arc<MatterData>> {
+        &self.matters
+    }
+
+    pub fn matter_fabrics(&self) -> &HashMap<String, Arc<MatterFabricData>> {
+        &self.matter_fabrics
+    }
+
+    pub fn motions(&self) -> &HashMap<String, Arc<MotionData>> {
+        &self.motions
+    }
+
+    pub fn motion_cameras(&self) -> &HashMap<String, Arc<MotionData>> {
+        &self.motion_cameras
+    }
+
+    pub fn device_powers(&self) -> &HashMap<String, Arc<DevicePowerData>> {
+        &self.power
+    }
+
+    pub fn rooms(&self) -> &HashMap<String, Arc<ZoneData>> {
+        &self.rooms
+    }
+
+    pub fn relative_rotaries(&self) -> &HashMap<String, Arc<RelativeRotaryData>> {
+        &self.rotaries
+    }
+
+    pub fn scenes(&self) -> &HashMap<String, Arc<SceneData>> {
+        &self.scenes
+    }
+
+    pub fn smart_scenes(&self) -> &HashMap<String, Arc<SmartSceneData>> {
+        &self.smart_scenes
+    }
+
+    pub fn software_updates(&self) -> &HashMap<String, Arc<DeviceSoftwareUpdateData>> {
+        &self.swu
+    }
+
+    pub fn tampers(&self) -> &HashMap<String, Arc<TamperData>> {
+        &self.tampers
+    }
+
+    pub fn temperatures(&self) -> &HashMap<String, Arc<TemperatureData>> {
+        &self.temps
+    }
+
+    pub fn zigbee_connectivities(&self) -> &HashMap<String, Arc<ZigbeeConnectivityData>> {
+        &self.zigbee_conns
+    }
+
+    pub fn zigbee_device_discoveries(&self) -> &HashMap<String, Arc<ZigbeeDeviceDiscoveryData>> {
+        &self.zigbee_dds
+    }
+
+    pub fn zgp_connectivities(&self) -> &HashMap<String, Arc<ZGPConnectivityData>> {
+        &self.zgp_conns
+    }
+
+    pub fn zones(&self) -> &HashMap<String, Arc<ZoneData>> {
+        &self.zones
+    }
+
+    /// The cached data of the referenced resource, or [None] if it is not
+    /// cached or is of a type which is never cached.
+    pub fn resource(&self, rid: &ResourceIdentifier) -> Option<Resource> {
+        let id = &rid.rid;
+        match rid.rtype {
+            ResourceType::BehaviorInstance => self
+                .behavior_instances
+                .get(id)
+                .map(|d| Resource::BehaviorInstance(d.as_ref().clone())),
+            ResourceType::BehaviorScript => self
+                .behavior_scripts
+                .get(id)
+                .map(|d| Resource::BehaviorScript(d.as_ref().clone())),
+            ResourceType::BridgeHome => self
+                .homes
+                .get(id)
+                .map(|d| Resource::BridgeHome(d.as_ref().clone())),
+            ResourceType::Button => self
+                .buttons
+                .get(id)
+                .map(|d| Resource::Button(d.as_ref().clone())),
+            ResourceType::CameraMotion => self
+                .motion_cameras
+                .get(id)
+                .map(|d| Resource::CameraMotion(d.as_ref().clone())),
+            ResourceType::Contact => self
+                .contacts
+                .get(id)
+                .map(|d| Resource::Contact(d.as_ref().clone())),
+            ResourceType::Device => self
+                .devices
+                .get(id)
+                .map(|d| Resource::Device(d.as_ref().clone())),
+            ResourceType::DevicePower => self
+                .power
+                .get(id)
+                .map(|d| Resource::DevicePower(d.as_ref().clone())),
+            ResourceType::DeviceSoftwareUpdate => self
+                .swu
+                .get(id)
+                .map(|d| Resource::DeviceSoftwareUpdate(d.as_ref().clone())),
+            ResourceType::Entertainment => self
+                .entertainments
+                .get(id)
+                .map(|d| Resource::Entertainment(d.as_ref().clone())),
+            ResourceType::EntertainmentConfiguration => self
+                .entertainment_configurations
+                .get(id)
+                .map(|d| Resource::EntertainmentConfiguration(d.as_ref().clone())),
+            ResourceType::GeofenceClient => self
+                .geofence_clients
+                .get(id)
+                .map(|d| Resource::GeofenceClient(d.as_ref().clone())),
+            ResourceType::Geolocation => self
+                .geolocations
+                .get(id)
+                .map(|d| Resource::Geolocation(d.as_ref().clone())),
+            ResourceType::Group => self
+                .groups
+                .get(id)
+                .map(|d| Resource::Group(d.as_ref().clone())),
+            ResourceType::GroupedLightLevel => self
+                .grouped_light_levels
+                .get(id)
+                .map(|d| Resource::GroupedLightLevel(d.as_ref().clone())),
+            ResourceType::GroupedMotion => self
+                .grouped_motions
+                .get(id)
+                .map(|d| Resource::GroupedMotion(d.as_ref().clone())),
+            ResourceType::HomeKit => self
+                .homekits
+                .get(id)
+                .map(|d| Resource::HomeKit(d.as_ref().clone())),
+            ResourceType::Light => self
+                .lights
+                .get(id)
+                .map(|d| Resource::Light(d.as_ref().clone())),
+            ResourceType::LightLevel => self
+                .light_levels
+                .get(id)
+                .map(|d| Resource::LightLevel(d.as_ref().clone())),
+            ResourceType::Matter => self
+                .matters
+                .get(id)
+                .map(|d| Resource::Matter(d.as_ref().clone())),
+            ResourceType::MatterFabric => self
+                .matter_fabrics
+                .get(id)
+                .map(|d| Resource::MatterFabric(d.as_ref().clone())),
+            ResourceType::Motion => self
+                .motions
+                .get(id)
+                .map(|d| Resource::Motion(d.as_ref().clone())),
+            ResourceType::RelativeRotary => self
+                .rotaries
+                .get(id)
+                .map(|d| Resource::RelativeRotary(d.as_ref().clone())),
+            ResourceType::Room => self
+                .rooms
+                .get(id)
+                .map(|d| Resource::Room(d.as_ref().clone())),
+            ResourceType::Scene => self
+                .scenes
+                .get(id)
+                .map(|d| Resource::Scene(d.as_ref().clone())),
+            ResourceType::SmartScene => self
+                .smart_scenes
+                .get(id)
+                .map(|d| Resource::SmartScene(d.as_ref().clone())),
+            ResourceType::Tamper => self
+                .tampers
+                .get(id)
+                .map(|d| Resource::Tamper(d.as_ref().clone())),
+            ResourceType::Temperature => self
+                .temps
+                .get(id)
+                .map(|d| Resource::Temperature(d.as_ref().clone())),
+            ResourceType::ZGPConnectivity => self
+                .zgp_conns
+                .get(id)
+                .map(|d| Resource::ZGPConnectivity(d.as_ref().clone())),
+            ResourceType::ZigbeeConnectivity => self
+                .zigbee_conns
+                .get(id)
+                .map(|d| Resource::ZigbeeConnectivity(d.as_ref().clone())),
+            ResourceType::ZigbeeDeviceDiscovery => self
+                .zigbee_dds
+                .get(id)
+                .map(|d| Resource::ZigbeeDeviceDiscovery(d.as_ref().clone())),
+            ResourceType::Zone => self
+                .zones
+                .get(id)
+                .map(|d| Resource::Zone(d.as_ref().clone())),
+            ResourceType::Bridge => self
+                .data
+                .as_ref()
+                .filter(|d| &d.id == id)
+                .map(|d| Resource::Bridge(d.clone())),
+            ResourceType::AuthV1
+            | ResourceType::Geofence
+            | ResourceType::PublicImage
+            | ResourceType::Recipe
+            | ResourceType::Taurus7455
+            | ResourceType::ZigbeeBridgeConnectivity
+            | ResourceType::Unknown => None,
+        }
+    }
+
+    /// Whether the referenced resource is present, or [None] for resource
+    /// types which are never cached.
+    pub fn contains(&self, rid: &ResourceIdentifier) -> Option<bool> {
+        let id = &rid.rid;
+        match rid.rtype {
+            ResourceType::BehaviorInstance => Some(self.behavior_instances.contains_key(id)),
+            ResourceType::BehaviorScript => Some(self.behavior_scripts.contains_key(id)),
+            ResourceType::BridgeHome => Some(self.homes.contains_key(id)),
+            ResourceType::Button => Some(self.buttons.contains_key(id)),
+            ResourceType::CameraMotion => Some(self.motion_cameras.contains_key(id)),
+            ResourceType::Contact => Some(self.contacts.contains_key(id)),
+            ResourceType::Device => Some(self.devices.contains_key(id)),
+            ResourceType::DevicePower => Some(self.power.contains_key(id)),
+            ResourceType::DeviceSoftwareUpdate => Some(self.swu.contains_key(id)),
+            ResourceType::Entertainment => Some(self.entertainments.contains_key(id)),
+            ResourceType::EntertainmentConfiguration => {
+                Some(self.entertainment_configurations.contains_key(id))
+            }
+            ResourceType::GeofenceClient => Some(self.geofence_clients.contains_key(id)),
+            ResourceType::Geolocation => Some(self.geolocations.contains_key(id)),
+            ResourceType::Group => Some(self.groups.contains_key(id)),
+            ResourceType::GroupedLightLevel => Some(self.grouped_light_levels.contains_key(id)),
+            ResourceType::GroupedMotion => Some(self.grouped_motions.contains_key(id)),
+            ResourceType::HomeKit => Some(self.homekits.contains_key(id)),
+            ResourceType::Light => Some(self.lights.contains_key(id)),
+            ResourceType::LightLevel => Some(self.light_levels.contains_key(id)),
+            ResourceType::Matter => Some(self.matters.contains_key(id)),
+            ResourceType::MatterFabric => Some(self.matter_fabrics.contains_key(id)),
+            ResourceType::Motion => Some(self.motions.contains_key(id)),
+            ResourceType::RelativeRotary => Some(self.rotaries.contains_key(id)),
+            ResourceType::Room => Some(self.rooms.contains_key(id)),
+            ResourceType::Scene => Some(self.scenes.contains_key(id)),
+            ResourceType::SmartScene => Some(self.smart_scenes.contains_key(id)),
+            ResourceType::Tamper => Some(self.tampers.contains_key(id)),
+            ResourceType::Temperature => Some(self.temps.contains_key(id)),
+            ResourceType::ZGPConnectivity => Some(self.zgp_conns.contains_key(id)),
+            ResourceType::ZigbeeConnectivity => Some(self.zigbee_conns.contains_key(id)),
+            ResourceType::ZigbeeDeviceDiscovery => Some(self.zigbee_dds.contains_key(id)),
+            ResourceType::Zone => Some(self.zones.contains_key(id)),
+            ResourceType::Bridge
+            | ResourceType::AuthV1
+            | ResourceType::Geofence
+            | ResourceType::PublicImage
+            | ResourceType::Recipe
+            | ResourceType::Taurus7455
+            | ResourceType::ZigbeeBridgeConnectivity
+            | ResourceType::Unknown => None,
+        }
+    }
+}
+
+/// The resources which differ between two states of the cache, as returned by
+/// [Bridge::refresh_diff].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BridgeDiff {
+    /// Resources present now but not before.
+    pub added: Vec<ResourceIdentifier>,
+    /// Resources present before but not now.
+    pub removed: Vec<ResourceIdentifier>,
+    /// Resources present in both, with different data.
+    pub changed: Vec<ResourceIdentifier>,
+}
+
+impl BridgeDiff {
+    /// Whether nothing was added, removed, or changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    fn between(old: &BridgeCache, new: &BridgeCache) -> Self {
+        let mut diff = BridgeDiff::default();
+        diff.compare(
+            &old.behavior_instances,
+            &new.behavior_instances,
+            ResourceType::BehaviorInstance,
+        );
+        diff.compare(
+            &old.behavior_scripts,
+            &new.behavior_scripts,
+            ResourceType::BehaviorScript,
+        );
+        diff.compare(&old.homes, &new.homes, ResourceType::BridgeHome);
+        diff.compare(&old.buttons, &new.buttons, ResourceType::Button);
+        diff.compare(
+            &old.motion_cameras,
+            &new.motion_cameras,
+            ResourceType::CameraMotion,
+        );
+        diff.compare(&old.contacts, &new.contacts, ResourceType::Contact);
+        diff.compare(&old.devices, &new.devices, ResourceType::Device);
+        diff.compare(&old.power, &new.power, ResourceType::DevicePower);
+        diff.compare(&old.swu, &new.swu, ResourceType::DeviceSoftwareUpdate);
+        diff.compare(
+            &old.entertainments,
+            &new.entertainments,
+            ResourceType::Entertainment,
+        );
+        diff.compare(
+            &old.entertainment_configurations,
+            &new.entertainment_configurations,
+            ResourceType::EntertainmentConfiguration,
+        );
+        diff.compare(
+            &old.geofence_clients,
+            &new.geofence_clients,
+            ResourceType::GeofenceClient,
+        );
+        diff.compare(
+            &old.geolocations,
+            &new.geolocations,
+            ResourceType::Geolocation,
+        );
+        diff.compare(&old.groups, &new.groups, ResourceType::Group);
+        diff.compare(
+            &old.grouped_light_levels,
+            &new.grouped_light_levels,
+            ResourceType::GroupedLightLevel,
+        );
+        diff.compare(
+            &old.grouped_motions,
+            &new.grouped_motions,
+            ResourceType::GroupedMotion,
+        );
+        diff.compare(&old.homekits, &new.homekits, ResourceType::HomeKit);
+        diff.compare(&old.lights, &new.lights, ResourceType::Light);
+        diff.compare(
+            &old.light_levels,
+            &new.light_levels,
+            ResourceType::LightLevel,
+        );
+        diff.compare(&old.matters, &new.matters, ResourceType::Matter);
+        diff.compare(
+            &old.matter_fabrics,
+            &new.matter_fabrics,
+            ResourceType::MatterFabric,
+        );
+        diff.compare(&old.motions, &new.motions, ResourceType::Motion);
+        diff.compare(&old.rotaries, &new.rotaries, ResourceType::RelativeRotary);
+        diff.compare(&old.rooms, &new.rooms, ResourceType::Room);
+        diff.compare(&old.scenes, &new.scenes, ResourceType::Scene);
+        diff.compare(
+            &old.smart_scenes,
+            &new.smart_scenes,
+            ResourceType::SmartScene,
+        );
+        diff.compare(&old.tampers, &new.tampers, ResourceType::Tamper);
+        diff.compare(&old.temps, &new.temps, ResourceType::Temperature);
+        diff.compare(
+            &old.zgp_conns,
+            &new.zgp_conns,
+            ResourceType::ZGPConnectivity,
+        );
+        diff.compare(
+            &old.zigbee_conns,
+            &new.zigbee_conns,
+            ResourceType::ZigbeeConnectivity,
+        );
+        diff.compare(
+            &old.zigbee_dds,
+            &new.zigbee_dds,
+            ResourceType::ZigbeeDeviceDiscovery,
+        );
+        diff.compare(&old.zones, &new.zones, ResourceType::Zone);
+        diff
+    }
+
+    fn compare<T: PartialEq>(
+        &mut self,
+        old: &HashMap<String, Arc<T>>,
+        new: &HashMap<String, Arc<T>>,
+        rtype: ResourceType,
+    ) {
+        let rid = |id: &String| ResourceIdentifier::new(id, rtype.clone());
+        for (id, data) in new {
+            match old.get(id) {
+                None => self.added.push(rid(id)),
+                Some(prev) if prev != data => self.changed.push(rid(id)),
+                Some(_) => {}
+            }
+        }
+        self.removed
+            .extend(old.keys().filter(|id| !new.contains_key(*id)).map(rid));
+    }
+}
+
+/// A reference from one resource to another which is not present in the cache,
+/// as found by [Bridge::validate_references].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DanglingReference {
+    /// The resource holding the reference.
+    pub source: ResourceIdentifier,
+    /// The missing resource.
+    pub target: ResourceIdentifier,
+}
+
+async fn refresh_cache(
+    api: &BridgeClient,
+    cache: &Mutex<BridgeCache>,
+    in_flight: &tokio::sync::Mutex<Result<(), HueAPIError>>,
+) -> Result<(), HueAPIError> {
+    match in_flight.try_lock() {
+        Ok(mut outcome) => {
+            *outcome = match api.get_resources().await {
+                Ok(listing) => {
+                    apply_listing(&mut cache.lock().expect("lock cache"), listing);
+                    Ok(())
+                }
+                Err(e) => Err(e),
+            };
+            outcome.clone()
+        }
+        // Another fetch is in flight, so wait for it rather than duplicating it
+        Err(_) => in_flight.lock().await.clone(),
+    }
+}
+
+/// Updates `cache` to match a full fetch of the bridge's resources, dropping
+/// any the bridge no longer reports, and returns what changed.
+fn apply_listing(cache: &mut BridgeCache, listing: ResourceListing) -> BridgeDiff {
+    let mut fetched = BridgeCache::default();
+    insert_to_cache(&mut fetched, listing.resources);
+    fetched.set_unmodeled(listing.unmodeled);
+    fetched.deserialize_failures = cache.deserialize_failures;
+    fetched.last_active_scenes = std::mem::take(&mut cache.last_active_scenes);
+    for (id, scene) in &fetched.scenes {
+        if cache
+            .scenes
+            .get(id)
+            .is_some_and(|before| scene_activated(before, scene))
+        {
+            fetched
+                .last_active_scenes
+                .insert(scene.group.rid.clone(), id.clone());
+        }
+    }
+
+    let diff = BridgeDiff::between(cache, &fetched);
+    *cache = fetched;
+    diff
 }
 
-fn insert_to_cache(cache: &mut MutexGuard<'_, BridgeCache>, data: Vec<Resource>) {
+fn insert_to_cache(cache: &mut BridgeCache, data: Vec<Resource>) {
     for res in data {
         match res {
-            // Resource::AuthV1 => {}
             Resource::BehaviorScript(d) => {
-                cache.behavior_scripts.insert(d.id.clone(), d);
+                cache.behavior_scripts.insert(d.id.clone(), Arc::new(d));
             }
             Resource::BehaviorInstance(d) => {
-                cache.behavior_instances.insert(d.id.clone(), d);
+                cache.behavior_instances.insert(d.id.clone(), Arc::new(d));
             }
             Resource::Bridge(d) => {
                 cache.data = Some(d);
             }
             Resource::BridgeHome(d) => {
-                cache.homes.insert(d.id.clone(), d);
+                cache.homes.insert(d.id.clone(), Arc::new(d));
             }
             Resource::Button(d) => {
-                cache.buttons.insert(d.id.clone(), d);
+                cache.buttons.insert(d.id.clone(), Arc::new(d));
             }
             Resource::CameraMotion(d) => {
-                cache.motion_cameras.insert(d.id.clone(), d);
+                cache.motion_cameras.insert(d.id.clone(), Arc::new(d));
             }
             Resource::Contact(d) => {
-                cache.contacts.insert(d.id.clone(), d);
+                cache.contacts.insert(d.id.clone(), Arc::new(d));
             }
             Resource::Device(d) => {
-                cache.devices.insert(d.id.clone(), d);
+                cache.devices.insert(d.id.clone(), Arc::new(d));
             }
             Resource::DevicePower(d) => {
-                cache.power.insert(d.id.clone(), d);
+                cache.power.insert(d.id.clone(), Arc::new(d));
             }
             Resource::DeviceSoftwareUpdate(d) => {
-                cache.swu.insert(d.id.clone(), d);
+                cache.swu.insert(d.id.clone(), Arc::new(d));
             }
             Resource::EntertainmentConfiguration(d) => {
-                cache.entertainment_configurations.insert(d.id.clone(), d);
+                cache
+                    .entertainment_configurations
+                    .insert(d.id.clone(), Arc::new(d));
             }
             Resource::Entertainment(d) => {
-                cache.entertainments.insert(d.id.clone(), d);
+                cache.entertainments.insert(d.id.clone(), Arc::new(d));
             }
             Resource::GeofenceClient(d) => {
-                cache.geofence_clients.insert(d.id.clone(), d);
+                cache.geofence_clients.insert(d.id.clone(), Arc::new(d));
             }
             Resource::Geolocation(d) => {
-                cache.geolocations.insert(d.id.clone(), d);
+                cache.geolocations.insert(d.id.clone(), Arc::new(d));
             }
             Resource::Group(d) => {
-                cache.groups.insert(d.id.clone(), d);
+                cache.groups.insert(d.id.clone(), Arc::new(d));
+            }
+            Resource::GroupedLightLevel(d) => {
+                cache.grouped_light_levels.insert(d.id.clone(), Arc::new(d));
+            }
+            Resource::GroupedMotion(d) => {
+                cache.grouped_motions.insert(d.id.clone(), Arc::new(d));
             }
             Resource::HomeKit(d) => {
-                cache.homekits.insert(d.id.clone(), d);
+                cache.homekits.insert(d.id.clone(), Arc::new(d));
             }
             Resource::Light(d) => {
-                cache.lights.insert(d.id.clone(), d);
+                cache.lights.insert(d.id.clone(), Arc::new(d));
             }
             Resource::LightLevel(d) => {
-                cache.light_levels.insert(d.id.clone(), d);
+                cache.light_levels.insert(d.id.clone(), Arc::new(d));
             }
             Resource::Matter(d) => {
-                cache.matters.insert(d.id.clone(), d);
+                cache.matters.insert(d.id.clone(), Arc::new(d));
             }
             Resource::MatterFabric(d) => {
-                cache.matter_fabrics.insert(d.id.clone(), d);
+                cache.matter_fabrics.insert(d.id.clone(), Arc::new(d));
             }
             Resource::Motion(d) => {
-                cache.motions.insert(d.id.clone(), d);
+                cache.motions.insert(d.id.clone(), Arc::new(d));
             }
             Resource::Room(d) => {
-                cache.rooms.insert(d.id.clone(), d);
+                cache.rooms.insert(d.id.clone(), Arc::new(d));
             }
             Resource::RelativeRotary(d) => {
-                cache.rotaries.insert(d.id.clone(), d);
+                cache.rotaries.insert(d.id.clone(), Arc::new(d));
             }
             Resource::Scene(d) => {
-                cache.scenes.insert(d.id.clone(), d);
+                if cache
+                    .scenes
+                    .get(&d.id)
+                    .is_some_and(|b| scene_activated(b, &d))
+                {
+                    cache
+                        .last_active_scenes
+                        .insert(d.group.rid.clone(), d.id.clone());
+                }
+                cache.scenes.insert(d.id.clone(), Arc::new(d));
             }
             Resource::SmartScene(d) => {
-                cache.smart_scenes.insert(d.id.clone(), d);
+                cache.smart_scenes.insert(d.id.clone(), Arc::new(d));
             }
             Resource::Tamper(d) => {
-                cache.tampers.insert(d.id.clone(), d);
+                cache.tampers.insert(d.id.clone(), Arc::new(d));
             }
             Resource::Temperature(d) => {
-                cache.temps.insert(d.id.clone(), d);
+                cache.temps.insert(d.id.clone(), Arc::new(d));
             }
-            // Resource::ZigbeeBridgeConnectivity => {}
             Resource::ZigbeeConnectivity(d) => {
-                cache.zigbee_conns.insert(d.id.clone(), d);
+                cache.zigbee_conns.insert(d.id.clone(), Arc::new(d));
             }
             Resource::ZigbeeDeviceDiscovery(d) => {
-                cache.zigbee_dds.insert(d.id.clone(), d);
+                cache.zigbee_dds.insert(d.id.clone(), Arc::new(d));
             }
             Resource::ZGPConnectivity(d) => {
-                cache.zgp_conns.insert(d.id.clone(), d);
+                cache.zgp_conns.insert(d.id.clone(), Arc::new(d));
             }
             Resource::Zone(d) => {
-                cache.zones.insert(d.id.clone(), d);
+                cache.zones.insert(d.id.clone(), Arc::new(d));
+            }
+            Resource::AuthV1
+            | Resource::Geofence
+            | Resource::PublicImage
+            | Resource::Recipe
+            | Resource::Taurus7455
+            | Resource::ZigbeeBridgeConnectivity => {
+                log::warn!("NOT IMPLEMENTED: {:?}", &res);
             }
             Resource::Unknown => {
                 log::debug!("UNKNOWN RESOURCE: {:?}", &res);
             }
-            _ => {
-                log::warn!("NOT IMPLEMENTED: {:?}", &res);
-            }
         }
     }
 }
 
-fn delete_from_cache(cache: &mut MutexGuard<'_, BridgeCache>, data: &Vec<ResourceIdentifier>) {
-    let ids_by_type: HashMap<&ResourceType, HashSet<&String>> =
-        data.into_iter().fold(Default::default(), |mut acc, r| {
-            if !acc.contains_key(&r.rtype) {
-                acc.insert(&r.rtype, Default::default());
-            }
-            acc.get_mut(&r.rtype).unwrap().insert(&r.rid);
-            acc
-        });
-    for res in ids_by_type.keys() {
-        let ids = ids_by_type.get(res).unwrap();
-        match res {
-            ResourceType::AuthV1 => {
-                todo!()
-            }
-            ResourceType::BehaviorInstance => {
-                cache.behavior_instances.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::BehaviorScript => {
-                cache.behavior_scripts.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::Bridge => {
-                // Is it possible to delete the bridge device?
-                todo!()
-            }
-            ResourceType::BridgeHome => {
-                cache.homes.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::Button => {
-                cache.buttons.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::CameraMotion => {
-                cache.motion_cameras.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::Contact => {
-                cache.contacts.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::Device => {
-                cache.devices.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::DevicePower => {
-                cache.power.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::DeviceSoftwareUpdate => {
-                cache.swu.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::Entertainment => {
-                cache.entertainments.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::EntertainmentConfiguration => {
-                cache
-                    .entertainment_configurations
-                    .retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::Geofence => {
-                todo!()
-            }
-            ResourceType::GeofenceClient => {
-                cache.geofence_clients.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::Geolocation => {
-                cache.geolocations.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::Group => {
-                cache.groups.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::HomeKit => {
-                cache.homekits.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::Light => {
-                cache.lights.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::LightLevel => {
-                cache.light_levels.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::Matter => {
-                cache.matters.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::MatterFabric => {
-                cache.matter_fabrics.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::Motion => {
-                cache.motions.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::PublicImage => {
-                todo!()
-            }
-            ResourceType::Recipe => {
-                todo!()
-            }
-            ResourceType::RelativeRotary => {
-                cache.rotaries.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::Room => {
-                cache.rooms.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::Scene => {
-                cache.scenes.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::SmartScene => {
-                cache.smart_scenes.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::Tamper => {
-                cache.tampers.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::Taurus7455 => {
-                todo!()
-            }
-            ResourceType::Temperature => {
-                cache.temps.retain(|id, _| !ids.contains(&id));
-            }
-            ResourceType::ZGPConnectivity => {
-                cache.zgp_conns.retain(|id, _| !ids.contains(&id));
+/// Whether a scene was recalled between two states of its data. Scenes which
+/// stay active are not counted, so that unrelated updates do not reorder them.
+fn scene_activated(before: &SceneData, after: &SceneData) -> bool {
+    after.status.active != SceneStatus::Inactive
+        && (before.status.active == SceneStatus::Inactive
+            || before.status.last_recall != after.status.last_recall)
+}
+
+/// Takes the data out of `data`, cloning it if the cache is not its only
+/// holder. Stands in for `Arc::unwrap_or_clone`, which needs Rust 1.76.
+fn unwrap_or_clone<T: Clone>(data: Arc<T>) -> T {
+    Arc::try_unwrap(data).unwrap_or_else(|shared| (*shared).clone())
+}
+
+/// Removes the given resources from the cache, returning the data of those
+/// which were present. Resources which are never cached are ignored.
+fn delete_from_cache(cache: &mut BridgeCache, data: &[ResourceIdentifier]) -> Vec<Resource> {
+    data.iter()
+        .filter_map(|rid| match rid.rtype {
+            ResourceType::BehaviorInstance => cache
+                .behavior_instances
+                .remove(&rid.rid)
+                .map(|d| Resource::BehaviorInstance(unwrap_or_clone(d))),
+            ResourceType::BehaviorScript => cache
+                .behavior_scripts
+                .remove(&rid.rid)
+                .map(|d| Resource::BehaviorScript(unwrap_or_clone(d))),
+            ResourceType::BridgeHome => cache
+                .homes
+                .remove(&rid.rid)
+                .map(|d| Resource::BridgeHome(unwrap_or_clone(d))),
+            ResourceType::Button => cache
+                .buttons
+                .remove(&rid.rid)
+                .map(|d| Resource::Button(unwrap_or_clone(d))),
+            ResourceType::CameraMotion => cache
+                .motion_cameras
+                .remove(&rid.rid)
+                .map(|d| Resource::CameraMotion(unwrap_or_clone(d))),
+            ResourceType::Contact => cache
+                .contacts
+                .remove(&rid.rid)
+                .map(|d| Resource::Contact(unwrap_or_clone(d))),
+            ResourceType::Device => cache
+                .devices
+                .remove(&rid.rid)
+                .map(|d| Resource::Device(unwrap_or_clone(d))),
+            ResourceType::DevicePower => cache
+                .power
+                .remove(&rid.rid)
+                .map(|d| Resource::DevicePower(unwrap_or_clone(d))),
+            ResourceType::DeviceSoftwareUpdate => cache
+                .swu
+                .remove(&rid.rid)
+                .map(|d| Resource::DeviceSoftwareUpdate(unwrap_or_clone(d))),
+            ResourceType::Entertainment => cache
+                .entertainments
+                .remove(&rid.rid)
+                .map(|d| Resource::Entertainment(unwrap_or_clone(d))),
+            ResourceType::EntertainmentConfiguration => cache
+                .entertainment_configurations
+                .remove(&rid.rid)
+                .map(|d| Resource::EntertainmentConfiguration(unwrap_or_clone(d))),
+            ResourceType::GeofenceClient => cache
+                .geofence_clients
+                .remove(&rid.rid)
+                .map(|d| Resource::GeofenceClient(unwrap_or_clone(d))),
+            ResourceType::Geolocation => cache
+                .geolocations
+                .remove(&rid.rid)
+                .map(|d| Resource::Geolocation(unwrap_or_clone(d))),
+            ResourceType::Group => cache
+                .groups
+                .remove(&rid.rid)
+                .map(|d| Resource::Group(unwrap_or_clone(d))),
+            ResourceType::GroupedLightLevel => cache
+                .grouped_light_levels
+                .remove(&rid.rid)
+                .map(|d| Resource::GroupedLightLevel(unwrap_or_clone(d))),
+            ResourceType::GroupedMotion => cache
+                .grouped_motions
+                .remove(&rid.rid)
+                .map(|d| Resource::GroupedMotion(unwrap_or_clone(d))),
+            ResourceType::HomeKit => cache
+                .homekits
+                .remove(&rid.rid)
+                .map(|d| Resource::HomeKit(unwrap_or_clone(d))),
+            ResourceType::Light => cache
+                .lights
+                .remove(&rid.rid)
+                .map(|d| Resource::Light(unwrap_or_clone(d))),
+            ResourceType::LightLevel => cache
+                .light_levels
+                .remove(&rid.rid)
+                .map(|d| Resource::LightLevel(unwrap_or_clone(d))),
+            ResourceType::Matter => cache
+                .matters
+                .remove(&rid.rid)
+                .map(|d| Resource::Matter(unwrap_or_clone(d))),
+            ResourceType::MatterFabric => cache
+                .matter_fabrics
+                .remove(&rid.rid)
+                .map(|d| Resource::MatterFabric(unwrap_or_clone(d))),
+            ResourceType::Motion => cache
+                .motions
+                .remove(&rid.rid)
+                .map(|d| Resource::Motion(unwrap_or_clone(d))),
+            ResourceType::RelativeRotary => cache
+                .rotaries
+                .remove(&rid.rid)
+                .map(|d| Resource::RelativeRotary(unwrap_or_clone(d))),
+            ResourceType::Room => cache
+                .rooms
+                .remove(&rid.rid)
+                .map(|d| Resource::Room(unwrap_or_clone(d))),
+            ResourceType::Scene => cache
+                .scenes
+                .remove(&rid.rid)
+                .map(|d| Resource::Scene(unwrap_or_clone(d))),
+            ResourceType::SmartScene => cache
+                .smart_scenes
+                .remove(&rid.rid)
+                .map(|d| Resource::SmartScene(unwrap_or_clone(d))),
+            ResourceType::Tamper => cache
+                .tampers
+                .remove(&rid.rid)
+                .map(|d| Resource::Tamper(unwrap_or_clone(d))),
+            ResourceType::Temperature => cache
+                .temps
+                .remove(&rid.rid)
+                .map(|d| Resource::Temperature(unwrap_or_clone(d))),
+            ResourceType::ZGPConnectivity => cache
+                .zgp_conns
+                .remove(&rid.rid)
+                .map(|d| Resource::ZGPConnectivity(unwrap_or_clone(d))),
+            ResourceType::ZigbeeConnectivity => cache
+                .zigbee_conns
+                .remove(&rid.rid)
+                .map(|d| Resource::ZigbeeConnectivity(unwrap_or_clone(d))),
+            ResourceType::ZigbeeDeviceDiscovery => cache
+                .zigbee_dds
+                .remove(&rid.rid)
+                .map(|d| Resource::ZigbeeDeviceDiscovery(unwrap_or_clone(d))),
+            ResourceType::Zone => cache
+                .zones
+                .remove(&rid.rid)
+                .map(|d| Resource::Zone(unwrap_or_clone(d))),
+            // Is it possible to delete the bridge device?
+            ResourceType::Bridge => None,
+            ResourceType::AuthV1
+            | ResourceType::Geofence
+            | ResourceType::PublicImage
+            | ResourceType::Recipe
+            | ResourceType::Taurus7455
+            | ResourceType::ZigbeeBridgeConnectivity
+            | ResourceType::Unknown => {
+                log::debug!("ignoring delete of uncached {:?} {}", rid.rtype, rid.rid);
+                None
             }
-            ResourceType::ZigbeeBridgeConnectivity => {
-                todo!()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{api::MockTransport, service::fixtures};
+    use reqwest::Method;
+    use serde_json::json;
+
+    fn home() -> Vec<serde_json::Value> {
+        vec![
+            fixtures::bridge("b1"),
+            fixtures::device("d1", &[fixtures::rid("light", "l1")]),
+            fixtures::light("l1", "d1"),
+            fixtures::room("r1", &[fixtures::rid("device", "d1")], "g1"),
+            fixtures::grouped_light("g1", fixtures::rid("room", "r1"), true),
+        ]
+    }
+
+    /// A resource of type `rtype` owned by a device, with the fields in
+    /// `extra`.
+    fn owned(rtype: &str, id: &str, extra: serde_json::Value) -> serde_json::Value {
+        let mut res = json!({ "type": rtype, "id": id, "owner": fixtures::rid("device", "d1") });
+        res.as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        res
+    }
+
+    /// One resource of every type the cache stores.
+    fn one_of_each() -> Vec<serde_json::Value> {
+        let zigbee = json!({ "status": "connected", "mac_address": "00:17:88:01:0b:aa:bb:cc" });
+        vec![
+            json!({
+                "type": "behavior_instance", "id": "bi1", "script_id": "bs1", "enabled": true,
+                "configuration": {}, "dependees": [], "status": "running", "metadata": {},
+            }),
+            json!({
+                "type": "behavior_script", "id": "bs1", "description": "Wake up",
+                "configuration_schema": {}, "trigger_schema": {}, "state_schema": {},
+                "version": "0.0.1", "metadata": { "category": "automation" },
+            }),
+            fixtures::bridge("b1"),
+            json!({ "type": "bridge_home", "id": "h1", "children": [], "services": [] }),
+            owned(
+                "button",
+                "bt1",
+                json!({
+                    "metadata": { "control_id": 1 },
+                    "button": { "event_values": ["short_release"] },
+                }),
+            ),
+            owned(
+                "camera_motion",
+                "cm1",
+                json!({ "enabled": true, "motion": { "motion_valid": true } }),
+            ),
+            owned("contact", "c1", json!({ "enabled": true })),
+            fixtures::device("d1", &[]),
+            owned("device_power", "dp1", json!({ "power_state": {} })),
+            owned(
+                "device_software_update",
+                "swu1",
+                json!({ "state": "no_update", "problems": [] }),
+            ),
+            owned(
+                "entertainment",
+                "e1",
+                json!({ "renderer": true, "proxy": false, "equalizer": false }),
+            ),
+            json!({
+                "type": "entertainment_configuration", "id": "ec1", "metadata": { "name": "TV" },
+                "configuration_type": "screen", "status": "inactive",
+                "stream_proxy": { "mode": "auto", "node": fixtures::rid("entertainment", "e1") },
+                "channels": [], "locations": { "service_locations": [] },
+            }),
+            json!({ "type": "geofence_client", "id": "gc1", "name": "Phone" }),
+            json!({ "type": "geolocation", "id": "geo1", "is_configured": false }),
+            fixtures::grouped_light("g1", fixtures::rid("room", "r1"), true),
+            owned(
+                "grouped_light_level",
+                "gll1",
+                json!({ "enabled": true, "light": {} }),
+            ),
+            owned(
+                "grouped_motion",
+                "gm1",
+                json!({ "enabled": true, "motion": {} }),
+            ),
+            json!({ "type": "homekit", "id": "hk1", "status": "unpaired" }),
+            fixtures::light("l1", "d1"),
+            owned(
+                "light_level",
+                "ll1",
+                json!({
+                    "enabled": true, "light": { "light_level": 0, "light_level_valid": true },
+                }),
+            ),
+            json!({ "type": "matter", "id": "m1", "max_fabrics": 16, "has_qr_code": true }),
+            json!({
+                "type": "matter_fabric", "id": "mf1", "status": "paired",
+                "creation_time": "2024-01-01T00:00:00Z",
+            }),
+            owned(
+                "motion",
+                "mo1",
+                json!({ "enabled": true, "motion": { "motion_valid": true } }),
+            ),
+            owned("relative_rotary", "rr1", json!({ "relative_rotary": {} })),
+            fixtures::room("r1", &[fixtures::rid("device", "d1")], "g1"),
+            fixtures::scene("s1", fixtures::rid("room", "r1"), &["l1"], false),
+            json!({
+                "type": "smart_scene", "id": "ss1", "metadata": { "name": "Natural light" },
+                "group": fixtures::rid("room", "r1"), "week_timeslots": [],
+                "transition_duration": 60000, "state": "inactive",
+            }),
+            owned("tamper", "t1", json!({ "tamper_reports": [] })),
+            owned(
+                "temperature",
+                "te1",
+                json!({
+                    "enabled": true, "temperature": { "temperature": 20.5, "temperature_valid": true },
+                }),
+            ),
+            owned(
+                "zgp_connectivity",
+                "zgp1",
+                json!({ "status": "connected", "source_id": "1234" }),
+            ),
+            owned("zigbee_connectivity", "zc1", zigbee),
+            owned(
+                "zigbee_device_discovery",
+                "zdd1",
+                json!({ "status": "ready" }),
+            ),
+            fixtures::zone("z1", &[fixtures::rid("light", "l1")], "g2"),
+        ]
+    }
+
+    #[test]
+    fn insert_routes_each_type_to_its_map() {
+        let resources: Vec<Resource> = one_of_each()
+            .into_iter()
+            .map(|v| serde_json::from_value(v.clone()).unwrap_or_else(|e| panic!("{e}: {v}")))
+            .collect();
+        let mut cache = BridgeCache::default();
+        insert_to_cache(&mut cache, resources);
+
+        let counts = [
+            ("behavior_instances", cache.behavior_instances.len()),
+            ("behavior_scripts", cache.behavior_scripts.len()),
+            ("homes", cache.homes.len()),
+            ("buttons", cache.buttons.len()),
+            ("motion_cameras", cache.motion_cameras.len()),
+            ("contacts", cache.contacts.len()),
+            ("devices", cache.devices.len()),
+            ("power", cache.power.len()),
+            ("swu", cache.swu.len()),
+            ("entertainments", cache.entertainments.len()),
+            (
+                "entertainment_configurations",
+                cache.entertainment_configurations.len(),
+            ),
+            ("geofence_clients", cache.geofence_clients.len()),
+            ("geolocations", cache.geolocations.len()),
+            ("groups", cache.groups.len()),
+            ("grouped_light_levels", cache.grouped_light_levels.len()),
+            ("grouped_motions", cache.grouped_motions.len()),
+            ("homekits", cache.homekits.len()),
+            ("lights", cache.lights.len()),
+            ("light_levels", cache.light_levels.len()),
+            ("matters", cache.matters.len()),
+            ("matter_fabrics", cache.matter_fabrics.len()),
+            ("motions", cache.motions.len()),
+            ("rotaries", cache.rotaries.len()),
+            ("rooms", cache.rooms.len()),
+            ("scenes", cache.scenes.len()),
+            ("smart_scenes", cache.smart_scenes.len()),
+            ("tampers", cache.tampers.len()),
+            ("temps", cache.temps.len()),
+            ("zgp_conns", cache.zgp_conns.len()),
+            ("zigbee_conns", cache.zigbee_conns.len()),
+            ("zigbee_dds", cache.zigbee_dds.len()),
+            ("zones", cache.zones.len()),
+        ];
+        for (map, count) in counts {
+            assert_eq!(count, 1, "{map}");
+        }
+        assert!(cache.data.is_some());
+        assert!(cache.motion_cameras.contains_key("cm1"));
+        assert!(cache.motions.contains_key("mo1"));
+        assert!(cache.unmodeled.is_empty());
+    }
+
+    /// Resource types which are never cached.
+    const UNCACHED: [&str; 8] = [
+        "auth_v1",
+        "bridge",
+        "geofence",
+        "public_image",
+        "recipe",
+        "taurus_7455",
+        "zigbee_bridge_connectivity",
+        "frobnicator",
+    ];
+
+    #[tokio::test]
+    async fn behavior_counters_read_their_own_maps() {
+        let mut resources = one_of_each();
+        for id in ["bs2", "bs3"] {
+            let mut script = resources[1].clone();
+            script["id"] = json!(id);
+            resources.push(script);
+        }
+        let (_, bridge) = fixtures::refreshed_bridge(resources).await;
+
+        assert_eq!(bridge.n_behavior_scripts(), 3);
+        assert_eq!(bridge.n_behavior_instances(), 1);
+        #[allow(deprecated)]
+        let scrips = bridge.n_behavior_scrips();
+        assert_eq!(scrips, 3);
+    }
+
+    #[tokio::test]
+    async fn resource_resolves_owners_and_groups() {
+        let mut resources = home();
+        resources.push(fixtures::scene(
+            "s1",
+            fixtures::rid("room", "r1"),
+            &["l1"],
+            false,
+        ));
+        let (_, bridge) = fixtures::refreshed_bridge(resources).await;
+
+        let owner = bridge.light("l1").unwrap().data().owner.clone();
+        assert!(matches!(
+            bridge.resource(&owner),
+            Some(Resource::Device(device)) if device.id == "d1"
+        ));
+
+        let group = bridge.scene("s1").unwrap().group();
+        assert!(matches!(
+            bridge.resource(&group),
+            Some(Resource::Room(room)) if room.id == "r1"
+        ));
+
+        let missing = ResourceIdentifier {
+            rid: "d2".into(),
+            rtype: ResourceType::Device,
+        };
+        assert!(bridge.resource(&missing).is_none());
+    }
+
+    #[tokio::test]
+    async fn delete_ignores_uncached_types() {
+        let (_, bridge) = fixtures::refreshed_bridge(home()).await;
+        let rids: Vec<ResourceIdentifier> = UNCACHED
+            .iter()
+            .map(|rtype| serde_json::from_value(fixtures::rid(rtype, "x1")).unwrap())
+            .collect();
+
+        let removed = delete_from_cache(&mut bridge.cache(), &rids);
+
+        assert!(removed.is_empty());
+        assert!(bridge.data().is_some());
+        assert_eq!(bridge.n_lights(), 1);
+    }
+
+    #[tokio::test]
+    async fn refresh_fills_cache_through_transport() {
+        let (mock, bridge) = fixtures::refreshed_bridge(home()).await;
+
+        assert_eq!(bridge.data().map(|d| d.id), Some("b1".to_owned()));
+        assert_eq!(bridge.n_lights(), 1);
+        assert!(bridge.light("l1").is_some_and(|l| l.is_on()));
+        assert!(bridge.room("r1").is_some());
+        assert!(bridge.group("g1").is_some());
+
+        let requests = mock.requests_to(Method::GET, "/resource");
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].app_key, "test-app-key");
+    }
+
+    #[tokio::test]
+    async fn refresh_skips_unknown_and_malformed_resources() {
+        let mut broken = fixtures::light("l4", "d1");
+        broken["on"] = json!("yes");
+        let (_, bridge) = fixtures::refreshed_bridge(vec![
+            fixtures::light("l1", "d1"),
+            json!({ "type": "frobnicator", "id": "f1", "owner": fixtures::rid("frob", "x") }),
+            fixtures::light("l2", "d1"),
+            broken,
+            fixtures::ct_light("l3", "d1"),
+        ])
+        .await;
+
+        assert_eq!(bridge.n_lights(), 3);
+        assert!(["l1", "l2", "l3"]
+            .iter()
+            .all(|id| bridge.light(*id).is_some()));
+        let unmodeled = bridge.diagnostics().unmodeled_resources;
+        assert_eq!(unmodeled["frobnicator"], 1);
+        assert_eq!(unmodeled["light"], 1);
+    }
+
+    #[test]
+    fn unknown_resource_types_deserialize() {
+        let rid: ResourceIdentifier =
+            serde_json::from_value(fixtures::rid("frobnicator", "f1")).unwrap();
+        assert_eq!(rid.rtype, ResourceType::Unknown);
+    }
+
+    #[tokio::test]
+    async fn refresh_surfaces_bridge_errors() {
+        let mock = MockTransport::new();
+        mock.respond(
+            Method::GET,
+            "/resource",
+            403,
+            json!({ "errors": [{ "description": "unauthorized user" }], "data": [] }).to_string(),
+        );
+        let bridge = mock.bridge();
+
+        assert!(matches!(
+            bridge.refresh().await,
+            Err(HueAPIError::HueBridgeError { status: 403, .. })
+        ));
+        assert_eq!(bridge.n_lights(), 0);
+    }
+
+    /// Sorted, so diffs can be compared regardless of cache iteration order.
+    fn sorted(mut rids: Vec<ResourceIdentifier>) -> Vec<String> {
+        rids.sort_by(|a, b| a.rid.cmp(&b.rid));
+        rids.into_iter().map(|r| r.rid).collect()
+    }
+
+    #[tokio::test]
+    async fn refresh_diff_compares_snapshots() {
+        let mut before = home();
+        before.push(fixtures::scene(
+            "s1",
+            fixtures::rid("room", "r1"),
+            &["l1"],
+            false,
+        ));
+        let (mock, bridge) = fixtures::refreshed_bridge(before).await;
+
+        let mut after = home();
+        after[2]["dimming"]["brightness"] = json!(20.0);
+        after.push(fixtures::light("l2", "d1"));
+        mock.respond_data(Method::GET, "/resource", json!(after));
+
+        let diff = bridge.refresh_diff().await.unwrap();
+
+        assert_eq!(sorted(diff.added), ["l2"]);
+        assert_eq!(sorted(diff.removed), ["s1"]);
+        assert_eq!(sorted(diff.changed), ["l1"]);
+        assert!(bridge.scene("s1").is_none());
+        assert_eq!(bridge.light("l1").unwrap().brightness(), 20.0);
+    }
+
+    #[tokio::test]
+    async fn refresh_diff_of_an_unchanged_bridge_is_empty() {
+        let (_, bridge) = fixtures::refreshed_bridge(one_of_each()).await;
+
+        let diff = bridge.refresh_diff().await.unwrap();
+
+        assert!(diff.is_empty(), "{diff:?}");
+    }
+
+    #[tokio::test]
+    async fn refresh_drops_resources_the_bridge_no_longer_reports() {
+        let mut before = home();
+        before.push(fixtures::light("l2", "d1"));
+        let (mock, bridge) = fixtures::refreshed_bridge(before).await;
+        assert_eq!(bridge.n_lights(), 2);
+
+        mock.respond_data(Method::GET, "/resource", json!(home()));
+        bridge.refresh().await.unwrap();
+
+        assert_eq!(bridge.n_lights(), 1);
+        assert!(bridge.light("l2").is_none());
+    }
+
+    #[tokio::test]
+    async fn exported_cache_round_trips() {
+        let mut resources = one_of_each();
+        resources.push(fixtures::scene(
+            "s2",
+            fixtures::rid("room", "r1"),
+            &["l1"],
+            true,
+        ));
+        let (_, bridge) = fixtures::refreshed_bridge(resources).await;
+        let json = bridge.export_cache().unwrap();
+
+        let imported = MockTransport::new().bridge();
+        imported.import_cache(&json).unwrap();
+        let built = BridgeBuilder::new()
+            .transport(MockTransport::new())
+            .with_cache(&json)
+            .unwrap()
+            .build();
+
+        let original = bridge.cache.lock().unwrap();
+        for restored in [&imported, &built] {
+            let restored = restored.cache.lock().unwrap();
+            let diff = BridgeDiff::between(&original, &restored);
+            assert!(diff.is_empty(), "{diff:?}");
+            assert_eq!(restored.data, original.data);
+            assert_eq!(restored.last_active_scenes, original.last_active_scenes);
+        }
+        assert_eq!(built.n_lights(), 1);
+        assert!(built.scene("s2").is_some());
+    }
+
+    #[test]
+    fn malformed_cache_is_rejected() {
+        let bridge = MockTransport::new().bridge();
+        assert!(bridge.import_cache("{\"lights\": 3}").is_err());
+        assert!(BridgeBuilder::new().with_cache("not json").is_err());
+    }
+
+    #[tokio::test]
+    async fn diagnostics_count_unmodeled_resources_per_fetch() {
+        let mut resources = home();
+        resources.push(json!({ "type": "frobnicator", "id": "f1" }));
+        resources.push(json!({ "type": "frobnicator", "id": "f2" }));
+        resources.push(json!({ "type": "auth_v1", "id": "a1" }));
+        let (mock, bridge) = fixtures::refreshed_bridge(resources).await;
+        bridge.refresh_diff().await.unwrap();
+
+        let unmodeled = bridge.diagnostics().unmodeled_resources;
+        assert_eq!(unmodeled.len(), 2);
+        assert_eq!(unmodeled["frobnicator"], 2);
+        assert_eq!(unmodeled["auth_v1"], 1);
+        assert_eq!(bridge.n_lights(), 1);
+
+        let mut resources = home();
+        resources.push(json!({ "type": "frobnicator", "id": "f1" }));
+        mock.respond_data(Method::GET, "/resource", json!(resources));
+        bridge.refresh().await.unwrap();
+
+        let unmodeled = bridge.diagnostics().unmodeled_resources;
+        assert_eq!(unmodeled.len(), 1);
+        assert_eq!(unmodeled["frobnicator"], 1);
+    }
+
+    fn scenes(active: &[&str]) -> Vec<serde_json::Value> {
+        let mut resources = home();
+        for id in ["s1", "s2"] {
+            let room = fixtures::rid("room", "r1");
+            resources.push(fixtures::scene(id, room, &["l1"], active.contains(&id)));
+        }
+        resources
+    }
+
+    #[tokio::test]
+    async fn last_active_scene_follows_status_changes() {
+        let (mock, bridge) = fixtures::refreshed_bridge(scenes(&[])).await;
+        let room = ResourceIdentifier::new("r1", ResourceType::Room);
+        assert!(bridge.last_active_scene_for(&room).is_none());
+
+        mock.respond_data(Method::GET, "/resource", json!(scenes(&["s1"])));
+        bridge.refresh().await.unwrap();
+        assert_eq!(bridge.last_active_scene_for(&room).unwrap().id(), "s1");
+
+        mock.respond_data(Method::GET, "/resource", json!(scenes(&["s1", "s2"])));
+        for _ in 0..5 {
+            bridge.refresh().await.unwrap();
+            assert_eq!(bridge.last_active_scene_for(&room).unwrap().id(), "s2");
+        }
+    }
+
+    #[tokio::test]
+    async fn last_active_scene_falls_back_to_last_recall() {
+        let mut resources = scenes(&["s1", "s2"]);
+        resources[5]["status"]["last_recall"] = json!("2024-03-01T18:00:00.000Z");
+        resources[6]["status"]["last_recall"] = json!("2024-03-02T07:30:00.000Z");
+        let (_, bridge) = fixtures::refreshed_bridge(resources).await;
+
+        let room = ResourceIdentifier::new("r1", ResourceType::Room);
+        assert_eq!(bridge.last_active_scene_for(&room).unwrap().id(), "s2");
+    }
+
+    #[tokio::test]
+    async fn create_zone_posts_builder_and_caches_result() {
+        let (mock, bridge) = fixtures::refreshed_bridge(home()).await;
+        mock.respond_data(
+            Method::POST,
+            "/resource/zone",
+            fixtures::written("zone", "z1"),
+        );
+        mock.respond_data(
+            Method::GET,
+            "/resource/zone/z1",
+            json!([fixtures::zone("z1", &[fixtures::rid("light", "l1")], "g2")]),
+        );
+
+        let zone = bridge
+            .create_zone(
+                ZoneBuilder::new("Upstairs", ZoneArchetype::Bedroom)
+                    .children(vec![ResourceIdentifier::new("l1", ResourceType::Light)]),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(zone.id(), "z1");
+        assert!(bridge.zone("z1").is_some());
+        let posted = mock.requests_to(Method::POST, "/resource/zone");
+        let body = posted[0].body.as_ref().unwrap();
+        assert_eq!(body["metadata"]["name"], "Upstairs");
+        assert_eq!(body["children"][0]["rid"], "l1");
+    }
+
+    #[tokio::test]
+    async fn delete_returns_bridge_rids_and_removed_data() {
+        let mut resources = home();
+        resources.push(fixtures::scene(
+            "s1",
+            fixtures::rid("room", "r1"),
+            &["l1"],
+            false,
+        ));
+        let (mock, bridge) = fixtures::refreshed_bridge(resources).await;
+        mock.respond_data(
+            Method::DELETE,
+            "/resource/scene/s1",
+            fixtures::written("scene", "s1"),
+        );
+
+        let outcome = bridge.delete_scene("s1").await.unwrap();
+
+        assert_eq!(
+            outcome.rids,
+            vec![ResourceIdentifier::new("s1", ResourceType::Scene)]
+        );
+        assert!(matches!(&outcome.removed[..], [Resource::Scene(d)] if d.id == "s1"));
+        assert!(bridge.scene("s1").is_none());
+    }
+
+    #[tokio::test]
+    async fn delete_of_uncached_resource_returns_rids() {
+        let (mock, bridge) = fixtures::refreshed_bridge(home()).await;
+        mock.respond_data(
+            Method::DELETE,
+            "/resource/scene/s9",
+            fixtures::written("scene", "s9"),
+        );
+
+        let outcome = bridge.delete_scene("s9").await.unwrap();
+
+        assert_eq!(outcome.rids.len(), 1);
+        assert!(outcome.removed.is_empty());
+        assert_eq!(bridge.n_lights(), 1);
+    }
+
+    /// A bridge with the lights `l1` to `l<n>`, each answering writes.
+    async fn many_lights(n: usize) -> (MockTransport, Bridge) {
+        let ids = (1..=n).map(|i| format!("l{i}")).collect::<Vec<_>>();
+        let mut resources = vec![fixtures::device(
+            "d1",
+            &ids.iter()
+                .map(|id| fixtures::rid("light", id))
+                .collect::<Vec<_>>(),
+        )];
+        resources.extend(ids.iter().map(|id| fixtures::light(id, "d1")));
+        let (mock, bridge) = fixtures::refreshed_bridge(resources).await;
+        for id in &ids {
+            let path = format!("/resource/light/{id}");
+            mock.respond_data(Method::PUT, &path, fixtures::written("light", id));
+        }
+        (mock, bridge)
+    }
+
+    fn batch_of(n: usize) -> Vec<(ResourceIdentifier, Vec<LightCommand>)> {
+        (1..=n)
+            .map(|i| {
+                let rid = ResourceIdentifier {
+                    rid: format!("l{i}"),
+                    rtype: ResourceType::Light,
+                };
+                (rid, vec![LightCommand::On(false)])
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn send_batch_limits_requests_in_flight() {
+        let (mock, bridge) = many_lights(6).await;
+        mock.set_delay(Duration::from_millis(20));
+
+        let results = bridge.send_batch(&batch_of(6), 2).await;
+
+        assert_eq!(results.len(), 6);
+        assert!(results.iter().all(|res| res.is_ok()));
+        assert_eq!(mock.requests_to(Method::PUT, "/resource/light/l6").len(), 1);
+        assert_eq!(mock.peak_in_flight(), 2);
+    }
+
+    #[tokio::test]
+    async fn send_batch_reports_failures_in_order() {
+        let (mock, bridge) = many_lights(3).await;
+        // Nothing answers writes to l4, so the mock rejects them
+        let mut batch = batch_of(4);
+        batch.swap(1, 3);
+        batch.push((
+            ResourceIdentifier {
+                rid: "r1".into(),
+                rtype: ResourceType::Room,
+            },
+            vec![LightCommand::On(true)],
+        ));
+
+        let results = bridge.send_batch(&batch, 0).await;
+
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(HueAPIError::HueBridgeError { status: 404, .. })
+        ));
+        assert!(results[2].is_ok());
+        assert!(results[3].is_ok());
+        assert!(matches!(results[4], Err(HueAPIError::InvalidInput(_))));
+        assert_eq!(mock.peak_in_flight(), 1);
+    }
+
+    #[tokio::test]
+    async fn set_powerup_all_reports_each_light() {
+        let mut resources = vec![fixtures::device("d1", &[])];
+        for id in ["l1", "l2", "l3"] {
+            let mut light = fixtures::light(id, "d1");
+            light["powerup"] = json!({
+                "preset": "last_on_state",
+                "configured": true,
+                "on": { "mode": "previous" },
+            });
+            resources.push(light);
+        }
+        resources.push(fixtures::light("l4", "d1"));
+        let (mock, bridge) = fixtures::refreshed_bridge(resources).await;
+        // Nothing answers writes to l2, so the mock rejects them
+        for id in ["l1", "l3"] {
+            let path = format!("/resource/light/{id}");
+            mock.respond_data(Method::PUT, &path, fixtures::written("light", id));
+        }
+        mock.set_delay(Duration::from_millis(10));
+
+        let mut results = bridge.set_powerup_all(PowerupPresetType::Safety, 2).await;
+        results.sort_by(|a, b| a.0.rid.cmp(&b.0.rid));
+
+        let ids: Vec<_> = results.iter().map(|(rid, _)| rid.rid.as_str()).collect();
+        assert_eq!(ids, ["l1", "l2", "l3"]);
+        assert!(results[0].1.is_ok());
+        assert!(matches!(
+            results[1].1,
+            Err(HueAPIError::HueBridgeError { status: 404, .. })
+        ));
+        assert!(results[2].1.is_ok());
+        assert_eq!(mock.peak_in_flight(), 2);
+        let sent = mock.requests_to(Method::PUT, "/resource/light/l1");
+        assert_eq!(
+            sent[0].body,
+            Some(json!({ "powerup": { "preset": "safety" } }))
+        );
+    }
+
+    /// Compares reading 60 lights through [Bridge::lights],
+    /// [Bridge::snapshot] and [Bridge::for_each_light]. Run with
+    /// `cargo test --release -- --ignored --nocapture read_sixty_lights`.
+    #[tokio::test]
+    #[ignore = "timing only"]
+    async fn read_sixty_lights() {
+        const ROUNDS: u32 = 100_000;
+        let (_mock, bridge) = many_lights(60).await;
+
+        let time = |label: &str, mut read: Box<dyn FnMut() -> usize + '_>| {
+            let start = std::time::Instant::now();
+            let mut seen = 0;
+            for _ in 0..ROUNDS {
+                seen += std::hint::black_box(read());
             }
-            ResourceType::ZigbeeConnectivity => {
-                cache.zigbee_conns.retain(|id, _| !ids.contains(&id));
+            assert_eq!(seen, 60 * ROUNDS as usize);
+            println!("{label}: {:?} per call", start.elapsed() / ROUNDS);
+        };
+
+        time("lights", Box::new(|| bridge.lights().len()));
+        time("snapshot", Box::new(|| bridge.snapshot().lights().len()));
+        time(
+            "snapshot + iterate",
+            Box::new(|| {
+                bridge
+                    .snapshot()
+                    .lights()
+                    .values()
+                    .filter(|l| !l.id.is_empty())
+                    .count()
+            }),
+        );
+        time(
+            "for_each_light",
+            Box::new(|| {
+                let mut n = 0;
+                bridge.for_each_light(|_| n += 1);
+                n
+            }),
+        );
+    }
+
+    #[tokio::test]
+    async fn builder_skips_verification_by_default() {
+        let mock = MockTransport::new();
+        mock.respond_data(Method::GET, "/resource", json!([]));
+        let bridge = BridgeBuilder::new()
+            .addr([192, 168, 1, 2])
+            .transport(mock.clone())
+            .build();
+
+        bridge.refresh().await.unwrap();
+
+        assert!(bridge.api.accepts_invalid_certs());
+        assert_eq!(mock.requests()[0].url.host_str(), Some("192.168.1.2"));
+    }
+
+    #[tokio::test]
+    async fn builder_verifies_against_the_bridge_id() {
+        let mock = MockTransport::new();
+        mock.respond_data(Method::GET, "/resource", json!([]));
+        let certificate = Certificate::from_pem(fixtures::BRIDGE_CERTIFICATE.as_bytes()).unwrap();
+        let bridge = BridgeBuilder::new()
+            .addr([192, 168, 1, 2])
+            .root_certificate(certificate)
+            .verify_bridge_id("001788FFFE6A1B2C")
+            .transport(mock.clone())
+            .build();
+
+        bridge.refresh().await.unwrap();
+
+        assert!(!bridge.api.accepts_invalid_certs());
+        assert_eq!(
+            mock.requests()[0].url.as_str(),
+            "https://001788fffe6a1b2c/clip/v2/resource"
+        );
+    }
+
+    #[test]
+    fn builder_can_opt_out_of_verification() {
+        let bridge = BridgeBuilder::new()
+            .verify_bridge_id("001788fffe6a1b2c")
+            .accept_invalid_certs(true)
+            .build();
+
+        assert!(bridge.api.accepts_invalid_certs());
+    }
+
+    #[cfg(feature = "sse")]
+    mod events {
+        use super::*;
+        use crate::event::HueEvent;
+
+        fn event(etype: &str, data: serde_json::Value) -> HueEvent {
+            serde_json::from_value(json!({
+                "id": "e1",
+                "creationtime": "2024-01-01T00:00:00Z",
+                "type": etype,
+                "data": [data],
+            }))
+            .unwrap()
+        }
+
+        #[tokio::test]
+        async fn malformed_added_behavior_instance_is_skipped() {
+            let (_, bridge) = fixtures::refreshed_bridge(home()).await;
+
+            let changes = upsert_to_cache(
+                &mut bridge.cache(),
+                vec![event(
+                    "add",
+                    json!({ "type": "behavior_instance", "id": "bi1", "enabled": "yes" }),
+                )],
+            );
+
+            assert!(changes.is_empty());
+            assert_eq!(bridge.n_behavior_instances(), 0);
+        }
+
+        #[tokio::test]
+        async fn malformed_added_grouped_sensors_are_skipped() {
+            let (_, bridge) = fixtures::refreshed_bridge(home()).await;
+            let owner = fixtures::rid("room", "r1");
+
+            let changes = upsert_to_cache(
+                &mut bridge.cache(),
+                vec![
+                    event(
+                        "add",
+                        json!({ "type": "grouped_motion", "id": "gm1", "owner": owner }),
+                    ),
+                    event(
+                        "add",
+                        json!({
+                            "type": "grouped_light_level", "id": "gll1", "owner": owner,
+                            "enabled": true, "light": { "light_level_report": "bright" },
+                        }),
+                    ),
+                ],
+            );
+
+            assert!(changes.is_empty());
+            assert_eq!(bridge.n_grouped_motions(), 0);
+            assert!(bridge.grouped_light_levels().is_empty());
+        }
+
+        #[tokio::test]
+        async fn upsert_merges_updates_into_cached_data() {
+            let (_, bridge) = fixtures::refreshed_bridge(home()).await;
+            let patch = json!({ "type": "light", "id": "l1", "on": { "on": false } });
+
+            let changes = upsert_to_cache(&mut bridge.cache(), vec![event("update", patch)]);
+
+            assert!(matches!(
+                &changes[..],
+                [HueEventKind::Updated { rid, .. }] if rid.rid == "l1"
+            ));
+            assert!(!bridge.light("l1").unwrap().is_on());
+            assert_eq!(bridge.light("l1").unwrap().brightness(), 80.0);
+        }
+
+        #[tokio::test]
+        async fn upsert_adds_camera_motion_to_motion_cameras() {
+            let (_, bridge) = fixtures::refreshed_bridge(home()).await;
+            let camera = owned(
+                "camera_motion",
+                "cm1",
+                json!({ "enabled": true, "motion": { "motion_valid": true } }),
+            );
+
+            upsert_to_cache(&mut bridge.cache(), vec![event("add", camera)]);
+
+            let cache = bridge.cache();
+            assert!(cache.motion_cameras.contains_key("cm1"));
+            assert!(cache.motions.is_empty());
+        }
+
+        #[tokio::test]
+        async fn upsert_tracks_scene_activation() {
+            let (_, bridge) = fixtures::refreshed_bridge(scenes(&["s1"])).await;
+            let room = ResourceIdentifier::new("r1", ResourceType::Room);
+            let status = |id: &str, active: &str| json!({ "type": "scene", "id": id, "status": { "active": active } });
+
+            upsert_to_cache(
+                &mut bridge.cache(),
+                vec![event("update", status("s2", "static"))],
+            );
+            assert_eq!(bridge.last_active_scene_for(&room).unwrap().id(), "s2");
+
+            // s1 was already active, so this does not count as a recall
+            upsert_to_cache(
+                &mut bridge.cache(),
+                vec![event("update", status("s1", "static"))],
+            );
+            assert_eq!(bridge.last_active_scene_for(&room).unwrap().id(), "s2");
+        }
+
+        #[tokio::test]
+        async fn upsert_ignores_deletes_of_uncached_types() {
+            let (_, bridge) = fixtures::refreshed_bridge(home()).await;
+
+            for rtype in UNCACHED {
+                let data = json!({ "type": rtype, "id": "x1" });
+                upsert_to_cache(&mut bridge.cache(), vec![event("delete", data)]);
             }
-            ResourceType::ZigbeeDeviceDiscovery => {
-                cache.zigbee_dds.retain(|id, _| !ids.contains(&id));
+            assert!(bridge.data().is_some());
+            assert_eq!(bridge.n_lights(), 1);
+        }
+
+        /// Yields `events`, then ends, or stays open if `hang` is set.
+        struct FakeStream {
+            events: std::collections::VecDeque<reqwest_eventsource::Event>,
+            hang: bool,
+        }
+
+        impl futures_util::Stream for FakeStream {
+            type Item = Result<reqwest_eventsource::Event, reqwest_eventsource::Error>;
+
+            fn poll_next(
+                mut self: std::pin::Pin<&mut Self>,
+                _: &mut std::task::Context<'_>,
+            ) -> std::task::Poll<Option<Self::Item>> {
+                use std::task::Poll;
+                match self.events.pop_front() {
+                    Some(event) => Poll::Ready(Some(Ok(event))),
+                    None if self.hang => Poll::Pending,
+                    None => Poll::Ready(None),
+                }
             }
-            ResourceType::Zone => {
-                cache.zones.retain(|id, _| !ids.contains(&id));
+        }
+
+        impl EventStream for FakeStream {
+            fn close(&mut self) {}
+        }
+
+        fn message(events: serde_json::Value) -> reqwest_eventsource::Event {
+            let mut message = reqwest_eventsource::Event::Message(Default::default());
+            if let reqwest_eventsource::Event::Message(m) = &mut message {
+                m.data = events.to_string();
             }
+            message
+        }
+
+        #[tokio::test]
+        async fn closed_stream_reconnects_and_refetches() {
+            let (mock, bridge) = fixtures::refreshed_bridge(home()).await;
+            let update = json!([{
+                "id": "e1",
+                "creationtime": "2024-01-01T00:00:00Z",
+                "type": "update",
+                "data": [{ "type": "light", "id": "l1", "on": { "on": false } }],
+            }]);
+            let mut connects = 0;
+            let connect = || {
+                connects += 1;
+                let stream = FakeStream {
+                    events: match connects {
+                        1 => vec![reqwest_eventsource::Event::Open, message(update.clone())],
+                        _ => vec![reqwest_eventsource::Event::Open],
+                    }
+                    .into(),
+                    hang: connects > 1,
+                };
+                async move { Ok::<_, HueAPIError>(stream) }
+            };
+            let options = ListenOptions {
+                stall_timeout: None,
+                reconnect: true,
+                max_backoff: Duration::from_secs(60),
+            };
+            let updated = std::sync::atomic::AtomicUsize::new(0);
+            let cb = |changes: Vec<HueEventKind>| {
+                updated.fetch_add(changes.len(), std::sync::atomic::Ordering::SeqCst);
+            };
+
+            let read = read_event_streams(
+                connect,
+                &bridge.api,
+                &bridge.cache,
+                &bridge.in_flight,
+                options,
+                cb,
+            );
+            // The second stream stays open, so reading never finishes. The
+            // first reconnect waits for SSE_INITIAL_BACKOFF.
+            let _ = tokio::time::timeout(SSE_INITIAL_BACKOFF * 3, read).await;
+
+            assert_eq!(updated.into_inner(), 1);
+            assert_eq!(mock.requests_to(Method::GET, "/resource").len(), 2);
+            // The refetch replaced the light turned off by the event
+            assert!(bridge.light("l1").unwrap().is_on());
+        }
+
+        #[tokio::test]
+        async fn upsert_adds_and_deletes_resources() {
+            let (_, bridge) = fixtures::refreshed_bridge(home()).await;
+
+            upsert_to_cache(
+                &mut bridge.cache(),
+                vec![event("add", fixtures::ct_light("l2", "d1"))],
+            );
+            assert_eq!(bridge.n_lights(), 2);
+
+            let changes = upsert_to_cache(
+                &mut bridge.cache(),
+                vec![event("delete", json!({ "type": "light", "id": "l1" }))],
+            );
+            assert!(matches!(
+                &changes[..],
+                [HueEventKind::Deleted(rid)] if rid.rid == "l1"
+            ));
+            assert!(bridge.light("l1").is_none());
+            assert!(bridge.light("l2").is_some());
         }
     }
 }
@@ -1,16 +1,28 @@
-use crate::service::{ResourceIdentifier, ResourceType};
+#[cfg(feature = "chrono")]
+use crate::service::parse_timestamp;
+use crate::{
+    api::HueAPIError,
+    command::{merge_commands, ButtonCommand},
+    service::{Bridge, ResourceIdentifier, ResourceType},
+};
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::Arc};
 
 /// A physical button on a device.
 #[derive(Debug)]
-pub struct Button {
-    data: ButtonData,
+pub struct Button<'a> {
+    bridge: &'a Bridge,
+    data: Arc<ButtonData>,
 }
 
-impl Button {
-    pub fn new(data: ButtonData) -> Self {
-        Button { data }
+impl<'a> Button<'a> {
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<ButtonData>>) -> Self {
+        Button {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &ButtonData {
@@ -28,10 +40,42 @@ impl Button {
     pub fn control_id(&self) -> u8 {
         self.data.metadata.control_id
     }
+
+    /// The most recent event reported by the button, if any.
+    pub fn last_event(&self) -> Option<ButtonEvent> {
+        #[allow(deprecated)]
+        self.data
+            .button
+            .button_report
+            .as_ref()
+            .map(|report| report.event)
+            .or(self.data.button.last_event)
+    }
+
+    /// The interval in ms between [ButtonEvent::Repeat] events while the
+    /// button is held, if the device supports it.
+    pub fn repeat_interval(&self) -> Option<usize> {
+        self.data.button.repeat_interval
+    }
+
+    pub async fn set_repeat_interval(
+        &self,
+        interval: usize,
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.send(&[ButtonCommand::RepeatInterval(interval)]).await
+    }
+
+    pub async fn send(
+        &self,
+        commands: &[ButtonCommand],
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        let payload = merge_commands(commands);
+        self.bridge.api.put_button(self.id(), &payload).await
+    }
 }
 
 /// Internal representation of a [Button].
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ButtonData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -53,7 +97,7 @@ impl ButtonData {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ButtonMetadata {
     /// Control identifier of the switch which is unique per device.
     /// In combination with type:
@@ -63,18 +107,19 @@ pub struct ButtonMetadata {
     pub control_id: u8,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ButtonState {
     #[deprecated]
     pub last_event: Option<ButtonEvent>,
     pub button_report: Option<ButtonReport>,
-    /// Duration of a light transition or timed effects in ms.
+    /// Interval in ms between [ButtonEvent::Repeat] events while the button
+    /// is held.
     pub repeat_interval: Option<usize>,
     /// List of all button events that this device supports.
     pub event_values: HashSet<ButtonEvent>,
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ButtonEvent {
     InitialPress,
@@ -85,7 +130,7 @@ pub enum ButtonEvent {
     LongPress,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ButtonReport {
     /// Last time the value of this property is updated.
     pub updated: String,
@@ -93,14 +138,22 @@ pub struct ButtonReport {
     pub event: ButtonEvent,
 }
 
+#[cfg(feature = "chrono")]
+impl ButtonReport {
+    /// The `updated` timestamp, parsed as UTC.
+    pub fn updated_at(&self) -> Result<DateTime<Utc>, chrono::ParseError> {
+        parse_timestamp(&self.updated)
+    }
+}
+
 #[derive(Debug)]
 pub struct RelativeRotary {
-    data: RelativeRotaryData,
+    data: Arc<RelativeRotaryData>,
 }
 
 impl RelativeRotary {
-    pub fn new(data: RelativeRotaryData) -> Self {
-        RelativeRotary { data }
+    pub fn new(data: impl Into<Arc<RelativeRotaryData>>) -> Self {
+        RelativeRotary { data: data.into() }
     }
 
     pub fn data(&self) -> &RelativeRotaryData {
@@ -114,9 +167,14 @@ impl RelativeRotary {
     pub fn rid(&self) -> ResourceIdentifier {
         self.data.rid()
     }
+
+    /// The most recent rotation of the dial, if any has been reported.
+    pub fn last_rotation(&self) -> Option<RotationReport> {
+        self.data.relative_rotary.rotary_report.clone()
+    }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct RelativeRotaryData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -136,7 +194,7 @@ impl RelativeRotaryData {
     }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct RelativeRotaryState {
     #[deprecated = "moved to `rotary_report`"]
     /// Indicates which type of rotary event is received.
@@ -144,21 +202,21 @@ pub struct RelativeRotaryState {
     pub rotary_report: Option<RotationReport>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct RelativeRotaryLastEvent {
     /// Indicates which type of rotary event is received.
     pub action: RelativeRotaryAction,
     pub rotation: RelativeRotaryRotationState,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum RelativeRotaryAction {
     Start,
     Repeat,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct RelativeRotaryRotationState {
     /// A rotation opposite to the previous rotation will always start with new start command.
     pub direction: RelativeRotaryDirection,
@@ -172,7 +230,7 @@ pub struct RelativeRotaryRotationState {
     pub duration: u16,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub enum RelativeRotaryDirection {
     #[serde(rename = "clock_wise")]
     Clockwise,
@@ -180,7 +238,7 @@ pub enum RelativeRotaryDirection {
     CounterClockwise,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct RotationReport {
     /// Last time the value of this property was updated.
     pub updated: String,
@@ -188,3 +246,141 @@ pub struct RotationReport {
     pub action: RelativeRotaryAction,
     pub rotation: RelativeRotaryRotationState,
 }
+
+#[cfg(feature = "chrono")]
+impl RotationReport {
+    /// The `updated` timestamp, parsed as UTC.
+    pub fn updated_at(&self) -> Result<DateTime<Utc>, chrono::ParseError> {
+        parse_timestamp(&self.updated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::MockTransport;
+    use serde_json::json;
+
+    /// Button `control_id` of a Hue dimmer switch, last reporting `event`.
+    fn dimmer_button(control_id: u8, event: Option<&str>) -> serde_json::Value {
+        let mut button = json!({
+            "type": "button",
+            "id": format!("bt{control_id}"),
+            "id_v1": "/sensors/7",
+            "owner": { "rid": "d1", "rtype": "device" },
+            "metadata": { "control_id": control_id },
+            "button": {
+                "repeat_interval": 800,
+                "event_values": [
+                    "initial_press", "repeat", "short_release", "long_release", "long_press",
+                ],
+            },
+        });
+        if let Some(event) = event {
+            button["button"]["last_event"] = json!(event);
+            button["button"]["button_report"] =
+                json!({ "updated": "2024-03-01T08:15:30.512Z", "event": event });
+        }
+        button
+    }
+
+    #[test]
+    fn dimmer_button_reports_its_last_event() {
+        let bridge = MockTransport::new().bridge();
+        for (event, expected) in [
+            ("initial_press", ButtonEvent::InitialPress),
+            ("repeat", ButtonEvent::Repeat),
+            ("short_release", ButtonEvent::ShortRelease),
+            ("long_press", ButtonEvent::LongPress),
+            ("long_release", ButtonEvent::LongRelease),
+        ] {
+            let data: ButtonData = serde_json::from_value(dimmer_button(2, Some(event))).unwrap();
+            let button = Button::new(&bridge, data);
+
+            assert_eq!(button.last_event(), Some(expected));
+            assert_eq!(button.control_id(), 2);
+            assert_eq!(button.repeat_interval(), Some(800));
+            assert!(button.data().button.event_values.contains(&expected));
+        }
+    }
+
+    #[test]
+    fn button_report_takes_precedence_over_last_event() {
+        let bridge = MockTransport::new().bridge();
+        let mut payload = dimmer_button(4, Some("long_release"));
+        payload["button"]["last_event"] = json!("initial_press");
+        let data: ButtonData = serde_json::from_value(payload).unwrap();
+
+        assert_eq!(
+            Button::new(&bridge, data).last_event(),
+            Some(ButtonEvent::LongRelease)
+        );
+    }
+
+    #[test]
+    fn unpressed_button_has_no_last_event() {
+        let bridge = MockTransport::new().bridge();
+        let data: ButtonData = serde_json::from_value(dimmer_button(1, None)).unwrap();
+        let button = Button::new(&bridge, data);
+
+        assert_eq!(button.last_event(), None);
+        assert_eq!(button.control_id(), 1);
+    }
+
+    /// A dial turned in `direction`, as reported by the Tap dial switch.
+    fn rotary(direction: &str) -> serde_json::Value {
+        let rotation = json!({ "direction": direction, "steps": 30, "duration": 400 });
+        json!({
+            "type": "relative_rotary",
+            "id": "rr1",
+            "id_v1": "/sensors/12",
+            "owner": { "rid": "d1", "rtype": "device" },
+            "relative_rotary": {
+                "last_event": { "action": "repeat", "rotation": rotation },
+                "rotary_report": {
+                    "updated": "2024-03-01T08:15:30.512Z",
+                    "action": "repeat",
+                    "rotation": rotation,
+                },
+            },
+        })
+    }
+
+    #[test]
+    fn rotary_event_reports_its_rotation() {
+        let data: RelativeRotaryData = serde_json::from_value(rotary("clock_wise")).unwrap();
+        let dial = RelativeRotary::new(data);
+
+        let report = dial.last_rotation().unwrap();
+        assert_eq!(report.action, RelativeRotaryAction::Repeat);
+        assert_eq!(
+            report.rotation.direction,
+            RelativeRotaryDirection::Clockwise
+        );
+        assert_eq!(report.rotation.steps, 30);
+        assert_eq!(report.rotation.duration, 400);
+        assert_eq!(report.updated, "2024-03-01T08:15:30.512Z");
+        assert_eq!(dial.rid().rtype, ResourceType::RelativeRotary);
+    }
+
+    #[test]
+    fn rotary_directions_deserialize() {
+        let data: RelativeRotaryData =
+            serde_json::from_value(rotary("counter_clock_wise")).unwrap();
+        let report = RelativeRotary::new(data).last_rotation().unwrap();
+
+        assert_eq!(
+            report.rotation.direction,
+            RelativeRotaryDirection::CounterClockwise
+        );
+    }
+
+    #[test]
+    fn rotary_without_a_report_has_no_rotation() {
+        let mut payload = rotary("clock_wise");
+        payload["relative_rotary"] = json!({});
+        let data: RelativeRotaryData = serde_json::from_value(payload).unwrap();
+
+        assert!(RelativeRotary::new(data).last_rotation().is_none());
+    }
+}
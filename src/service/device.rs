@@ -1,9 +1,10 @@
 use crate::{
     api::HueAPIError,
     command::{merge_commands, DeviceCommand},
-    service::{Bridge, ResourceIdentifier, ResourceType},
+    service::{Bridge, Light, LightLevel, Motion, Resource, ResourceIdentifier, ResourceType},
 };
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 /// A Device represents a physical product which may have device-level
 /// properties, and implement multiple services -- even of the same type.
@@ -14,13 +15,16 @@ use serde::{Deserialize, Serialize};
 /// unregister them.
 pub struct Device<'a> {
     bridge: &'a Bridge,
-    data: DeviceData,
+    data: Arc<DeviceData>,
 }
 
 impl<'a> Device<'a> {
     /// Constructs a new Device. Unlikely to used in application contexts.
-    pub fn new(bridge: &'a Bridge, data: DeviceData) -> Self {
-        Device { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<DeviceData>>) -> Self {
+        Device {
+            bridge,
+            data: data.into(),
+        }
     }
 
     /// Retrieves underlying data representation.
@@ -41,7 +45,40 @@ impl<'a> Device<'a> {
     }
 
     pub fn archetype(&self) -> ProductArchetype {
-        self.data.metadata.archetype
+        self.data.metadata.archetype.clone()
+    }
+
+    /// The cached data of each of the device's services of type `rtype`.
+    pub fn services_of_type(&self, rtype: ResourceType) -> Vec<Resource> {
+        self.data
+            .services
+            .iter()
+            .filter(|s| s.rtype == rtype)
+            .filter_map(|s| self.bridge.resource(s))
+            .collect()
+    }
+
+    /// The [Light]s implemented by this device.
+    pub fn lights(&self) -> Vec<Light<'a>> {
+        self.service_ids(ResourceType::Light)
+            .filter_map(|s| self.bridge.light(&s.rid))
+            .collect()
+    }
+
+    /// The [LightLevel] sensor implemented by this device, if any.
+    pub fn light_level(&self) -> Option<LightLevel<'a>> {
+        self.service_ids(ResourceType::LightLevel)
+            .find_map(|s| self.bridge.light_level(&s.rid))
+    }
+
+    /// The [Motion] sensor implemented by this device, if any.
+    pub fn motion(&self) -> Option<Motion<'a>> {
+        self.service_ids(ResourceType::Motion)
+            .find_map(|s| self.bridge.motion(&s.rid))
+    }
+
+    fn service_ids(&self, rtype: ResourceType) -> impl Iterator<Item = &ResourceIdentifier> {
+        self.data.services.iter().filter(move |s| s.rtype == rtype)
     }
 
     /// Triggers a visual identification sequence, currently implemented as
@@ -62,7 +99,7 @@ impl<'a> Device<'a> {
 }
 
 /// Internal representation of a [Device].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct DeviceData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -85,7 +122,7 @@ impl DeviceData {
     }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ProductData {
     /// Unique identification of device model.
     pub model_id: String,
@@ -104,7 +141,7 @@ pub struct ProductData {
 }
 
 /// Known classes of compatible products.
-#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ProductArchetype {
     Bollard,
@@ -156,10 +193,13 @@ pub enum ProductArchetype {
     WallShade,
     WallSpot,
     WallWasher,
-    #[serde(other)]
     UnknownArchetype,
+    /// An archetype not known to this crate. The original value is preserved so
+    /// that it survives being written back to the bridge.
+    #[serde(untagged)]
+    Unknown(String),
 }
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct DeviceMetadata {
     /// Human readable name of a resource.
     pub name: String,
@@ -167,7 +207,7 @@ pub struct DeviceMetadata {
     pub archetype: ProductArchetype,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct UserTest {
     pub status: UserTestStatus,
     /// Activates or extends user usertest mode of device for 120 seconds.
@@ -176,7 +216,7 @@ pub struct UserTest {
     pub usertest: bool,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum UserTestStatus {
     Set,
@@ -186,12 +226,12 @@ pub enum UserTestStatus {
 /// A virtual device representing the charge state of a device's battery.
 #[derive(Debug)]
 pub struct DevicePower {
-    pub data: DevicePowerData,
+    pub data: Arc<DevicePowerData>,
 }
 
 impl DevicePower {
-    pub fn new(data: DevicePowerData) -> Self {
-        DevicePower { data }
+    pub fn new(data: impl Into<Arc<DevicePowerData>>) -> Self {
+        DevicePower { data: data.into() }
     }
 
     pub fn data(&self) -> &DevicePowerData {
@@ -210,13 +250,14 @@ impl DevicePower {
         self.data.power_state.battery_state
     }
 
-    pub fn battery_level(&self) -> Option<f32> {
+    /// Remaining battery charge percentage \[`0`, `100`\].
+    pub fn battery_level(&self) -> Option<u8> {
         self.data.power_state.battery_level
     }
 }
 
 /// Internal representation of a [DevicePower].
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct DevicePowerData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -236,12 +277,15 @@ impl DevicePowerData {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct PowerState {
-    battery_state: Option<BatteryState>,
-    battery_level: Option<f32>,
+    /// Status of the power source of a device. [None] for mains-powered devices.
+    pub battery_state: Option<BatteryState>,
+    /// The current battery state in percent, only for battery powered devices.
+    pub battery_level: Option<u8>,
 }
 
+/// Status of the power source of a device.
 #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum BatteryState {
@@ -250,15 +294,55 @@ pub enum BatteryState {
     Critical,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SetStatus {
     Set,
     Changing,
 }
 
+/// A virtual device representing the firmware update status of a device.
+#[derive(Debug)]
+pub struct DeviceSoftwareUpdate {
+    data: Arc<DeviceSoftwareUpdateData>,
+}
+
+impl DeviceSoftwareUpdate {
+    pub fn new(data: impl Into<Arc<DeviceSoftwareUpdateData>>) -> Self {
+        DeviceSoftwareUpdate { data: data.into() }
+    }
+
+    pub fn data(&self) -> &DeviceSoftwareUpdateData {
+        &self.data
+    }
+
+    pub fn id(&self) -> &str {
+        &self.data.id
+    }
+
+    pub fn rid(&self) -> ResourceIdentifier {
+        self.data.rid()
+    }
+
+    pub fn owner(&self) -> &ResourceIdentifier {
+        &self.data.owner
+    }
+
+    pub fn state(&self) -> SoftwareUpdateStatus {
+        self.data.state
+    }
+
+    /// Whether new firmware is available for, but not yet installed on, the device.
+    pub fn is_update_available(&self) -> bool {
+        matches!(
+            self.data.state,
+            SoftwareUpdateStatus::UpdatePending | SoftwareUpdateStatus::ReadyToInstall
+        )
+    }
+}
+
 /// Internal representation of the up-to-dateness of a device's firmware.
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct DeviceSoftwareUpdateData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -270,16 +354,29 @@ pub struct DeviceSoftwareUpdateData {
     pub problems: Vec<String>,
 }
 
-#[derive(Debug, Deserialize)]
+impl DeviceSoftwareUpdateData {
+    pub fn rid(&self) -> ResourceIdentifier {
+        ResourceIdentifier {
+            rid: self.id.to_owned(),
+            rtype: ResourceType::DeviceSoftwareUpdate,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SoftwareUpdateStatus {
     NoUpdate,
     UpdatePending,
+    ReadyToInstall,
     Installing,
+    #[serde(other)]
+    Unknown,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct BasicMetadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
 }
 
@@ -289,3 +386,102 @@ pub enum BasicStatus {
     Active,
     Inactive,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::fixtures;
+    use serde_json::json;
+
+    fn device_power(power_state: serde_json::Value) -> serde_json::Value {
+        json!({
+            "type": "device_power",
+            "id": "dp1",
+            "id_v1": "/sensors/5",
+            "owner": fixtures::rid("device", "d1"),
+            "power_state": power_state,
+        })
+    }
+
+    #[test]
+    fn battery_powered_device_power_deserializes() {
+        let data: DevicePowerData = serde_json::from_value(device_power(
+            json!({ "battery_state": "low", "battery_level": 23 }),
+        ))
+        .unwrap();
+        let power = DevicePower::new(data);
+
+        assert_eq!(power.battery_state(), Some(BatteryState::Low));
+        assert_eq!(power.battery_level(), Some(23));
+        assert_eq!(power.rid().rtype, ResourceType::DevicePower);
+    }
+
+    #[test]
+    fn every_battery_state_deserializes() {
+        for (state, expected) in [
+            ("normal", BatteryState::Normal),
+            ("low", BatteryState::Low),
+            ("critical", BatteryState::Critical),
+        ] {
+            let data: DevicePowerData =
+                serde_json::from_value(device_power(json!({ "battery_state": state }))).unwrap();
+            assert_eq!(data.power_state.battery_state, Some(expected));
+        }
+    }
+
+    #[test]
+    fn mains_powered_device_power_has_no_battery() {
+        let data: DevicePowerData = serde_json::from_value(device_power(json!({}))).unwrap();
+        let power = DevicePower::new(data);
+
+        assert_eq!(power.battery_state(), None);
+        assert_eq!(power.battery_level(), None);
+    }
+
+    #[tokio::test]
+    async fn device_power_is_cached_on_refresh() {
+        let (_, bridge) = fixtures::refreshed_bridge(vec![device_power(
+            json!({ "battery_state": "critical", "battery_level": 3 }),
+        )])
+        .await;
+
+        let power = bridge.device_power("dp1").unwrap();
+        assert_eq!(power.battery_state(), Some(BatteryState::Critical));
+        assert_eq!(power.battery_level(), Some(3));
+    }
+
+    #[tokio::test]
+    async fn device_navigates_to_its_services() {
+        let (_, bridge) = fixtures::refreshed_bridge(vec![
+            fixtures::device(
+                "d1",
+                &[
+                    fixtures::rid("light", "l1"),
+                    fixtures::rid("zigbee_connectivity", "zc1"),
+                ],
+            ),
+            fixtures::light("l1", "d1"),
+            fixtures::ct_light("l2", "d2"),
+            json!({
+                "type": "zigbee_connectivity",
+                "id": "zc1",
+                "owner": fixtures::rid("device", "d1"),
+                "status": "connected",
+                "mac_address": "00:17:88:01:0b:aa:bb:cc",
+            }),
+        ])
+        .await;
+        let device = bridge.device("d1").unwrap();
+
+        let lights = device.lights();
+        assert_eq!(lights.len(), 1);
+        assert_eq!(lights[0].id(), "l1");
+        assert!(matches!(
+            &device.services_of_type(ResourceType::ZigbeeConnectivity)[..],
+            [Resource::ZigbeeConnectivity(zigbee)] if zigbee.id == "zc1"
+        ));
+        assert!(device.services_of_type(ResourceType::Button).is_empty());
+        assert!(device.light_level().is_none());
+        assert!(device.motion().is_none());
+    }
+}
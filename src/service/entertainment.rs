@@ -4,16 +4,20 @@ use crate::{
     service::{BasicMetadata, BasicStatus, Bridge, ResourceIdentifier, ResourceType},
 };
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct EntertainmentConfiguration<'a> {
     bridge: &'a Bridge,
-    data: EntertainmentConfigurationData,
+    data: Arc<EntertainmentConfigurationData>,
 }
 
 impl<'a> EntertainmentConfiguration<'a> {
-    pub fn new(bridge: &'a Bridge, data: EntertainmentConfigurationData) -> Self {
-        EntertainmentConfiguration { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<EntertainmentConfigurationData>>) -> Self {
+        EntertainmentConfiguration {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &EntertainmentConfigurationData {
@@ -39,12 +43,122 @@ impl<'a> EntertainmentConfiguration<'a> {
             .await
     }
 
+    pub fn builder(
+        name: impl Into<String>,
+        configuration_type: EntertainmentConfigurationType,
+    ) -> EntertainmentConfigurationBuilder {
+        EntertainmentConfigurationBuilder::new(name, configuration_type)
+    }
+
+    /// Starts streaming to this configuration, as with
+    /// [Bridge::initialize_streaming].
     #[cfg(feature = "streaming")]
-    pub async fn open_stream(&self) {}
+    pub async fn open_stream(&self) -> Result<crate::service::StreamSession, HueAPIError> {
+        self.bridge.initialize_streaming(self.id()).await
+    }
+}
+
+/// Builder for a new [EntertainmentConfiguration], as passed to
+/// [Bridge::create_entertainment_configuration](crate::service::Bridge::create_entertainment_configuration).
+#[derive(Serialize)]
+pub struct EntertainmentConfigurationBuilder {
+    metadata: BasicMetadata,
+    configuration_type: EntertainmentConfigurationType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_proxy: Option<StreamProxyRequest>,
+    locations: ServiceLocationsRequest,
+}
+
+#[derive(Serialize)]
+struct StreamProxyRequest {
+    mode: StreamProxyMode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    node: Option<ResourceIdentifier>,
+}
+
+#[derive(Serialize)]
+struct ServiceLocationsRequest {
+    service_locations: Vec<ServiceLocationRequest>,
+}
+
+#[derive(Serialize)]
+struct ServiceLocationRequest {
+    service: ResourceIdentifier,
+    positions: Vec<Position>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    equalization_factor: Option<f32>,
+}
+
+impl EntertainmentConfigurationBuilder {
+    pub fn new(
+        name: impl Into<String>,
+        configuration_type: EntertainmentConfigurationType,
+    ) -> Self {
+        EntertainmentConfigurationBuilder {
+            metadata: BasicMetadata {
+                name: Some(name.into()),
+            },
+            configuration_type,
+            stream_proxy: None,
+            locations: ServiceLocationsRequest {
+                service_locations: vec![],
+            },
+        }
+    }
+
+    /// Let the bridge select the proxy node. This is the default.
+    pub fn stream_proxy_auto(mut self) -> Self {
+        self.stream_proxy = Some(StreamProxyRequest {
+            mode: StreamProxyMode::Auto,
+            node: None,
+        });
+        self
+    }
+
+    /// Relay entertainment traffic through `node`, which can be of type
+    /// [ResourceType::Bridge] or [ResourceType::ZigbeeConnectivity].
+    pub fn stream_proxy_node(mut self, node: ResourceIdentifier) -> Self {
+        self.stream_proxy = Some(StreamProxyRequest {
+            mode: StreamProxyMode::Manual,
+            node: Some(node),
+        });
+        self
+    }
+
+    /// Place an entertainment `service` of type [ResourceType::Entertainment]
+    /// at one or more `positions`, e.g. one per segment of a gradient strip.
+    pub fn location(mut self, service: ResourceIdentifier, positions: Vec<Position>) -> Self {
+        self.locations
+            .service_locations
+            .push(ServiceLocationRequest {
+                service,
+                positions,
+                equalization_factor: None,
+            });
+        self
+    }
+
+    /// As [EntertainmentConfigurationBuilder::location], with a relative
+    /// `equalization_factor` to compensate for differences in brightness.
+    pub fn location_equalized(
+        mut self,
+        service: ResourceIdentifier,
+        positions: Vec<Position>,
+        equalization_factor: f32,
+    ) -> Self {
+        self.locations
+            .service_locations
+            .push(ServiceLocationRequest {
+                service,
+                positions,
+                equalization_factor: Some(equalization_factor),
+            });
+        self
+    }
 }
 
 /// Internal representation of an [EntertainmentConfiguration].
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct EntertainmentConfigurationData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -97,7 +211,7 @@ pub enum EntertainmentConfigurationType {
     Other,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct StreamProxy {
     /// Proxymode used for this group.
     pub mode: StreamProxyMode,
@@ -116,7 +230,7 @@ pub enum StreamProxyMode {
     Manual,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct EntertainmentChannel {
     /// Bridge assigns a number upon creation. This is the number to be used by the HueStream API when addressing the channel
     pub channel_id: u8,
@@ -126,25 +240,27 @@ pub struct EntertainmentChannel {
     pub members: Vec<SegmentReference>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+/// A location in the entertainment area, with each coordinate in
+/// \[`-1.0`, `1.0`\].
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Position {
     pub x: f32,
     pub y: f32,
     pub z: f32,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SegmentReference {
     pub service: ResourceIdentifier,
     pub index: usize,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct EntertainmentServiceLocations {
     pub service_locations: Vec<EntertainmentServiceLocation>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct EntertainmentServiceLocation {
     pub service: ResourceIdentifier,
     #[deprecated = "use `positions`"]
@@ -159,12 +275,12 @@ pub struct EntertainmentServiceLocation {
 
 #[derive(Debug)]
 pub struct Entertainment {
-    data: EntertainmentData,
+    data: Arc<EntertainmentData>,
 }
 
 impl Entertainment {
-    pub fn new(data: EntertainmentData) -> Self {
-        Entertainment { data }
+    pub fn new(data: impl Into<Arc<EntertainmentData>>) -> Self {
+        Entertainment { data: data.into() }
     }
 
     pub fn data(&self) -> &EntertainmentData {
@@ -180,7 +296,7 @@ impl Entertainment {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct EntertainmentData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -211,7 +327,7 @@ impl EntertainmentData {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SegmentData {
     /// Defines if the segmentation of the device are configurable or not.
     pub configurable: bool,
@@ -221,8 +337,137 @@ pub struct SegmentData {
     pub segments: Vec<Segment>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Segment {
     pub start: usize,
     pub length: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::fixtures;
+    use reqwest::Method;
+    use serde_json::json;
+
+    fn entertainment(id: &str) -> ResourceIdentifier {
+        ResourceIdentifier::new(id, ResourceType::Entertainment)
+    }
+
+    #[test]
+    fn builder_serializes_to_the_documented_payload() {
+        let builder =
+            EntertainmentConfigurationBuilder::new("TV", EntertainmentConfigurationType::Screen)
+                .stream_proxy_auto()
+                .location(
+                    entertainment("e1"),
+                    vec![Position {
+                        x: -0.5,
+                        y: 0.5,
+                        z: 0.0,
+                    }],
+                )
+                .location_equalized(
+                    entertainment("e2"),
+                    vec![
+                        Position {
+                            x: 0.25,
+                            y: 1.0,
+                            z: 0.0,
+                        },
+                        Position {
+                            x: 0.5,
+                            y: 1.0,
+                            z: 0.0,
+                        },
+                    ],
+                    0.5,
+                );
+
+        assert_eq!(
+            serde_json::to_value(builder).unwrap(),
+            json!({
+                "metadata": { "name": "TV" },
+                "configuration_type": "screen",
+                "stream_proxy": { "mode": "auto" },
+                "locations": {
+                    "service_locations": [
+                        {
+                            "service": { "rid": "e1", "rtype": "entertainment" },
+                            "positions": [{ "x": -0.5, "y": 0.5, "z": 0.0 }],
+                        },
+                        {
+                            "service": { "rid": "e2", "rtype": "entertainment" },
+                            "positions": [
+                                { "x": 0.25, "y": 1.0, "z": 0.0 },
+                                { "x": 0.5, "y": 1.0, "z": 0.0 },
+                            ],
+                            "equalization_factor": 0.5,
+                        },
+                    ],
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn manual_proxy_names_its_node() {
+        let builder =
+            EntertainmentConfigurationBuilder::new("Desk", EntertainmentConfigurationType::Space3D)
+                .stream_proxy_node(ResourceIdentifier::new(
+                    "zc1",
+                    ResourceType::ZigbeeConnectivity,
+                ));
+
+        let json = serde_json::to_value(builder).unwrap();
+        assert_eq!(json["configuration_type"], "3dspace");
+        assert_eq!(
+            json["stream_proxy"],
+            json!({
+                "mode": "manual",
+                "node": { "rid": "zc1", "rtype": "zigbee_connectivity" },
+            })
+        );
+        assert_eq!(json["locations"], json!({ "service_locations": [] }));
+    }
+
+    #[tokio::test]
+    async fn create_posts_the_builder_and_caches_the_result() {
+        let (mock, bridge) = fixtures::refreshed_bridge(vec![]).await;
+        mock.respond_data(
+            Method::POST,
+            "/resource/entertainment_configuration",
+            fixtures::written("entertainment_configuration", "ec1"),
+        );
+        mock.respond_data(
+            Method::GET,
+            "/resource/entertainment_configuration/ec1",
+            json!([fixtures::entertainment_configuration("ec1", "e1")]),
+        );
+
+        let builder =
+            EntertainmentConfiguration::builder("TV", EntertainmentConfigurationType::Screen)
+                .location(
+                    entertainment("e1"),
+                    vec![Position {
+                        x: -0.5,
+                        y: 0.5,
+                        z: 0.0,
+                    }],
+                );
+        let config = bridge
+            .create_entertainment_configuration(builder)
+            .await
+            .unwrap();
+
+        assert_eq!(config.id(), "ec1");
+        assert!(bridge.entertainment_configuration("ec1").is_some());
+        let posted = mock.requests_to(Method::POST, "/resource/entertainment_configuration");
+        let body = posted[0].body.as_ref().unwrap();
+        assert_eq!(body["metadata"]["name"], "TV");
+        assert_eq!(
+            body["locations"]["service_locations"][0]["service"]["rid"],
+            "e1"
+        );
+    }
+}
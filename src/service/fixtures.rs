@@ -0,0 +1,202 @@
+//! Resource JSON in the shape the bridge reports it, shared by the tests of
+//! each service module.
+
+use crate::{api::MockTransport, service::Bridge};
+use reqwest::Method;
+use serde_json::{json, Value};
+
+/// A self-signed certificate issued to the bridge ID reported by [bridge].
+pub(crate) const BRIDGE_CERTIFICATE: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBjjCCATOgAwIBAgIUFwtZLKUJyUXue1jM7kR6QgEryO4wCgYIKoZIzj0EAwIw
+GzEZMBcGA1UEAwwQMDAxNzg4ZmZmZTZhMWIyYzAgFw0yNjEwMTYxMjUxMjZaGA8y
+MTI2MDkyMjEyNTEyNlowGzEZMBcGA1UEAwwQMDAxNzg4ZmZmZTZhMWIyYzBZMBMG
+ByqGSM49AgEGCCqGSM49AwEHA0IABG8r9SYfhUErgIzUtxbthqrYx4mmMI0ZFYdy
+2O2IfWUrE8dmDwuU1Ya4e3YMwQ7EayiM5NNrsUYzh3tcqM4iIrijUzBRMB0GA1Ud
+DgQWBBSB8SLU7+yTkUvUgBiimI97VOu4/zAfBgNVHSMEGDAWgBSB8SLU7+yTkUvU
+gBiimI97VOu4/zAPBgNVHRMBAf8EBTADAQH/MAoGCCqGSM49BAMCA0kAMEYCIQDf
+TrC4N8ximlk2USVhUi9sYr89E0xnob/stb0sSm9/BwIhAMLgSo1Wyp8UgBGhToNL
+FFj89jzmm+81ViIYjtYWrSML
+-----END CERTIFICATE-----
+";
+
+/// A resource reference of type `rtype`.
+pub(crate) fn rid(rtype: &str, id: &str) -> Value {
+    json!({ "rid": id, "rtype": rtype })
+}
+
+/// A mock transport answering `GET /resource` with `resources`, and a bridge
+/// whose cache has been filled from it.
+pub(crate) async fn refreshed_bridge(resources: Vec<Value>) -> (MockTransport, Bridge) {
+    let mock = MockTransport::new();
+    mock.respond_data(Method::GET, "/resource", Value::Array(resources));
+    let bridge = mock.bridge();
+    bridge.refresh().await.expect("refresh from mock");
+    (mock, bridge)
+}
+
+pub(crate) fn bridge(id: &str) -> Value {
+    json!({
+        "type": "bridge",
+        "id": id,
+        "owner": rid("device", "bridge-device"),
+        "bridge_id": "001788fffe6a1b2c",
+        "time_zone": { "time_zone": "Europe/Amsterdam" },
+    })
+}
+
+pub(crate) fn device(id: &str, services: &[Value]) -> Value {
+    json!({
+        "type": "device",
+        "id": id,
+        "product_data": {
+            "model_id": "LCA001",
+            "manufacturer_name": "Signify Netherlands B.V.",
+            "product_name": "Hue color lamp",
+            "product_archetype": "sultan_bulb",
+            "certified": true,
+            "software_version": "1.104.2",
+        },
+        "metadata": { "name": format!("Device {id}"), "archetype": "sultan_bulb" },
+        "services": services,
+    })
+}
+
+/// A color capable light owned by the device `owner`, currently on.
+pub(crate) fn light(id: &str, owner: &str) -> Value {
+    let mut light = ct_light(id, owner);
+    light["color"] = json!({
+        "xy": { "x": 0.4573, "y": 0.41 },
+        "gamut": {
+            "red": { "x": 0.6915, "y": 0.3083 },
+            "green": { "x": 0.17, "y": 0.7 },
+            "blue": { "x": 0.1532, "y": 0.0475 },
+        },
+        "gamut_type": "C",
+    });
+    light
+}
+
+/// A white ambiance light owned by the device `owner`, currently on.
+pub(crate) fn ct_light(id: &str, owner: &str) -> Value {
+    json!({
+        "type": "light",
+        "id": id,
+        "owner": rid("device", owner),
+        "metadata": { "name": format!("Light {id}"), "archetype": "sultan_bulb" },
+        "on": { "on": true },
+        "dimming": { "brightness": 80.0, "min_dim_level": 0.2 },
+        "color_temperature": {
+            "mirek": 366,
+            "mirek_valid": true,
+            "mirek_schema": { "mirek_minimum": 153, "mirek_maximum": 500 },
+        },
+        "dynamics": {
+            "status": "none",
+            "status_values": ["none"],
+            "speed": 0.0,
+            "speed_valid": false,
+        },
+        "alert": { "action_values": ["breathe"] },
+        "signaling": {},
+        "mode": "normal",
+    })
+}
+
+pub(crate) fn room(id: &str, children: &[Value], grouped_light: &str) -> Value {
+    json!({
+        "type": "room",
+        "id": id,
+        "children": children,
+        "services": [rid("grouped_light", grouped_light)],
+        "metadata": { "name": format!("Room {id}"), "archetype": "living_room" },
+    })
+}
+
+pub(crate) fn zone(id: &str, children: &[Value], grouped_light: &str) -> Value {
+    let mut zone = room(id, children, grouped_light);
+    zone["type"] = json!("zone");
+    zone["metadata"]["name"] = json!(format!("Zone {id}"));
+    zone
+}
+
+pub(crate) fn grouped_light(id: &str, owner: Value, on: bool) -> Value {
+    json!({
+        "type": "grouped_light",
+        "id": id,
+        "owner": owner,
+        "on": { "on": on },
+        "dimming": { "brightness": 50.0 },
+        "alert": { "action_values": ["breathe"] },
+    })
+}
+
+/// A scene for `group` setting each light in `targets` to full brightness.
+pub(crate) fn scene(id: &str, group: Value, targets: &[&str], active: bool) -> Value {
+    let actions: Vec<Value> = targets
+        .iter()
+        .map(|light| {
+            json!({
+                "target": rid("light", light),
+                "action": { "on": { "on": true }, "dimming": { "brightness": 100.0 } },
+            })
+        })
+        .collect();
+    json!({
+        "type": "scene",
+        "id": id,
+        "actions": actions,
+        "metadata": { "name": format!("Scene {id}") },
+        "group": group,
+        "speed": 0.5,
+        "auto_dynamic": false,
+        "status": { "active": if active { "static" } else { "inactive" } },
+    })
+}
+
+/// An inactive smart scene for `group`, recalling `target` at 7:00 on Mondays.
+pub(crate) fn smart_scene(id: &str, group: Value, target: &str) -> Value {
+    json!({
+        "type": "smart_scene",
+        "id": id,
+        "metadata": { "name": format!("Smart scene {id}") },
+        "group": group,
+        "week_timeslots": [{
+            "timeslots": [{
+                "start_time": { "kind": "time", "time": { "hour": 7, "minute": 0, "second": 0 } },
+                "target": rid("scene", target),
+            }],
+            "recurrence": ["monday"],
+        }],
+        "transition_duration": 60000,
+        "state": "inactive",
+    })
+}
+
+/// A successful response to a write, listing the affected resource.
+pub(crate) fn written(rtype: &str, id: &str) -> Value {
+    json!([rid(rtype, id)])
+}
+
+/// An inactive entertainment configuration placing the entertainment
+/// service `entertainment` front left.
+pub(crate) fn entertainment_configuration(id: &str, entertainment: &str) -> Value {
+    json!({
+        "type": "entertainment_configuration",
+        "id": id,
+        "metadata": { "name": format!("Entertainment {id}") },
+        "configuration_type": "screen",
+        "status": "inactive",
+        "stream_proxy": { "mode": "auto", "node": rid("entertainment", entertainment) },
+        "channels": [],
+        "locations": {
+            "service_locations": [{
+                "service": rid("entertainment", entertainment),
+                "position": { "x": -0.5, "y": 0.5, "z": 0.0 },
+                "positions": [{ "x": -0.5, "y": 0.5, "z": 0.0 }],
+                "equalization_factor": 1.0,
+            }],
+        },
+        "light_services": [],
+    })
+}
@@ -1,21 +1,27 @@
 use crate::{
     api::HueAPIError,
-    command::{merge_commands, GroupCommand},
-    service::{AlertState, Bridge, OnState, ResourceIdentifier, ResourceType, SignalType},
+    command::{merge_commands, GroupCommand, SceneCommand},
+    service::{
+        AlertEffectType, AlertState, Bridge, CIEColor, Light, LightAction, OnState,
+        ResourceIdentifier, ResourceType, Scene, SceneStatus, SignalType,
+    },
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::{borrow::Borrow, collections::HashSet, sync::Arc, time::Duration};
 
 /// A virtual device consisting of a group of lights.
 #[derive(Debug)]
 pub struct Group<'a> {
     bridge: &'a Bridge,
-    data: GroupData,
+    data: Arc<GroupData>,
 }
 
 impl<'a> Group<'a> {
-    pub fn new(bridge: &'a Bridge, data: GroupData) -> Self {
-        Group { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<GroupData>>) -> Self {
+        Group {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &GroupData {
@@ -38,6 +44,41 @@ impl<'a> Group<'a> {
             .unwrap_or_default()
     }
 
+    /// The [Light]s aggregated by this group, resolved through its owning
+    /// [Room](crate::service::Room), [Zone](crate::service::Zone), or
+    /// [Home](crate::service::Home).
+    pub fn lights(&self) -> Vec<Light<'a>> {
+        let owner = &self.data.owner;
+        let children = match owner.rtype {
+            ResourceType::BridgeHome => return self.bridge.lights(),
+            ResourceType::Room => self
+                .bridge
+                .room(&owner.rid)
+                .map(|r| r.data.children.clone()),
+            ResourceType::Zone => self
+                .bridge
+                .zone(&owner.rid)
+                .map(|z| z.data.children.clone()),
+            _ => None,
+        }
+        .unwrap_or_default();
+
+        self.bridge
+            .lights()
+            .into_iter()
+            .filter(|l| children.contains(&l.rid()) || children.contains(&l.data().owner))
+            .collect()
+    }
+
+    /// The current color of each member light, or [None] for lights that do not
+    /// support color.
+    pub fn member_colors(&self) -> Vec<(ResourceIdentifier, Option<CIEColor>)> {
+        self.lights()
+            .into_iter()
+            .map(|l| (l.rid(), l.data().color.as_ref().map(|c| c.xy)))
+            .collect()
+    }
+
     pub async fn on(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
         self.send(&[GroupCommand::On(true)]).await
     }
@@ -50,17 +91,150 @@ impl<'a> Group<'a> {
         self.send(&[GroupCommand::On(!self.is_on())]).await
     }
 
-    pub async fn send(
+    /// Sets the brightness percentage of all members that are turned on.
+    pub async fn dim(&self, brightness: f32) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.send(&[GroupCommand::Dim(brightness)]).await
+    }
+
+    /// Makes all members perform a breathe cycle.
+    pub async fn alert(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.send(&[GroupCommand::Alert(AlertEffectType::Breathe)])
+            .await
+    }
+
+    /// Makes all members perform a breathe cycle. Grouped lights have no
+    /// identify action, so this uses the breathe [Group::alert] instead.
+    pub async fn identify(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.alert().await
+    }
+
+    /// Recalls `scene` on the members, transitioning over `duration` in ms.
+    /// A scene of this group's [Room](crate::service::Room) or
+    /// [Zone](crate::service::Zone) is recalled as a whole. A scene of another
+    /// group with an action for every member, such as a scene of a room
+    /// containing this zone, is applied to the members alone by sending each
+    /// its action. Any other scene fails with [HueAPIError::SceneGroupMismatch].
+    pub async fn recall_scene(
+        &self,
+        scene: &Scene<'_>,
+        duration: Option<usize>,
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        if scene.data().group == self.data.owner {
+            return scene
+                .send(&[SceneCommand::Recall {
+                    action: Some(SceneStatus::Active),
+                    duration,
+                    dimming: None,
+                }])
+                .await;
+        }
+        let actions = self
+            .lights()
+            .iter()
+            .map(|l| scene.data().actions.iter().find(|a| a.target == l.rid()))
+            .collect::<Option<Vec<_>>>();
+        let actions = match actions {
+            Some(actions) if !actions.is_empty() => actions,
+            _ => {
+                return Err(HueAPIError::SceneGroupMismatch {
+                    scene: scene.rid(),
+                    group: self.rid(),
+                })
+            }
+        };
+        let mut rids = Vec::new();
+        for action in actions {
+            let id = &action.target.rid;
+            let payload = scene_action_payload(&action.action, duration);
+            let outcome = self.bridge.api.put_light(id, &payload).await?;
+            if outcome.warnings.is_empty() {
+                self.bridge.apply_optimistic_light_update(id, &payload);
+            }
+            rids.extend(outcome.rids);
+        }
+        Ok(rids)
+    }
+
+    /// Sets the transition duration and dynamic palette or effect speed for
+    /// all members.
+    pub async fn set_dynamics(
         &self,
-        commands: &[GroupCommand],
+        duration: Option<Duration>,
+        speed: Option<f32>,
     ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
-        let payload = merge_commands(commands);
+        self.send(&[GroupCommand::Dynamics {
+            duration: duration.map(|d| d.as_millis() as usize),
+            speed,
+        }])
+        .await
+    }
+
+    /// Fades all members to the CIE XY color over the given `duration`.
+    pub async fn set_color_xy_over(
+        &self,
+        x: f32,
+        y: f32,
+        duration: Duration,
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.send(&[
+            GroupCommand::color(x, y),
+            GroupCommand::Dynamics {
+                duration: Some(duration.as_millis() as usize),
+                speed: None,
+            },
+        ])
+        .await
+    }
+
+    /// Fades all members to the color temperature in mirek over the given `duration`.
+    pub async fn set_color_temp_over(
+        &self,
+        mirek: u16,
+        duration: Duration,
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.send(&[
+            GroupCommand::ColorTemp(mirek),
+            GroupCommand::Dynamics {
+                duration: Some(duration.as_millis() as usize),
+                speed: None,
+            },
+        ])
+        .await
+    }
+
+    /// Fades all turned-on members to the brightness percentage over the given `duration`.
+    pub async fn set_brightness_over(
+        &self,
+        brightness: f32,
+        duration: Duration,
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.send(&[
+            GroupCommand::Dim(brightness),
+            GroupCommand::Dynamics {
+                duration: Some(duration.as_millis() as usize),
+                speed: None,
+            },
+        ])
+        .await
+    }
+
+    /// Sends the merged `commands`, which may be a slice, an array, or an owned
+    /// collection such as a [Vec].
+    pub async fn send<C: Borrow<GroupCommand>>(
+        &self,
+        commands: impl IntoIterator<Item = C>,
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        let payload = {
+            let commands = commands.into_iter().collect::<Vec<_>>();
+            GroupCommand::validate(&commands)?;
+            merge_commands(&commands.iter().map(C::borrow).collect::<Vec<_>>())
+        };
         self.bridge.api.put_grouped_light(self.id(), &payload).await
     }
 }
 
 /// Internal representation of a [Group].
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct GroupData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -87,15 +261,218 @@ impl GroupData {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct GroupDimmingState {
     /// Brightness percentage.
     /// Value cannot be `0`, writing `0` changes it to lowest possible brightness.
     pub brightness: f32,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct GroupSignalingState {
     /// Signals that the group supports.
     pub signal_values: Option<HashSet<SignalType>>,
 }
+
+/// The body of a light PUT applying a scene `action`, transitioning over
+/// `duration` in ms if set rather than the action's own duration.
+fn scene_action_payload(action: &LightAction, duration: Option<usize>) -> serde_json::Value {
+    let mut payload = serde_json::to_value(action).unwrap_or_default();
+    if let Some(map) = payload.as_object_mut() {
+        // Lights outside the ct spectrum have no mirek, which cannot be written
+        if action
+            .color_temperature
+            .as_ref()
+            .is_some_and(|ct| ct.mirek.is_none())
+        {
+            map.remove("color_temperature");
+        }
+        if let Some(duration) = duration {
+            map.insert(
+                "dynamics".into(),
+                serde_json::json!({ "duration": duration }),
+            );
+        }
+    }
+    payload
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::{HueAPIError, MockTransport},
+        service::{fixtures, Bridge},
+    };
+    use reqwest::Method;
+    use serde_json::{json, Value};
+
+    /// A bridge with the grouped light `g1`, answering writes to it.
+    async fn grouped(on: bool) -> (MockTransport, Bridge) {
+        let (mock, bridge) = fixtures::refreshed_bridge(vec![fixtures::grouped_light(
+            "g1",
+            fixtures::rid("room", "r1"),
+            on,
+        )])
+        .await;
+        mock.respond_data(
+            Method::PUT,
+            "/resource/grouped_light/g1",
+            fixtures::written("grouped_light", "g1"),
+        );
+        (mock, bridge)
+    }
+
+    fn written(mock: &MockTransport) -> Vec<Value> {
+        mock.requests_to(Method::PUT, "/resource/grouped_light/g1")
+            .into_iter()
+            .filter_map(|r| r.body)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn toggle_turns_off_a_group_that_is_on() {
+        let (mock, bridge) = grouped(true).await;
+        let group = bridge.group("g1").unwrap();
+        assert!(group.is_on());
+
+        group.toggle().await.unwrap();
+
+        assert_eq!(written(&mock), vec![json!({ "on": { "on": false } })]);
+    }
+
+    #[tokio::test]
+    async fn toggle_turns_on_a_group_that_is_off() {
+        let (mock, bridge) = grouped(false).await;
+
+        bridge.group("g1").unwrap().toggle().await.unwrap();
+
+        assert_eq!(written(&mock), vec![json!({ "on": { "on": true } })]);
+    }
+
+    #[tokio::test]
+    async fn convenience_methods_send_their_command() {
+        let (mock, bridge) = grouped(true).await;
+        let group = bridge.group("g1").unwrap();
+
+        group.on().await.unwrap();
+        group.off().await.unwrap();
+        group.dim(40.0).await.unwrap();
+        group.identify().await.unwrap();
+
+        assert_eq!(
+            written(&mock),
+            vec![
+                json!({ "on": { "on": true } }),
+                json!({ "on": { "on": false } }),
+                json!({ "dimming": { "brightness": 40.0 } }),
+                json!({ "alert": { "action": "breathe" } }),
+            ]
+        );
+    }
+
+    /// The room `r1` with the lights `l1` and `l2`, and the zone `z1` with
+    /// only `l1`, each with a grouped light.
+    async fn home(scenes: Vec<Value>) -> (MockTransport, Bridge) {
+        let mut resources = vec![
+            fixtures::device("d1", &[fixtures::rid("light", "l1")]),
+            fixtures::device("d2", &[fixtures::rid("light", "l2")]),
+            fixtures::light("l1", "d1"),
+            fixtures::light("l2", "d2"),
+            fixtures::room(
+                "r1",
+                &[fixtures::rid("device", "d1"), fixtures::rid("device", "d2")],
+                "g1",
+            ),
+            fixtures::zone("z1", &[fixtures::rid("light", "l1")], "g2"),
+            fixtures::grouped_light("g1", fixtures::rid("room", "r1"), true),
+            fixtures::grouped_light("g2", fixtures::rid("zone", "z1"), true),
+        ];
+        resources.extend(scenes);
+        let (mock, bridge) = fixtures::refreshed_bridge(resources).await;
+        mock.respond_data(
+            Method::PUT,
+            "/resource/light/l1",
+            fixtures::written("light", "l1"),
+        );
+        (mock, bridge)
+    }
+
+    #[tokio::test]
+    async fn scene_of_the_group_is_recalled() {
+        let scene = fixtures::scene("s1", fixtures::rid("zone", "z1"), &["l1"], false);
+        let (mock, bridge) = home(vec![scene]).await;
+        mock.respond_data(
+            Method::PUT,
+            "/resource/scene/s1",
+            fixtures::written("scene", "s1"),
+        );
+        let scene = bridge.scene("s1").unwrap();
+
+        let rids = bridge
+            .group("g2")
+            .unwrap()
+            .recall_scene(&scene, Some(400))
+            .await
+            .unwrap();
+
+        assert_eq!(rids, [scene.rid()]);
+        let sent = mock.requests_to(Method::PUT, "/resource/scene/s1");
+        assert_eq!(
+            sent[0].body,
+            Some(json!({ "recall": { "action": "active", "duration": 400 } }))
+        );
+        assert!(mock
+            .requests_to(Method::PUT, "/resource/light/l1")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn scene_of_a_larger_group_is_applied_to_the_members() {
+        let scene = fixtures::scene("s1", fixtures::rid("room", "r1"), &["l1", "l2"], false);
+        let (mock, bridge) = home(vec![scene]).await;
+        let scene = bridge.scene("s1").unwrap();
+
+        let rids = bridge
+            .group("g2")
+            .unwrap()
+            .recall_scene(&scene, Some(400))
+            .await
+            .unwrap();
+
+        assert_eq!(rids, [bridge.light("l1").unwrap().rid()]);
+        let sent = mock.requests_to(Method::PUT, "/resource/light/l1");
+        assert_eq!(
+            sent[0].body,
+            Some(json!({
+                "on": { "on": true },
+                "dimming": { "brightness": 100.0 },
+                "dynamics": { "duration": 400 },
+            }))
+        );
+        assert!(mock
+            .requests_to(Method::PUT, "/resource/scene/s1")
+            .is_empty());
+        assert!(mock
+            .requests_to(Method::PUT, "/resource/light/l2")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn scene_missing_members_is_a_mismatch() {
+        let scene = fixtures::scene("s1", fixtures::rid("zone", "z2"), &["l1"], false);
+        let (mock, bridge) = home(vec![scene]).await;
+        let scene = bridge.scene("s1").unwrap();
+        let group = bridge.group("g1").unwrap();
+
+        let res = group.recall_scene(&scene, None).await;
+
+        assert_eq!(
+            res,
+            Err(HueAPIError::SceneGroupMismatch {
+                scene: scene.rid(),
+                group: group.rid(),
+            })
+        );
+        assert_eq!(mock.requests().len(), 1);
+    }
+}
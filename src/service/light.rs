@@ -1,21 +1,33 @@
+#[cfg(feature = "chrono")]
+use crate::service::parse_timestamp;
 use crate::{
     api::HueAPIError,
     command::{merge_commands, LightCommand},
-    service::{Bridge, ProductArchetype, ResourceIdentifier, ResourceType},
+    service::{Bridge, ProductArchetype, ResourceIdentifier, ResourceType, SendOutcome},
 };
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::{
+    borrow::Borrow,
+    collections::HashSet,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
 
 /// A controllable bulb, strip, or other light device.
 #[derive(Debug)]
 pub struct Light<'a> {
     bridge: &'a Bridge,
-    data: LightData,
+    data: Arc<LightData>,
 }
 
 impl<'a> Light<'a> {
-    pub fn new(bridge: &'a Bridge, data: LightData) -> Self {
-        Light { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<LightData>>) -> Self {
+        Light {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &LightData {
@@ -30,6 +42,15 @@ impl<'a> Light<'a> {
         self.data.rid()
     }
 
+    /// Whether this handle's data has been superseded in the [Bridge] cache by
+    /// a refresh or event, or the light has since been removed.
+    pub fn is_stale(&self) -> bool {
+        match self.bridge.cached_light_data(self.id()) {
+            Some(data) => !Arc::ptr_eq(&self.data, &data),
+            None => true,
+        }
+    }
+
     pub fn is_on(&self) -> bool {
         self.data.on.on
     }
@@ -38,38 +59,255 @@ impl<'a> Light<'a> {
         self.data.color.is_some()
     }
 
-    pub async fn identify(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+    /// Brightness percentage.
+    pub fn brightness(&self) -> f32 {
+        self.data.dimming.brightness
+    }
+
+    /// Color temperature in mirek, or [None] when the light color is not in
+    /// the ct spectrum.
+    pub fn color_temp_mirek(&self) -> Option<u16> {
+        self.data.color_temperature.mirek
+    }
+
+    /// The current XY color at the current brightness, converted to RGB, or
+    /// [None] if the light does not support color.
+    pub fn color_rgb(&self) -> Option<(u8, u8, u8)> {
+        self.data
+            .color
+            .as_ref()
+            .map(|c| c.xy.as_rgb(Some(self.brightness() / 100.0)))
+    }
+
+    /// Whether the powerup behavior shown in [LightData::powerup] has been
+    /// configured in the light source.
+    pub fn powerup_configured(&self) -> bool {
+        self.data
+            .powerup
+            .as_ref()
+            .map(|p| p.configured)
+            .unwrap_or_default()
+    }
+
+    /// Effects this light can be set to, or an empty set if it does not
+    /// support effects.
+    pub fn available_effects(&self) -> &HashSet<EffectType> {
+        static NO_EFFECTS: OnceLock<HashSet<EffectType>> = OnceLock::new();
+        match &self.data.effects {
+            Some(effects) => &effects.effect_values,
+            None => NO_EFFECTS.get_or_init(HashSet::new),
+        }
+    }
+
+    /// Timed effects this light can be set to, or an empty set if it does not
+    /// support timed effects.
+    pub fn available_timed_effects(&self) -> &HashSet<TimedEffectType> {
+        static NO_TIMED_EFFECTS: OnceLock<HashSet<TimedEffectType>> = OnceLock::new();
+        match &self.data.timed_effects {
+            Some(effects) => &effects.effect_values,
+            None => NO_TIMED_EFFECTS.get_or_init(HashSet::new),
+        }
+    }
+
+    pub async fn identify(&self) -> Result<SendOutcome, HueAPIError> {
         self.send(&[LightCommand::Identify]).await
     }
 
-    pub async fn alert(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+    pub async fn alert(&self) -> Result<SendOutcome, HueAPIError> {
         self.send(&[LightCommand::Alert(AlertEffectType::Breathe)])
             .await
     }
 
-    pub async fn on(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+    pub async fn on(&self) -> Result<SendOutcome, HueAPIError> {
         self.send(&[LightCommand::On(true)]).await
     }
 
-    pub async fn off(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+    pub async fn off(&self) -> Result<SendOutcome, HueAPIError> {
         self.send(&[LightCommand::On(false)]).await
     }
 
-    pub async fn toggle(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+    pub async fn toggle(&self) -> Result<SendOutcome, HueAPIError> {
         self.send(&[LightCommand::On(!self.is_on())]).await
     }
 
-    pub async fn send(
+    /// Sets the color from anything convertible to a [CIEColor], such as an
+    /// sRGB array: `light.set_color([255, 0, 0])`.
+    pub async fn set_color(&self, color: impl Into<CIEColor>) -> Result<SendOutcome, HueAPIError> {
+        let cie = color.into();
+        self.send(&[LightCommand::color(cie.x, cie.y)]).await
+    }
+
+    /// Like [Light::set_color], but first moves the color to the nearest point
+    /// this light can reproduce, rather than leaving the bridge to clip it. If
+    /// the light does not report a gamut, the color is sent unchanged.
+    pub async fn set_color_clamped(
         &self,
-        commands: &[LightCommand],
-    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
-        let payload = merge_commands(commands);
-        self.bridge.api.put_light(self.id(), &payload).await
+        color: impl Into<CIEColor>,
+    ) -> Result<SendOutcome, HueAPIError> {
+        let cie = color.into();
+        let cie = match &self.data.color {
+            Some(state) => cie.clamp_to_gamut(&state.gamut),
+            None => cie,
+        };
+        self.send(&[LightCommand::color(cie.x, cie.y)]).await
+    }
+
+    /// Fades to the CIE XY color over the given `duration`.
+    pub async fn set_color_xy_over(
+        &self,
+        x: f32,
+        y: f32,
+        duration: Duration,
+    ) -> Result<SendOutcome, HueAPIError> {
+        self.send(&[
+            LightCommand::color(x, y),
+            LightCommand::Dynamics {
+                duration: Some(duration.as_millis() as usize),
+                speed: None,
+            },
+        ])
+        .await
+    }
+
+    /// Sets the color temperature in Kelvin, clamped to the range this light
+    /// supports according to its [MirekSchema].
+    pub async fn set_color_temp_kelvin(&self, kelvin: u32) -> Result<SendOutcome, HueAPIError> {
+        let mirek = self
+            .data
+            .color_temperature
+            .mirek_schema
+            .mirek_from_kelvin(kelvin);
+        self.send(&[LightCommand::ColorTemp(mirek)]).await
+    }
+
+    /// Fades to the color temperature in mirek over the given `duration`.
+    pub async fn set_color_temp_over(
+        &self,
+        mirek: u16,
+        duration: Duration,
+    ) -> Result<SendOutcome, HueAPIError> {
+        self.send(&[
+            LightCommand::ColorTemp(mirek),
+            LightCommand::Dynamics {
+                duration: Some(duration.as_millis() as usize),
+                speed: None,
+            },
+        ])
+        .await
+    }
+
+    /// Fades to the brightness percentage over the given `duration`.
+    pub async fn set_brightness_over(
+        &self,
+        brightness: f32,
+        duration: Duration,
+    ) -> Result<SendOutcome, HueAPIError> {
+        self.send(&[
+            LightCommand::Dim(brightness),
+            LightCommand::Dynamics {
+                duration: Some(duration.as_millis() as usize),
+                speed: None,
+            },
+        ])
+        .await
+    }
+
+    /// Starts building a fade to a new color, color temperature, or brightness,
+    /// sent as a single request once [LightTransition::send] is called.
+    pub fn transition(&self) -> LightTransition<'_, 'a> {
+        LightTransition {
+            light: self,
+            color: None,
+            mirek: None,
+            brightness: None,
+            duration: None,
+        }
+    }
+
+    /// Sends the merged `commands`, which may be a slice, an array, or an owned
+    /// collection such as a [Vec].
+    pub async fn send<C: Borrow<LightCommand>>(
+        &self,
+        commands: impl IntoIterator<Item = C>,
+    ) -> Result<SendOutcome, HueAPIError> {
+        let payload = {
+            let commands = commands.into_iter().collect::<Vec<_>>();
+            merge_commands(&commands.iter().map(C::borrow).collect::<Vec<_>>())
+        };
+        let outcome = self.bridge.api.put_light(self.id(), &payload).await?;
+        if outcome.warnings.is_empty() {
+            self.bridge
+                .apply_optimistic_light_update(self.id(), &payload);
+        }
+        Ok(outcome)
+    }
+}
+
+/// A fade of a [Light], created with [Light::transition]. Setting a color
+/// replaces any color temperature, and vice versa.
+#[derive(Debug)]
+pub struct LightTransition<'l, 'a> {
+    light: &'l Light<'a>,
+    color: Option<CIEColor>,
+    mirek: Option<u16>,
+    brightness: Option<f32>,
+    duration: Option<Duration>,
+}
+
+impl<'l, 'a> LightTransition<'l, 'a> {
+    pub fn to_color(mut self, color: impl Into<CIEColor>) -> Self {
+        self.color = Some(color.into());
+        self.mirek = None;
+        self
+    }
+
+    /// The target color temperature in mirek.
+    pub fn to_color_temp(mut self, mirek: u16) -> Self {
+        self.mirek = Some(mirek);
+        self.color = None;
+        self
+    }
+
+    /// The target brightness percentage.
+    pub fn to_brightness(mut self, brightness: f32) -> Self {
+        self.brightness = Some(brightness);
+        self
+    }
+
+    /// How long the fade takes. Without it, the bridge's default transition
+    /// is used.
+    pub fn over(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    fn commands(&self) -> Vec<LightCommand> {
+        let mut commands = vec![];
+        if let Some(color) = self.color {
+            commands.push(LightCommand::color(color.x, color.y));
+        }
+        if let Some(mirek) = self.mirek {
+            commands.push(LightCommand::ColorTemp(mirek));
+        }
+        if let Some(brightness) = self.brightness {
+            commands.push(LightCommand::Dim(brightness));
+        }
+        if let Some(duration) = self.duration {
+            commands.push(LightCommand::Dynamics {
+                duration: Some(duration.as_millis() as usize),
+                speed: None,
+            });
+        }
+        commands
+    }
+
+    pub async fn send(self) -> Result<SendOutcome, HueAPIError> {
+        self.light.send(self.commands()).await
     }
 }
 
 /// Internal representation of a [Light].
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct LightData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -107,7 +345,7 @@ impl LightData {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct LightMetadata {
     /// Human readable name of a resource.
     pub name: String,
@@ -117,7 +355,7 @@ pub struct LightMetadata {
     pub fixed_mired: Option<u16>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct OnState {
     /// On/Off state of the light.
     ///
@@ -126,7 +364,7 @@ pub struct OnState {
     pub on: bool,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct DimmingState {
     /// Brightness percentage.
     ///
@@ -136,7 +374,7 @@ pub struct DimmingState {
     pub min_dim_level: Option<f32>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ColorTempState {
     /// Color temperature in mirek or [None] when the light color is not in the ct spectrum.
     pub mirek: Option<u16>,
@@ -146,6 +384,12 @@ pub struct ColorTempState {
 }
 
 impl ColorTempState {
+    /// The color temperature in Kelvin, or [None] when the light color is not
+    /// in the ct spectrum.
+    pub fn as_kelvin(&self) -> Option<u32> {
+        self.mirek.filter(|m| *m > 0).map(|m| 1_000_000 / m as u32)
+    }
+
     /// Uses a [method by Tanner Helland](https://tannerhelland.com/2012/09/18/convert-temperature-rgb-algorithm-code.html)
     /// to convert a color temperature in mirek (reciprocal megakelvin) to RGB.
     pub fn as_rgb(&self) -> (u8, u8, u8) {
@@ -182,7 +426,7 @@ impl ColorTempState {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct MirekSchema {
     /// Minimum color temperature this light supports.
     pub mirek_minimum: u16,
@@ -190,7 +434,36 @@ pub struct MirekSchema {
     pub mirek_maximum: u16,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+impl MirekSchema {
+    /// The warmest color temperature this light supports, in Kelvin.
+    pub fn kelvin_minimum(&self) -> u32 {
+        1_000_000 / (self.mirek_maximum as u32).max(1)
+    }
+
+    /// The coolest color temperature this light supports, in Kelvin.
+    pub fn kelvin_maximum(&self) -> u32 {
+        1_000_000 / (self.mirek_minimum as u32).max(1)
+    }
+
+    /// Converts `kelvin` to mirek, clamped to the range this light supports.
+    pub fn mirek_from_kelvin(&self, kelvin: u32) -> u16 {
+        kelvin_to_mirek(kelvin, self.mirek_minimum, self.mirek_maximum)
+    }
+}
+
+/// The coolest color temperature accepted by the API, in mirek (about 6536 K).
+pub(crate) const MIREK_MINIMUM: u16 = 153;
+/// The warmest color temperature accepted by the API, in mirek (2000 K).
+pub(crate) const MIREK_MAXIMUM: u16 = 500;
+
+/// Converts `kelvin` to mirek (reciprocal megakelvin), clamped to
+/// \[`minimum`, `maximum`\] mirek.
+pub(crate) fn kelvin_to_mirek(kelvin: u32, minimum: u16, maximum: u16) -> u16 {
+    let mirek = 1_000_000 / kelvin.max(1);
+    mirek.clamp(minimum as u32, maximum.max(minimum) as u32) as u16
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ColorState {
     /// CIE XY gamut position
     pub xy: CIEColor,
@@ -204,9 +477,23 @@ pub struct ColorState {
     pub gamut_type: GamutType,
 }
 
+/// The hue in degrees of an RGB color with the given maximum channel and chroma.
+fn hue(r: f32, g: f32, b: f32, max: f32, c: f32) -> f32 {
+    let h = if c == 0.0 {
+        0.0
+    } else if max == r {
+        ((g - b) / c).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / c + 2.0
+    } else {
+        (r - g) / c + 4.0
+    };
+    h * 60.0
+}
+
 /// Color gamut of color bulb.
 /// Some bulbs do not properly return the Gamut information. In this case this is not present.
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct CIEGamut {
     /// CIE XY gamut position
     pub red: CIEColor,
@@ -216,9 +503,25 @@ pub struct CIEGamut {
     pub blue: CIEColor,
 }
 
+impl CIEGamut {
+    /// Whether `color` lies within this gamut triangle, including its edges.
+    pub fn contains(&self, color: &CIEColor) -> bool {
+        fn cross(o: &CIEColor, a: &CIEColor, b: &CIEColor) -> f32 {
+            (a.x - o.x) * (b.y - o.y) - (a.y - o.y) * (b.x - o.x)
+        }
+
+        let d1 = cross(&self.red, &self.green, color);
+        let d2 = cross(&self.green, &self.blue, color);
+        let d3 = cross(&self.blue, &self.red, color);
+        let has_neg = d1 < 0.0 || d2 < 0.0 || d3 < 0.0;
+        let has_pos = d1 > 0.0 || d2 > 0.0 || d3 > 0.0;
+        !(has_neg && has_pos)
+    }
+}
+
 /// A [CIE chromaticity](https://en.wikipedia.org/wiki/CIE_1931_color_space#CIE_xy_chromaticity_diagram_and_the_CIE_xyY_color_space)
 /// of a [Light].
-#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
 pub struct CIEColor {
     /// X position in color gamut
     pub x: f32,
@@ -233,12 +536,39 @@ pub enum ParseColorError {
 }
 
 impl CIEColor {
+    /// Create a new color, clamping each coordinate to the valid range \[`0.0`, `1.0`\].
+    pub fn new(x: f32, y: f32) -> CIEColor {
+        fn clamp_unit(v: f32) -> f32 {
+            if v.is_nan() {
+                0.0
+            } else {
+                v.clamp(0.0, 1.0)
+            }
+        }
+
+        CIEColor {
+            x: clamp_unit(x),
+            y: clamp_unit(y),
+        }
+    }
+
+    /// Whether both coordinates fall within the valid range \[`0.0`, `1.0`\].
+    pub fn is_valid(&self) -> bool {
+        (0.0..=1.0).contains(&self.x) && (0.0..=1.0).contains(&self.y)
+    }
+
     /// The method provided in the [official Hue documentataion](https://developers.meethue.com/develop/application-design-guidance/color-conversion-formulas-rgb-to-xy-and-back/)
     /// for converting RGB colors to CIE.
+    ///
+    /// Expects 8-bit, gamma-encoded sRGB values, as found in hex codes and most
+    /// color pickers. For linear light values use [CIEColor::from_rgb_linear].
     pub fn from_rgb(rgb: [u8; 3]) -> CIEColor {
-        let r = rgb[0] as f32 / 255.0;
-        let g = rgb[1] as f32 / 255.0;
-        let b = rgb[2] as f32 / 255.0;
+        CIEColor::from_rgb_unit(rgb.map(|c| c as f32 / 255.0))
+    }
+
+    /// As [CIEColor::from_rgb], with gamma-encoded channels in \[`0.0`, `1.0`\].
+    fn from_rgb_unit(rgb: [f32; 3]) -> CIEColor {
+        let [r, g, b] = rgb.map(|c| c.clamp(0.0, 1.0));
         // Gamma corrections
         let r = if r > 0.04045 {
             ((r + 0.055) / 1.055).powf(2.4)
@@ -256,25 +586,53 @@ impl CIEColor {
             b / 12.92
         };
 
+        CIEColor::from_rgb_linear([r, g, b])
+    }
+
+    /// Convert linear RGB values in the range \[`0.0`, `1.0`\] to CIE, skipping
+    /// the sRGB gamma correction performed by [CIEColor::from_rgb].
+    ///
+    /// Use this for colors which are already linear, e.g. those produced by a
+    /// rendering pipeline. Passing gamma-encoded values here will produce the
+    /// wrong hue.
+    pub fn from_rgb_linear(rgb: [f32; 3]) -> CIEColor {
+        let [r, g, b] = rgb.map(|c| c.clamp(0.0, 1.0));
+
         let x = 0.4124 * r + 0.3576 * g + 0.1805 * b;
         let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
         let z = 0.0193 * r + 0.1192 * g + 0.9505 * b;
+        let sum = x + y + z;
+
+        if sum == 0.0 {
+            return CIEColor { x: 0.0, y: 0.0 };
+        }
 
         CIEColor {
-            x: x / (x + y + z),
-            y: y / (x + y + z),
+            x: x / sum,
+            y: y / sum,
         }
     }
 
+    /// Convert to 8-bit, gamma-encoded sRGB, the inverse of [CIEColor::from_rgb].
+    ///
+    /// The xy coordinates carry no luminance, so `bri` supplies it, defaulting to
+    /// `1.0`. Channels which fall outside \[`0`, `255`\] are clamped.
     pub fn as_rgb(&self, bri: Option<f32>) -> (u8, u8, u8) {
+        let [r, g, b] = self.as_rgb_unit(bri).map(|c| (c * 255.0).round() as u8);
+        (r, g, b)
+    }
+
+    /// As [CIEColor::as_rgb], with gamma-encoded channels in \[`0.0`, `1.0`\].
+    fn as_rgb_unit(&self, bri: Option<f32>) -> [f32; 3] {
         let z = 1.0 - self.x - self.y;
         let yy = bri.unwrap_or(1.0);
         let xx = (yy / self.y) * self.x;
         let zz = (yy / self.y) * z;
 
-        let mut r = xx * 1.656492 - yy * 0.354851 - zz * 0.255038;
-        let mut g = -xx * 0.707196 + yy * 1.655397 + zz * 0.036152;
-        let mut b = xx * 0.051713 - yy * 0.121364 + zz * 1.011530;
+        // The inverse of the matrix in [CIEColor::from_rgb_linear]
+        let mut r = xx * 3.2406 - yy * 1.5372 - zz * 0.4986;
+        let mut g = -xx * 0.9689 + yy * 1.8758 + zz * 0.0415;
+        let mut b = xx * 0.0557 - yy * 0.2040 + zz * 1.0570;
 
         r = if r <= 0.0031308 {
             12.92 * r
@@ -292,7 +650,132 @@ impl CIEColor {
             (1.0 + 0.055) * b.powf(1.0 / 2.4) - 0.055
         };
 
-        ((r * 256.0) as u8, (g * 256.0) as u8, (b * 256.0) as u8)
+        [r, g, b].map(|c| c.clamp(0.0, 1.0))
+    }
+
+    /// Convert an HSV color to CIE, where `h` is the hue in degrees
+    /// \[`0.0`, `360.0`\) and `s` and `v` are in \[`0.0`, `1.0`\]. Uses the same
+    /// sRGB gamma correction as [CIEColor::from_rgb], without rounding to 8 bits.
+    pub fn from_hsv(h: f32, s: f32, v: f32) -> CIEColor {
+        let s = s.clamp(0.0, 1.0);
+        let v = v.clamp(0.0, 1.0);
+        CIEColor::from_hue_chroma(h, v * s, v - v * s)
+    }
+
+    /// Convert an HSL color to CIE, where `h` is the hue in degrees
+    /// \[`0.0`, `360.0`\) and `s` and `l` are in \[`0.0`, `1.0`\]. Uses the same
+    /// sRGB gamma correction as [CIEColor::from_rgb], without rounding to 8 bits.
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> CIEColor {
+        let s = s.clamp(0.0, 1.0);
+        let l = l.clamp(0.0, 1.0);
+        let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        CIEColor::from_hue_chroma(h, c, l - c / 2.0)
+    }
+
+    /// Build a color from its hue in degrees, chroma, and the amount `m` added
+    /// to every channel, as shared by HSV and HSL.
+    fn from_hue_chroma(h: f32, c: f32, m: f32) -> CIEColor {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u8 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
+        };
+        CIEColor::from_rgb_unit([r + m, g + m, b + m])
+    }
+
+    /// Convert to HSV as `(h, s, v)`, with the hue in degrees \[`0.0`, `360.0`\)
+    /// and saturation and value in \[`0.0`, `1.0`\]. As with [CIEColor::as_rgb],
+    /// `bri` sets the luminance, which the xy coordinates alone do not carry.
+    pub fn as_hsv(&self, bri: Option<f32>) -> (f32, f32, f32) {
+        let [r, g, b] = self.as_rgb_unit(bri);
+        let max = r.max(g).max(b);
+        let c = max - r.min(g).min(b);
+        let s = if max == 0.0 { 0.0 } else { c / max };
+        (hue(r, g, b, max, c), s, max)
+    }
+
+    /// Convert to HSL as `(h, s, l)`, with the hue in degrees \[`0.0`, `360.0`\)
+    /// and saturation and lightness in \[`0.0`, `1.0`\]. As with
+    /// [CIEColor::as_rgb], `bri` sets the luminance, which the xy coordinates
+    /// alone do not carry.
+    pub fn as_hsl(&self, bri: Option<f32>) -> (f32, f32, f32) {
+        let [r, g, b] = self.as_rgb_unit(bri);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let c = max - min;
+        let l = (max + min) / 2.0;
+        let s = if c == 0.0 {
+            0.0
+        } else {
+            c / (1.0 - (2.0 * l - 1.0).abs())
+        };
+        (hue(r, g, b, max, c), s, l)
+    }
+
+    /// The closest color within `gamut`, or this color if it already lies
+    /// inside. Points outside are projected onto the nearest edge of the gamut
+    /// triangle.
+    pub fn clamp_to_gamut(&self, gamut: &CIEGamut) -> CIEColor {
+        if gamut.contains(self) {
+            return *self;
+        }
+        [
+            (gamut.red, gamut.green),
+            (gamut.green, gamut.blue),
+            (gamut.blue, gamut.red),
+        ]
+        .into_iter()
+        .map(|(a, b)| self.closest_on_segment(&a, &b))
+        .min_by(|a, b| self.distance_sq(a).total_cmp(&self.distance_sq(b)))
+        .unwrap_or(*self)
+    }
+
+    fn closest_on_segment(&self, a: &CIEColor, b: &CIEColor) -> CIEColor {
+        let (dx, dy) = (b.x - a.x, b.y - a.y);
+        let len_sq = dx * dx + dy * dy;
+        if len_sq == 0.0 {
+            return *a;
+        }
+        let t = ((self.x - a.x) * dx + (self.y - a.y) * dy) / len_sq;
+        a.lerp(b, t)
+    }
+
+    fn distance_sq(&self, other: &CIEColor) -> f32 {
+        (self.x - other.x).powi(2) + (self.y - other.y).powi(2)
+    }
+
+    /// Linearly interpolate between this color and `other`, where `t` of `0.0`
+    /// yields `self` and `1.0` yields `other`. `t` is clamped to \[`0.0`, `1.0`\].
+    ///
+    /// Interpolation happens in xy space, which is not perceptually uniform: the
+    /// midpoint of two colors may not look halfway between them. To blend colors
+    /// authored in RGB, convert each with [CIEColor::from_rgb] first.
+    pub fn lerp(&self, other: &CIEColor, t: f32) -> CIEColor {
+        let t = t.clamp(0.0, 1.0);
+        CIEColor::new(
+            self.x + (other.x - self.x) * t,
+            self.y + (other.y - self.y) * t,
+        )
+    }
+
+    /// The mean of `colors` in xy space, or [None] if `colors` is empty.
+    ///
+    /// As with [CIEColor::lerp], this is not perceptually uniform, but is a
+    /// reasonable representative color for a group of lights.
+    pub fn average(colors: &[CIEColor]) -> Option<CIEColor> {
+        if colors.is_empty() {
+            return None;
+        }
+        let n = colors.len() as f32;
+        let (x, y) = colors
+            .iter()
+            .fold((0.0, 0.0), |(x, y), c| (x + c.x, y + c.y));
+        Some(CIEColor::new(x / n, y / n))
     }
 
     /// Try to parse a hex color string, and on success convert the value to
@@ -348,6 +831,20 @@ impl CIEColor {
     }
 }
 
+impl From<[u8; 3]> for CIEColor {
+    fn from(rgb: [u8; 3]) -> Self {
+        CIEColor::from_rgb(rgb)
+    }
+}
+
+impl TryFrom<&str> for CIEColor {
+    type Error = ParseColorError;
+
+    fn try_from(hex: &str) -> Result<Self, Self::Error> {
+        CIEColor::from_hex(hex)
+    }
+}
+
 /// The gamut types supported by hue.
 #[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub enum GamutType {
@@ -362,7 +859,7 @@ pub enum GamutType {
     Other,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct DynamicsState {
     /// Current status of the lamp with dynamics.
     pub status: DynamicsStatus,
@@ -383,7 +880,7 @@ pub enum DynamicsStatus {
     None,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct AlertState {
     /// Alert effects that the light supports.
     pub action_values: HashSet<AlertEffectType>,
@@ -395,7 +892,7 @@ pub enum AlertEffectType {
     Breathe,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SignalingState {
     /// Signals that the light supports.
     pub signal_values: Option<HashSet<SignalType>>,
@@ -403,7 +900,7 @@ pub struct SignalingState {
     pub status: Option<SignalStatus>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SignalStatus {
     /// Indicates which signal is currently active.
     pub signal: SignalType,
@@ -413,6 +910,14 @@ pub struct SignalStatus {
     pub colors: Vec<ColorFeatureBasic>,
 }
 
+#[cfg(feature = "chrono")]
+impl SignalStatus {
+    /// The `estimated_end` timestamp, parsed as UTC.
+    pub fn estimated_end_at(&self) -> Result<DateTime<Utc>, chrono::ParseError> {
+        parse_timestamp(&self.estimated_end)
+    }
+}
+
 #[derive(Copy, Clone, Eq, Debug, Deserialize, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SignalType {
@@ -433,7 +938,7 @@ pub enum Mode {
     Streaming,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct GradientState {
     /// Collection of gradients points.
     /// For control of the gradient points through a PUT a minimum of 2 points need to be provided.
@@ -449,20 +954,21 @@ pub struct GradientState {
     pub pixel_count: Option<usize>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct GradientPoint {
     pub color: ColorFeatureBasic,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ColorFeatureBasic {
     pub xy: CIEColor,
 }
 
 impl ColorFeatureBasic {
+    /// Out-of-range coordinates are clamped to \[`0.0`, `1.0`\].
     pub fn xy(x: f32, y: f32) -> Self {
         ColorFeatureBasic {
-            xy: CIEColor { x, y },
+            xy: CIEColor::new(x, y),
         }
     }
 }
@@ -475,7 +981,7 @@ pub enum GradientMode {
     RandomPixelated,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct EffectState {
     pub effect: Option<EffectType>,
     /// Possible effect values you can set in a light.
@@ -495,10 +1001,18 @@ pub enum EffectType {
     Sparkle,
     Fire,
     Candle,
+    Sunbeam,
+    Enchant,
+    Cosmos,
+    Underwater,
     NoEffect,
+    /// An effect introduced in a newer bridge firmware that is not yet known
+    /// to this library.
+    #[serde(other)]
+    Unknown,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct TimedEffectState {
     pub effect: Option<TimedEffectType>,
     /// Possible timed effect values you can set in a light.
@@ -520,9 +1034,13 @@ pub enum TimedEffectType {
     Sunrise,
     Sunset,
     NoEffect,
+    /// A timed effect introduced in a newer bridge firmware that is not yet
+    /// known to this library.
+    #[serde(other)]
+    Unknown,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct PowerupState {
     /// When setting the [PowerupPresetType::Custom] preset the additional properties can be set.
     /// For all other presets, no other properties can be included.
@@ -542,7 +1060,7 @@ pub enum PowerupPresetType {
     Custom,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct PowerupOnState {
     /// State to activate after powerup. When setting mode [PowerupOnMode::On], the `on` property must be included.
     pub mode: PowerupOnMode,
@@ -559,3 +1077,392 @@ pub enum PowerupOnMode {
     /// Return to the state it was in before powering off.
     Previous,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_rgb_near(actual: (u8, u8, u8), expected: (u8, u8, u8)) {
+        let near = |a: u8, b: u8| a.abs_diff(b) <= 2;
+        assert!(
+            near(actual.0, expected.0) && near(actual.1, expected.1) && near(actual.2, expected.2),
+            "{actual:?} is not near {expected:?}"
+        );
+    }
+
+    #[test]
+    fn rgb_round_trips_through_cie() {
+        // Each color with its relative luminance, which xy does not carry
+        for (rgb, luminance) in [
+            ((255, 0, 0), 0.2126),
+            ((0, 255, 0), 0.7152),
+            ((0, 0, 255), 0.0722),
+            ((255, 255, 255), 1.0),
+        ] {
+            let color = CIEColor::from_rgb([rgb.0, rgb.1, rgb.2]);
+            assert_rgb_near(color.as_rgb(Some(luminance)), rgb);
+        }
+    }
+
+    #[test]
+    fn full_brightness_white_does_not_overflow() {
+        let white = CIEColor::from_rgb([255, 255, 255]);
+        assert_eq!(white.as_rgb(None), (255, 255, 255));
+        assert_eq!(white.as_rgb(Some(2.0)), (255, 255, 255));
+    }
+
+    #[test]
+    fn out_of_range_channels_are_clamped() {
+        // Red at full luminance pushes the red channel far past 255
+        let red = CIEColor::from_rgb([255, 0, 0]);
+        let (r, g, b) = red.as_rgb(Some(1.0));
+        assert_eq!(r, 255);
+        assert!(g < r && b < r);
+
+        // A point outside the sRGB gamut drives some channels negative
+        assert_eq!(CIEColor::new(0.1, 0.8).as_rgb(None).2, 0);
+    }
+
+    /// Gamut C, as reported by Hue white and color ambiance lights.
+    fn gamut_c() -> CIEGamut {
+        CIEGamut {
+            red: CIEColor::new(0.6915, 0.3083),
+            green: CIEColor::new(0.17, 0.7),
+            blue: CIEColor::new(0.1532, 0.0475),
+        }
+    }
+
+    #[test]
+    fn saturated_blue_is_clamped_into_gamut_c() {
+        let gamut = gamut_c();
+        let blue = CIEColor::from_rgb([0, 0, 255]);
+        assert!(!gamut.contains(&blue));
+
+        let clamped = blue.clamp_to_gamut(&gamut);
+        assert!(gamut.contains(&clamped), "{clamped:?}");
+        assert!(clamped.distance_sq(&gamut.blue) < 1e-3);
+        // Nothing in the gamut lies closer to the requested blue
+        assert!(clamped.distance_sq(&blue) <= gamut.blue.distance_sq(&blue));
+    }
+
+    #[test]
+    fn colors_inside_the_gamut_are_unchanged() {
+        let white = CIEColor::from_rgb([255, 255, 255]);
+        assert_eq!(white.clamp_to_gamut(&gamut_c()), white);
+    }
+
+    #[test]
+    fn colors_beyond_a_corner_snap_to_it() {
+        let gamut = gamut_c();
+        let clamped = CIEColor::new(0.1, 0.0).clamp_to_gamut(&gamut);
+        assert!(clamped.distance_sq(&gamut.blue) < 1e-6, "{clamped:?}");
+    }
+
+    #[tokio::test]
+    async fn set_color_clamped_sends_the_clamped_color() {
+        use crate::service::fixtures;
+        use reqwest::Method;
+
+        let (mock, bridge) = fixtures::refreshed_bridge(vec![fixtures::light("l1", "d1")]).await;
+        mock.respond_data(
+            Method::PUT,
+            "/resource/light/l1",
+            fixtures::written("light", "l1"),
+        );
+        let blue = CIEColor::from_rgb([0, 0, 255]);
+        bridge
+            .light("l1")
+            .unwrap()
+            .set_color_clamped(blue)
+            .await
+            .unwrap();
+
+        let sent = mock.requests_to(Method::PUT, "/resource/light/l1");
+        let xy = &sent[0].body.as_ref().unwrap()["color"]["xy"];
+        let sent = CIEColor::new(
+            xy["x"].as_f64().unwrap() as f32,
+            xy["y"].as_f64().unwrap() as f32,
+        );
+        assert_eq!(sent, blue.clamp_to_gamut(&gamut_c()));
+    }
+
+    /// A partly rejected send, as the bridge answers a brightness above 100.
+    fn partly_rejected() -> String {
+        serde_json::json!({
+            "errors": [{ "description": "invalid value, 900.0, for parameter, dimming.brightness" }],
+            "data": [{ "rid": "l1", "rtype": "light" }],
+        })
+        .to_string()
+    }
+
+    #[tokio::test]
+    async fn optimistic_send_updates_the_cache() {
+        use crate::{command::LightCommand, service::fixtures};
+        use reqwest::Method;
+
+        let (mock, bridge) = fixtures::refreshed_bridge(vec![fixtures::light("l1", "d1")]).await;
+        let bridge = bridge.optimistic(true);
+        mock.respond_data(
+            Method::PUT,
+            "/resource/light/l1",
+            fixtures::written("light", "l1"),
+        );
+        let light = bridge.light("l1").unwrap();
+        let outcome = light
+            .send([LightCommand::On(false), LightCommand::Dim(40.0)])
+            .await
+            .unwrap();
+
+        assert!(outcome.warnings.is_empty());
+        let light = bridge.light("l1").unwrap();
+        assert!(!light.is_on());
+        assert_eq!(light.brightness(), 40.0);
+    }
+
+    #[tokio::test]
+    async fn partly_rejected_send_leaves_the_cache() {
+        use crate::{command::LightCommand, service::fixtures};
+        use reqwest::Method;
+
+        let (mock, bridge) = fixtures::refreshed_bridge(vec![fixtures::light("l1", "d1")]).await;
+        let bridge = bridge.optimistic(true);
+        mock.respond(Method::PUT, "/resource/light/l1", 207, partly_rejected());
+        let light = bridge.light("l1").unwrap();
+        let outcome = light
+            .send([LightCommand::On(false), LightCommand::Dim(900.0)])
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.rids, [light.rid()]);
+        assert_eq!(
+            outcome.warnings,
+            ["invalid value, 900.0, for parameter, dimming.brightness"]
+        );
+        let light = bridge.light("l1").unwrap();
+        assert!(light.is_on());
+        assert_eq!(light.brightness(), 80.0);
+    }
+
+    fn assert_near(actual: (f32, f32, f32), expected: (f32, f32, f32)) {
+        let near = |a: f32, b: f32, tolerance: f32| (a - b).abs() <= tolerance;
+        assert!(
+            near(actual.0, expected.0, 1.0)
+                && near(actual.1, expected.1, 0.01)
+                && near(actual.2, expected.2, 0.01),
+            "{actual:?} is not near {expected:?}"
+        );
+    }
+
+    fn assert_same_color(actual: CIEColor, expected: CIEColor) {
+        assert!(
+            actual.distance_sq(&expected) < 1e-8,
+            "{actual:?} is not {expected:?}"
+        );
+    }
+
+    #[test]
+    fn hsv_matches_known_rgb() {
+        for (hsv, rgb) in [
+            ((0.0, 1.0, 1.0), [255, 0, 0]),
+            ((60.0, 1.0, 1.0), [255, 255, 0]),
+            ((120.0, 1.0, 1.0), [0, 255, 0]),
+            ((180.0, 1.0, 1.0), [0, 255, 255]),
+            ((240.0, 1.0, 1.0), [0, 0, 255]),
+            ((300.0, 1.0, 1.0), [255, 0, 255]),
+            ((0.0, 0.0, 1.0), [255, 255, 255]),
+        ] {
+            let (h, s, v) = hsv;
+            assert_same_color(CIEColor::from_hsv(h, s, v), CIEColor::from_rgb(rgb));
+        }
+    }
+
+    #[test]
+    fn hsl_matches_known_rgb() {
+        for (hsl, rgb) in [
+            ((0.0, 1.0, 0.5), [255, 0, 0]),
+            ((120.0, 1.0, 0.5), [0, 255, 0]),
+            ((240.0, 1.0, 0.5), [0, 0, 255]),
+            ((30.0, 1.0, 0.5), [255, 128, 0]),
+            ((0.0, 0.0, 1.0), [255, 255, 255]),
+        ] {
+            let (h, s, l) = hsl;
+            let expected = CIEColor::from_rgb(rgb);
+            let actual = CIEColor::from_hsl(h, s, l);
+            // 128 is not exactly half of 255
+            assert!(actual.distance_sq(&expected) < 1e-5, "{hsl:?}: {actual:?}");
+        }
+    }
+
+    #[test]
+    fn hue_wraps_and_inputs_are_clamped() {
+        let red = CIEColor::from_hsv(0.0, 1.0, 1.0);
+        assert_same_color(CIEColor::from_hsv(360.0, 1.0, 1.0), red);
+        assert_same_color(CIEColor::from_hsv(-360.0, 1.0, 1.0), red);
+        assert_same_color(CIEColor::from_hsv(0.0, 2.0, 1.5), red);
+        assert_same_color(CIEColor::from_hsl(720.0, 1.0, 0.5), red);
+        assert_eq!(
+            CIEColor::from_hsv(90.0, 1.0, 0.0),
+            CIEColor { x: 0.0, y: 0.0 }
+        );
+    }
+
+    #[test]
+    fn hsv_and_hsl_round_trip() {
+        // Each color with its relative luminance, which xy does not carry
+        for (hue, luminance) in [
+            (0.0, 0.2126),
+            (120.0, 0.7152),
+            (180.0, 0.7874),
+            (240.0, 0.0722),
+        ] {
+            let color = CIEColor::from_hsv(hue, 1.0, 1.0);
+            assert_near(color.as_hsv(Some(luminance)), (hue, 1.0, 1.0));
+            let color = CIEColor::from_hsl(hue, 1.0, 0.5);
+            assert_near(color.as_hsl(Some(luminance)), (hue, 1.0, 0.5));
+        }
+
+        let white = CIEColor::from_hsv(0.0, 0.0, 1.0);
+        assert_near(white.as_hsv(None), (0.0, 0.0, 1.0));
+        assert_near(white.as_hsl(None), (0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn kelvin_converts_to_mirek() {
+        let mirek = |kelvin| kelvin_to_mirek(kelvin, MIREK_MINIMUM, MIREK_MAXIMUM);
+        assert_eq!(mirek(2000), 500);
+        assert_eq!(mirek(4000), 250);
+        assert_eq!(mirek(6500), 153);
+        // Beyond either end of the range
+        assert_eq!(mirek(1000), 500);
+        assert_eq!(mirek(10_000), 153);
+        assert_eq!(mirek(0), 500);
+    }
+
+    #[test]
+    fn kelvin_commands_clamp_to_the_api_range() {
+        use crate::command::LightCommand;
+
+        for (kelvin, mirek) in [
+            (2000, 500),
+            (4000, 250),
+            (6500, 153),
+            (1500, 500),
+            (9000, 153),
+        ] {
+            assert!(matches!(
+                LightCommand::color_temp_kelvin(kelvin),
+                LightCommand::ColorTemp(m) if m == mirek
+            ));
+        }
+    }
+
+    #[test]
+    fn mirek_schema_bounds_in_kelvin() {
+        let schema = MirekSchema {
+            mirek_minimum: 200,
+            mirek_maximum: 454,
+        };
+        assert_eq!(schema.kelvin_minimum(), 2202);
+        assert_eq!(schema.kelvin_maximum(), 5000);
+        assert_eq!(schema.mirek_from_kelvin(2000), 454);
+        assert_eq!(schema.mirek_from_kelvin(4000), 250);
+        assert_eq!(schema.mirek_from_kelvin(6500), 200);
+    }
+
+    fn light_from(json: serde_json::Value) -> LightData {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn color_light_reads_brightness_and_rgb() {
+        use crate::{api::MockTransport, service::fixtures};
+
+        let bridge = MockTransport::new().bridge();
+        let light = Light::new(&bridge, light_from(fixtures::light("l1", "d1")));
+
+        assert_eq!(light.brightness(), 80.0);
+        assert_eq!(light.color_temp_mirek(), Some(366));
+        let xy = CIEColor::new(0.4573, 0.41);
+        assert_eq!(light.color_rgb(), Some(xy.as_rgb(Some(0.8))));
+        // A warm white
+        let (r, g, b) = light.color_rgb().unwrap();
+        assert!(r >= g && g > b, "{:?}", (r, g, b));
+    }
+
+    #[test]
+    fn color_temperature_light_has_no_rgb() {
+        use crate::{api::MockTransport, service::fixtures};
+
+        let bridge = MockTransport::new().bridge();
+        let light = Light::new(&bridge, light_from(fixtures::ct_light("l2", "d1")));
+
+        assert_eq!(light.brightness(), 80.0);
+        assert_eq!(light.color_temp_mirek(), Some(366));
+        assert_eq!(light.color_rgb(), None);
+    }
+
+    #[test]
+    fn color_temperature_outside_the_ct_spectrum_is_none() {
+        use crate::{api::MockTransport, service::fixtures};
+
+        let bridge = MockTransport::new().bridge();
+        let mut json = fixtures::light("l1", "d1");
+        json["color_temperature"]["mirek"] = serde_json::Value::Null;
+        json["color_temperature"]["mirek_valid"] = false.into();
+        let light = Light::new(&bridge, light_from(json));
+
+        assert_eq!(light.color_temp_mirek(), None);
+        assert!(light.color_rgb().is_some());
+    }
+
+    #[tokio::test]
+    async fn transition_sends_the_duration_with_its_targets() {
+        use crate::service::fixtures;
+        use reqwest::Method;
+        use serde_json::json;
+
+        let (mock, bridge) = fixtures::refreshed_bridge(vec![fixtures::light("l1", "d1")]).await;
+        mock.respond_data(
+            Method::PUT,
+            "/resource/light/l1",
+            fixtures::written("light", "l1"),
+        );
+        let light = bridge.light("l1").unwrap();
+        light
+            .transition()
+            .to_color(CIEColor::new(0.25, 0.5))
+            .to_brightness(60.0)
+            .over(Duration::from_millis(1500))
+            .send()
+            .await
+            .unwrap();
+        light
+            .transition()
+            .to_color(CIEColor::new(0.25, 0.5))
+            .to_color_temp(250)
+            .over(Duration::from_secs(2))
+            .send()
+            .await
+            .unwrap();
+
+        let sent: Vec<_> = mock
+            .requests_to(Method::PUT, "/resource/light/l1")
+            .into_iter()
+            .map(|r| r.body.unwrap())
+            .collect();
+        assert_eq!(
+            sent,
+            [
+                json!({
+                    "color": { "xy": { "x": 0.25, "y": 0.5 } },
+                    "dimming": { "brightness": 60.0 },
+                    "dynamics": { "duration": 1500 },
+                }),
+                json!({
+                    "color_temperature": { "mirek": 250 },
+                    "dynamics": { "duration": 2000 },
+                }),
+            ]
+        );
+    }
+}
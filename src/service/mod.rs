@@ -13,6 +13,9 @@ pub use device::*;
 mod entertainment;
 pub use entertainment::*;
 
+#[cfg(test)]
+pub(crate) mod fixtures;
+
 mod group;
 pub use group::*;
 
@@ -28,6 +31,11 @@ pub use scene::*;
 mod sensor;
 pub use sensor::*;
 
+#[cfg(feature = "streaming")]
+mod stream;
+#[cfg(feature = "streaming")]
+pub use stream::*;
+
 mod thirdparty;
 pub use thirdparty::*;
 
@@ -1,14 +1,23 @@
 use crate::service::{
     BehaviorInstanceData, BehaviorScriptData, BridgeData, ButtonData, ContactData, DeviceData,
     DevicePowerData, DeviceSoftwareUpdateData, EntertainmentConfigurationData, EntertainmentData,
-    GeofenceClientData, GeolocationData, GroupData, HomeData, HomeKitData, LightData,
-    LightLevelData, MatterData, MatterFabricData, MotionData, RelativeRotaryData, SceneData,
-    SmartSceneData, TamperData, TemperatureData, ZGPConnectivityData, ZigbeeConnectivityData,
-    ZigbeeDeviceDiscoveryData, ZoneData,
+    GeofenceClientData, GeolocationData, GroupData, GroupedLightLevelData, GroupedMotionData,
+    HomeData, HomeKitData, LightData, LightLevelData, MatterData, MatterFabricData, MotionData,
+    RelativeRotaryData, SceneData, SmartSceneData, TamperData, TemperatureData,
+    ZGPConnectivityData, ZigbeeConnectivityData, ZigbeeDeviceDiscoveryData, ZoneData,
 };
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize)]
+/// Parses an RFC 3339 timestamp as reported by the bridge, such as
+/// `2023-09-26T16:08:10.183Z`, converting any offset to UTC.
+#[cfg(feature = "chrono")]
+pub(crate) fn parse_timestamp(
+    s: &str,
+) -> Result<chrono::DateTime<chrono::Utc>, chrono::ParseError> {
+    chrono::DateTime::parse_from_rfc3339(s).map(|t| t.with_timezone(&chrono::Utc))
+}
+
+#[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "snake_case", tag = "type")]
 pub enum Resource {
     #[serde(rename = "auth_v1")]
@@ -30,6 +39,8 @@ pub enum Resource {
     Geolocation(GeolocationData),
     #[serde(rename = "grouped_light")]
     Group(GroupData),
+    GroupedLightLevel(GroupedLightLevelData),
+    GroupedMotion(GroupedMotionData),
     #[serde(rename = "homekit")]
     HomeKit(HomeKitData),
     Light(LightData),
@@ -38,6 +49,7 @@ pub enum Resource {
     MatterFabric(MatterFabricData),
     Motion(MotionData),
     PublicImage,
+    Recipe,
     RelativeRotary(RelativeRotaryData),
     Room(ZoneData),
     Scene(SceneData),
@@ -46,6 +58,7 @@ pub enum Resource {
     #[serde(rename = "taurus_7455")]
     Taurus7455,
     Temperature(TemperatureData),
+    #[serde(rename = "zgp_connectivity")]
     ZGPConnectivity(ZGPConnectivityData),
     ZigbeeBridgeConnectivity,
     ZigbeeConnectivity(ZigbeeConnectivityData),
@@ -55,6 +68,23 @@ pub enum Resource {
     Unknown,
 }
 
+impl Resource {
+    /// Whether this crate models resources of this type, rather than only
+    /// recognizing their name.
+    pub(crate) fn is_modeled(&self) -> bool {
+        !matches!(
+            self,
+            Resource::AuthV1
+                | Resource::Geofence
+                | Resource::PublicImage
+                | Resource::Recipe
+                | Resource::Taurus7455
+                | Resource::ZigbeeBridgeConnectivity
+                | Resource::Unknown
+        )
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct ResourceIdentifier {
     /// The unique id of the referenced resource.
@@ -63,6 +93,68 @@ pub struct ResourceIdentifier {
     pub rtype: ResourceType,
 }
 
+/// The result of sending commands to a resource.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SendOutcome {
+    /// Resources the bridge reported as changed.
+    pub rids: Vec<ResourceIdentifier>,
+    /// Errors the bridge reported for the parts of the request it rejected,
+    /// when it applied the rest.
+    pub warnings: Vec<String>,
+}
+
+impl SendOutcome {
+    /// Whether the bridge reported any change. A successful send that was not
+    /// applied had no effect, e.g. turning on a light that was already on.
+    pub fn is_applied(&self) -> bool {
+        !self.rids.is_empty()
+    }
+}
+
+/// The result of deleting a resource.
+#[derive(Clone, Debug, Default)]
+pub struct DeleteOutcome {
+    /// Resources the bridge reported as deleted.
+    pub rids: Vec<ResourceIdentifier>,
+    /// The cached data of the deleted resources, for those which were cached.
+    pub removed: Vec<Resource>,
+}
+
+#[derive(Debug)]
+pub enum ParseResourceIdError {
+    InvalidLength,
+    InvalidByte,
+}
+
+impl ResourceIdentifier {
+    pub fn new(rid: impl Into<String>, rtype: ResourceType) -> Self {
+        ResourceIdentifier {
+            rid: rid.into(),
+            rtype,
+        }
+    }
+
+    /// Create a new identifier, checking that `rid` is a well-formed UUID in
+    /// the hyphenated form used by the bridge, e.g. `"0d116b60-5b57-4a5c-8a9b-1e5d2f5c6a7b"`.
+    pub fn try_new(
+        rid: impl Into<String>,
+        rtype: ResourceType,
+    ) -> Result<Self, ParseResourceIdError> {
+        let rid = rid.into();
+        if rid.len() != 36 {
+            return Err(ParseResourceIdError::InvalidLength);
+        }
+        let well_formed = rid.chars().enumerate().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        });
+        if !well_formed {
+            return Err(ParseResourceIdError::InvalidByte);
+        }
+        Ok(ResourceIdentifier { rid, rtype })
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ResourceType {
@@ -85,6 +177,8 @@ pub enum ResourceType {
     Geolocation,
     #[serde(rename = "grouped_light")]
     Group,
+    GroupedLightLevel,
+    GroupedMotion,
     #[serde(rename = "homekit")]
     HomeKit,
     Light,
@@ -102,9 +196,13 @@ pub enum ResourceType {
     #[serde(rename = "taurus_7455")]
     Taurus7455,
     Temperature,
+    #[serde(rename = "zgp_connectivity")]
     ZGPConnectivity,
     ZigbeeBridgeConnectivity,
     ZigbeeConnectivity,
     ZigbeeDeviceDiscovery,
     Zone,
+    /// A resource type not known to this crate.
+    #[serde(other)]
+    Unknown,
 }
@@ -1,13 +1,18 @@
+#[cfg(feature = "chrono")]
+use crate::service::parse_timestamp;
 use crate::{
     api::HueAPIError,
     command::{merge_commands, SceneCommand, SmartSceneCommand},
     service::{
-        BasicStatus, Bridge, ColorFeatureBasic, EffectType, GradientMode, GradientPoint,
-        GroupDimmingState, OnState, ResourceIdentifier, ResourceType,
+        kelvin_to_mirek, BasicStatus, Bridge, CIEColor, ColorFeatureBasic, DeleteOutcome,
+        EffectType, GradientMode, GradientPoint, GroupDimmingState, OnState, ParseColorError,
+        ResourceIdentifier, ResourceType, MIREK_MAXIMUM, MIREK_MINIMUM,
     },
 };
-use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{collections::HashSet, sync::Arc};
 
 /// A virtual device representing the collective light states of a
 /// [Room](crate::service::Room), [Zone](crate::service::Zone), or
@@ -15,12 +20,15 @@ use std::collections::HashSet;
 #[derive(Debug, Clone)]
 pub struct Scene<'a> {
     bridge: &'a Bridge,
-    data: SceneData,
+    data: Arc<SceneData>,
 }
 
 impl<'a> Scene<'a> {
-    pub fn new(bridge: &'a Bridge, data: SceneData) -> Self {
-        Scene { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<SceneData>>) -> Self {
+        Scene {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &SceneData {
@@ -47,10 +55,34 @@ impl<'a> Scene<'a> {
         self.data.metadata.image.as_ref()
     }
 
+    /// Application specific data stored with the scene.
+    pub fn appdata(&self) -> Option<&str> {
+        self.data.metadata.appdata.as_deref()
+    }
+
+    /// Parse the scene's [appdata](Scene::appdata) as JSON, returning [None]
+    /// if it is absent or not in the expected structure.
+    pub fn appdata_json<T: DeserializeOwned>(&self) -> Option<T> {
+        self.appdata()
+            .and_then(|appdata| serde_json::from_str(appdata).ok())
+    }
+
     pub fn status(&self) -> SceneStatus {
         self.data.status.active
     }
 
+    /// Whether recalling this scene immediately starts its dynamic palette.
+    pub fn auto_dynamic(&self) -> bool {
+        self.data.auto_dynamic
+    }
+
+    pub async fn set_auto_dynamic(
+        &self,
+        auto_dynamic: bool,
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.send(&[SceneCommand::AutoDynamic(auto_dynamic)]).await
+    }
+
     pub fn builder(name: impl Into<String>, group: ResourceIdentifier) -> SceneBuilder {
         SceneBuilder::new(name, group)
     }
@@ -87,6 +119,8 @@ pub struct SceneBuilder {
     speed: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     auto_dynamic: Option<bool>,
+    #[serde(skip)]
+    unique_in_group: bool,
 }
 
 impl SceneBuilder {
@@ -101,6 +135,7 @@ impl SceneBuilder {
             group,
             speed: None,
             auto_dynamic: None,
+            unique_in_group: false,
         }
     }
 
@@ -119,6 +154,12 @@ impl SceneBuilder {
         self
     }
 
+    /// Store structured data in the scene's appdata, serialized as JSON.
+    pub fn appdata_json(mut self, data: &impl Serialize) -> Result<Self, serde_json::Error> {
+        self.metadata.appdata = Some(serde_json::to_string(data)?);
+        Ok(self)
+    }
+
     pub fn palette(mut self, palette: ScenePalette) -> Self {
         self.palette = Some(palette);
         self
@@ -133,10 +174,29 @@ impl SceneBuilder {
         self.auto_dynamic = Some(auto_dynamic);
         self
     }
+
+    /// When `true`, [Bridge::create_scene](crate::service::Bridge::create_scene)
+    /// fails if a cached scene in the same group already has this name.
+    pub fn unique_in_group(mut self, unique: bool) -> Self {
+        self.unique_in_group = unique;
+        self
+    }
+
+    pub(crate) fn name(&self) -> &str {
+        &self.metadata.name
+    }
+
+    pub(crate) fn group(&self) -> &ResourceIdentifier {
+        &self.group
+    }
+
+    pub(crate) fn is_unique_in_group(&self) -> bool {
+        self.unique_in_group
+    }
 }
 
 /// Internal representation of a [Scene].
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SceneData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -166,7 +226,7 @@ impl SceneData {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SceneAction {
     /// The identifier of the light to execute the action on.
     pub target: ResourceIdentifier,
@@ -174,7 +234,7 @@ pub struct SceneAction {
     pub action: LightAction,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct LightAction {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub on: Option<OnState>,
@@ -206,13 +266,83 @@ impl Default for LightAction {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+impl LightAction {
+    pub fn on(mut self, on: bool) -> Self {
+        self.on = Some(OnState { on });
+        self
+    }
+
+    /// Brightness percentage. Value cannot be `0`, writing `0` changes it to
+    /// lowest possible brightness.
+    pub fn dim(mut self, brightness: f32) -> Self {
+        self.dimming = Some(GroupDimmingState { brightness });
+        self
+    }
+
+    pub fn color_xy(mut self, x: f32, y: f32) -> Self {
+        self.color = Some(ColorFeatureBasic::xy(x, y));
+        self.color_temperature = None;
+        self
+    }
+
+    pub fn color_rgb(mut self, rgb: [u8; 3]) -> Self {
+        self.color = Some(ColorFeatureBasic {
+            xy: CIEColor::from_rgb(rgb),
+        });
+        self.color_temperature = None;
+        self
+    }
+
+    /// Sets the color from a hex code such as `#c70709`. An invalid code is
+    /// logged and leaves the action unchanged; use [LightAction::try_color_hex]
+    /// to handle it instead.
+    pub fn color_hex(self, hex: impl Into<String>) -> Self {
+        let hex = hex.into();
+        match CIEColor::from_hex(&hex) {
+            Ok(xy) => self.color_xy(xy.x, xy.y),
+            Err(e) => {
+                log::warn!("ignoring color {hex:?}: {e:?}");
+                self
+            }
+        }
+    }
+
+    /// Like [LightAction::color_hex], but fails if `hex` is not a valid color.
+    pub fn try_color_hex(self, hex: impl Into<String>) -> Result<Self, ParseColorError> {
+        let xy = CIEColor::from_hex(hex)?;
+        Ok(self.color_xy(xy.x, xy.y))
+    }
+
+    /// Color temperature in absolute mirek \[`153`, `500`\].
+    pub fn color_temp(mut self, mirek: u16) -> Self {
+        self.color_temperature = Some(SceneColorTempState {
+            mirek: Some(mirek.clamp(MIREK_MINIMUM, MIREK_MAXIMUM)),
+        });
+        self.color = None;
+        self
+    }
+
+    /// Color temperature in Kelvin, converted to mirek and clamped to
+    /// \[`153`, `500`\] mirek, or about \[`2000`, `6536`\] K.
+    pub fn color_temp_kelvin(self, kelvin: u32) -> Self {
+        self.color_temp(kelvin_to_mirek(kelvin, MIREK_MINIMUM, MIREK_MAXIMUM))
+    }
+
+    pub fn effect(mut self, effect: EffectType) -> Self {
+        self.effects = Some(SceneEffectState {
+            effect: Some(effect),
+        });
+        self
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SceneColorTempState {
     /// Color temperature in mirek or `None` when the light color is not in the ct spectrum.
     pub mirek: Option<u16>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SceneGradientState {
     /// Collection of gradients points.
     /// For control of the gradient points through a PUT a minimum of 2 points need to be provided.
@@ -222,18 +352,18 @@ pub struct SceneGradientState {
     pub mode: GradientMode,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SceneEffectState {
     pub effect: Option<EffectType>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SceneDynamics {
     /// Duration of a light transition or timed effects in ms.
     pub duration: Option<usize>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct SceneMetadata {
     /// Human readable name of a resource.
     pub name: String,
@@ -246,7 +376,7 @@ pub struct SceneMetadata {
     pub appdata: Option<String>,
 }
 
-#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 pub struct ScenePalette {
     pub color: Vec<ScenePaletteColor>,
     pub dimming: Vec<GroupDimmingState>,
@@ -254,7 +384,40 @@ pub struct ScenePalette {
     pub effects: Vec<SceneEffectState>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+impl ScenePalette {
+    /// Adds a colored point with brightness percentage `bri`.
+    pub fn add_color(mut self, x: f32, y: f32, bri: f32) -> Self {
+        self.color.push(ScenePaletteColor::xyb(x, y, bri));
+        self
+    }
+
+    /// Adds a color temperature point in mirek with brightness percentage `bri`,
+    /// for white ambiance dynamic scenes.
+    pub fn add_color_temp(mut self, mirek: u16, bri: f32) -> Self {
+        self.color_temperature.push(ScenePaletteColorTempState {
+            color_temperature: SceneColorTempState {
+                mirek: Some(mirek.clamp(MIREK_MINIMUM, MIREK_MAXIMUM)),
+            },
+            dimming: GroupDimmingState { brightness: bri },
+        });
+        self
+    }
+
+    /// Adds a brightness-only point, for dimmable lights without color support.
+    pub fn add_dimming(mut self, bri: f32) -> Self {
+        self.dimming.push(GroupDimmingState { brightness: bri });
+        self
+    }
+
+    pub fn add_effect(mut self, effect: EffectType) -> Self {
+        self.effects.push(SceneEffectState {
+            effect: Some(effect),
+        });
+        self
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ScenePaletteColor {
     pub color: ColorFeatureBasic,
     pub dimming: GroupDimmingState,
@@ -269,15 +432,25 @@ impl ScenePaletteColor {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ScenePaletteColorTempState {
     pub color_temperature: SceneColorTempState,
     pub dimming: GroupDimmingState,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SceneStatusState {
     pub active: SceneStatus,
+    /// Last time the scene was recalled, if reported by the bridge.
+    pub last_recall: Option<String>,
+}
+
+#[cfg(feature = "chrono")]
+impl SceneStatusState {
+    /// The `last_recall` timestamp parsed as UTC, or [None] if not reported.
+    pub fn last_recall_at(&self) -> Option<Result<DateTime<Utc>, chrono::ParseError>> {
+        self.last_recall.as_deref().map(parse_timestamp)
+    }
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
@@ -293,12 +466,15 @@ pub enum SceneStatus {
 #[derive(Debug)]
 pub struct SmartScene<'a> {
     bridge: &'a Bridge,
-    data: SmartSceneData,
+    data: Arc<SmartSceneData>,
 }
 
 impl<'a> SmartScene<'a> {
-    pub fn new(bridge: &'a Bridge, data: SmartSceneData) -> Self {
-        SmartScene { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<SmartSceneData>>) -> Self {
+        SmartScene {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &SmartSceneData {
@@ -341,17 +517,24 @@ impl<'a> SmartScene<'a> {
         &self,
         commands: &[SmartSceneCommand],
     ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        for command in commands {
+            if let SmartSceneCommand::Schedule(schedules) = command {
+                schedules.iter().try_for_each(Schedule::validate)?;
+            }
+        }
         let payload = merge_commands(commands);
         self.bridge.api.put_smart_scene(self.id(), &payload).await
     }
 
-    // pub async fn delete(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
-    //     self.api.delete_scene(self.id()).await
-    // }
+    /// Deletes the smart scene from the bridge, and from the [Bridge] cache,
+    /// as [Bridge::delete_smart_scene] does.
+    pub async fn delete(&self) -> Result<DeleteOutcome, HueAPIError> {
+        self.bridge.delete_smart_scene(self.id()).await
+    }
 }
 
 /// Internal representation of a [SmartScene].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SmartSceneData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -380,7 +563,7 @@ impl SmartSceneData {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Schedule {
     pub timeslots: Vec<SmartSceneTimeslot>,
     pub recurrence: HashSet<Weekday>,
@@ -443,6 +626,27 @@ impl Schedule {
         self
     }
 
+    /// Checks that the schedule recurs on at least one day, has at least one
+    /// timeslot, and that every timeslot time is within a day.
+    pub fn validate(&self) -> Result<(), HueAPIError> {
+        if self.recurrence.is_empty() {
+            return Err(HueAPIError::InvalidInput(
+                "schedule recurs on no days".into(),
+            ));
+        }
+        if self.timeslots.is_empty() {
+            return Err(HueAPIError::InvalidInput(
+                "schedule has no timeslots".into(),
+            ));
+        }
+        for slot in &self.timeslots {
+            if let TimeslotStart::Time { time } = &slot.start_time {
+                time.validate()?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn build(self) -> SmartSceneCommand {
         SmartSceneCommand::Schedule(vec![Schedule {
             timeslots: self.timeslots,
@@ -451,20 +655,20 @@ impl Schedule {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SmartSceneTimeslot {
     pub start_time: TimeslotStart,
     pub target: ResourceIdentifier,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case", tag = "kind")]
 pub enum TimeslotStart {
     Sunset,
     Time { time: TimeslotTime },
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct TimeslotTime {
     /// `0` to `23`
     hour: u8,
@@ -474,7 +678,47 @@ pub struct TimeslotTime {
     second: u8,
 }
 
+impl TimeslotTime {
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    fn validate(&self) -> Result<(), HueAPIError> {
+        if self.hour > 23 || self.minute > 59 || self.second > 59 {
+            return Err(HueAPIError::InvalidInput(format!(
+                "invalid timeslot time {:02}:{:02}:{:02}",
+                self.hour, self.minute, self.second
+            )));
+        }
+        Ok(())
+    }
+
+    /// Parses a bridge-formatted `HH:MM:SS` time, as in [SunToday](crate::service::SunToday).
+    pub(crate) fn parse(hms: &str) -> Option<TimeslotTime> {
+        let mut parts = hms.split(':').map(|p| p.parse::<u8>().ok());
+        let time = TimeslotTime {
+            hour: parts.next()??,
+            minute: parts.next()??,
+            second: parts.next().unwrap_or(Some(0))?,
+        };
+        if parts.next().is_some() || time.hour > 23 || time.minute > 59 || time.second > 59 {
+            return None;
+        }
+        Some(time)
+    }
+}
+
 impl TimeslotStart {
+    /// A start at `[hour, minute, second]`. The time is not checked until the
+    /// schedule is sent, see [TimeslotStart::try_time].
     pub fn time(hms: &[u8; 3]) -> TimeslotStart {
         TimeslotStart::Time {
             time: TimeslotTime {
@@ -484,9 +728,19 @@ impl TimeslotStart {
             },
         }
     }
+
+    /// Like [TimeslotStart::time], but fails with [HueAPIError::InvalidInput]
+    /// if the hour is above `23`, or the minute or second above `59`.
+    pub fn try_time(hms: &[u8; 3]) -> Result<TimeslotStart, HueAPIError> {
+        let start = TimeslotStart::time(hms);
+        if let TimeslotStart::Time { time } = &start {
+            time.validate()?;
+        }
+        Ok(start)
+    }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ActiveTimeslot {
     pub timeslot_id: usize,
     pub weekday: Weekday,
@@ -539,4 +793,160 @@ impl SmartSceneBuilder {
         self.week_timeslots.push(s);
         self
     }
+
+    pub(crate) fn group(&self) -> &ResourceIdentifier {
+        &self.group
+    }
+
+    pub(crate) fn schedules(&self) -> &[Schedule] {
+        &self.week_timeslots
+    }
+
+    pub(crate) fn targets(&self) -> impl Iterator<Item = &ResourceIdentifier> {
+        self.week_timeslots
+            .iter()
+            .flat_map(|s| s.timeslots.iter().map(|t| &t.target))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::{fixtures, Resource};
+    use reqwest::Method;
+
+    #[test]
+    fn color_hex_chains_with_other_builders() {
+        let action = LightAction::default()
+            .color_temp(300)
+            .color_hex("#c70709")
+            .dim(40.0);
+
+        let expected = CIEColor::from_hex("#c70709").unwrap();
+        let color = action.color.unwrap();
+        assert_eq!((color.xy.x, color.xy.y), (expected.x, expected.y));
+        assert!(action.color_temperature.is_none());
+        assert_eq!(action.dimming.unwrap().brightness, 40.0);
+    }
+
+    #[test]
+    fn color_hex_ignores_invalid_codes() {
+        let action = LightAction::default().color_temp(300).color_hex("#nothex");
+
+        assert!(action.color.is_none());
+        assert_eq!(action.color_temperature.unwrap().mirek, Some(300));
+    }
+
+    #[test]
+    fn try_color_hex_reports_invalid_codes() {
+        assert!(matches!(
+            LightAction::default().try_color_hex("#1234567"),
+            Err(ParseColorError::InvalidLength)
+        ));
+        assert!(matches!(
+            LightAction::default().try_color_hex("xyz"),
+            Err(ParseColorError::InvalidByte)
+        ));
+        assert!(LightAction::default()
+            .try_color_hex("#fff")
+            .is_ok_and(|a| a.color.is_some()));
+    }
+
+    #[tokio::test]
+    async fn smart_scene_delete_evicts_it_from_the_cache() {
+        let (mock, bridge) = fixtures::refreshed_bridge(vec![
+            fixtures::scene("s1", fixtures::rid("room", "r1"), &[], false),
+            fixtures::smart_scene("ss1", fixtures::rid("room", "r1"), "s1"),
+            fixtures::smart_scene("ss2", fixtures::rid("room", "r1"), "s1"),
+        ])
+        .await;
+        mock.respond_data(
+            Method::DELETE,
+            "/resource/smart_scene/ss1",
+            fixtures::written("smart_scene", "ss1"),
+        );
+        let smart_scene = bridge.smart_scene("ss1").unwrap();
+
+        let outcome = smart_scene.delete().await.unwrap();
+
+        assert_eq!(outcome.rids, [smart_scene.rid()]);
+        assert!(matches!(
+            &outcome.removed[..],
+            [Resource::SmartScene(data)] if data.id == "ss1"
+        ));
+        let remaining: Vec<_> = bridge
+            .smart_scenes()
+            .iter()
+            .map(|s| s.id().to_owned())
+            .collect();
+        assert_eq!(remaining, ["ss2"]);
+        assert!(bridge.smart_scene("ss1").is_none());
+    }
+
+    fn morning() -> ResourceIdentifier {
+        ResourceIdentifier::new("s1", ResourceType::Scene)
+    }
+
+    #[test]
+    fn timeslot_times_are_bounded() {
+        for hms in [[0, 0, 0], [23, 59, 59], [7, 30, 0]] {
+            assert_eq!(
+                TimeslotStart::try_time(&hms).unwrap(),
+                TimeslotStart::time(&hms)
+            );
+        }
+        for hms in [[24, 0, 0], [0, 60, 0], [0, 0, 60], [25, 70, 99]] {
+            assert!(matches!(
+                TimeslotStart::try_time(&hms),
+                Err(HueAPIError::InvalidInput(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn schedules_need_days_and_timeslots() {
+        let at_seven = TimeslotStart::time(&[7, 0, 0]);
+        assert!(Schedule::new()
+            .at(at_seven.clone(), morning())
+            .validate()
+            .is_err());
+        assert!(Schedule::new().monday().validate().is_err());
+        assert!(Schedule::new()
+            .monday()
+            .at(TimeslotStart::time(&[24, 0, 0]), morning())
+            .validate()
+            .is_err());
+        assert!(Schedule::new()
+            .monday()
+            .at(at_seven, morning())
+            .at(TimeslotStart::Sunset, morning())
+            .validate()
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn empty_schedules_are_not_sent() {
+        let (mock, bridge) = fixtures::refreshed_bridge(vec![
+            fixtures::scene("s1", fixtures::rid("room", "r1"), &[], false),
+            fixtures::smart_scene("ss1", fixtures::rid("room", "r1"), "s1"),
+        ])
+        .await;
+        let group = ResourceIdentifier::new("r1", ResourceType::Room);
+
+        let res = bridge
+            .create_smart_scene(SmartSceneBuilder::new("Empty", group.clone()))
+            .await;
+        assert!(matches!(res, Err(HueAPIError::InvalidInput(_))));
+        let builder = SmartSceneBuilder::new("Empty", group).schedule(Schedule::new());
+        let res = bridge.create_smart_scene(builder).await;
+        assert!(matches!(res, Err(HueAPIError::InvalidInput(_))));
+        let res = bridge
+            .smart_scene("ss1")
+            .unwrap()
+            .send(&[Schedule::new().monday().build()])
+            .await;
+        assert!(matches!(res, Err(HueAPIError::InvalidInput(_))));
+
+        assert_eq!(mock.requests().len(), 1);
+    }
 }
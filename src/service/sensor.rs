@@ -1,22 +1,30 @@
+#[cfg(feature = "chrono")]
+use crate::service::parse_timestamp;
 use crate::{
     api::HueAPIError,
     command::{
         merge_commands, BasicCommand, GeofenceClientCommand, GeolocationCommand, MotionCommand,
     },
-    service::{Bridge, ResourceIdentifier, ResourceType, SetStatus},
+    service::{Bridge, ResourceIdentifier, ResourceType, SetStatus, TimeslotTime},
 };
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, NaiveTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 /// A physical contact sensor device.
 #[derive(Debug)]
 pub struct Contact<'a> {
     bridge: &'a Bridge,
-    data: ContactData,
+    data: Arc<ContactData>,
 }
 
 impl<'a> Contact<'a> {
-    pub fn new(bridge: &'a Bridge, data: ContactData) -> Self {
-        Contact { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<ContactData>>) -> Self {
+        Contact {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &ContactData {
@@ -31,6 +39,24 @@ impl<'a> Contact<'a> {
         self.data.rid()
     }
 
+    pub fn is_enabled(&self) -> bool {
+        self.data.enabled
+    }
+
+    /// The last reported contact state, or [None] if the sensor has not yet
+    /// reported.
+    pub fn state(&self) -> Option<ContactStatus> {
+        self.data.contact_report.as_ref().map(|r| r.state)
+    }
+
+    pub async fn enable(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.send(&[BasicCommand::Enabled(true)]).await
+    }
+
+    pub async fn disable(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.send(&[BasicCommand::Enabled(false)]).await
+    }
+
     pub async fn send(
         &self,
         commands: &[BasicCommand],
@@ -41,7 +67,7 @@ impl<'a> Contact<'a> {
 }
 
 /// Internal representation of a [Contact].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ContactData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -63,14 +89,22 @@ impl ContactData {
     }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ContactReport {
     /// Last time the value of this property was updated.
     pub changed: String,
     pub state: ContactStatus,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[cfg(feature = "chrono")]
+impl ContactReport {
+    /// The `changed` timestamp, parsed as UTC.
+    pub fn changed_at(&self) -> Result<DateTime<Utc>, chrono::ParseError> {
+        parse_timestamp(&self.changed)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ContactStatus {
     Contact,
@@ -81,12 +115,15 @@ pub enum ContactStatus {
 #[derive(Debug)]
 pub struct Motion<'a> {
     bridge: &'a Bridge,
-    data: MotionData,
+    data: Arc<MotionData>,
 }
 
 impl<'a> Motion<'a> {
-    pub fn new(bridge: &'a Bridge, data: MotionData) -> Self {
-        Motion { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<MotionData>>) -> Self {
+        Motion {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &MotionData {
@@ -104,6 +141,26 @@ impl<'a> Motion<'a> {
         }
     }
 
+    pub fn is_enabled(&self) -> bool {
+        self.data.enabled
+    }
+
+    /// Whether motion is currently detected, or [None] if the sensor has no
+    /// valid motion report.
+    pub fn is_motion_detected(&self) -> Option<bool> {
+        self.data.motion.motion_report.as_ref().map(|r| r.motion)
+    }
+
+    /// When the motion report last changed, or [None] if the sensor has no
+    /// valid motion report.
+    pub fn last_changed(&self) -> Option<&str> {
+        self.data
+            .motion
+            .motion_report
+            .as_ref()
+            .map(|r| r.changed.as_str())
+    }
+
     pub async fn send(
         &self,
         commands: &[MotionCommand],
@@ -116,13 +173,16 @@ impl<'a> Motion<'a> {
 #[derive(Debug)]
 pub struct CameraMotion<'a> {
     bridge: &'a Bridge,
-    data: MotionData,
+    data: Arc<MotionData>,
 }
 
 /// A camera device with motion detection capability.
 impl<'a> CameraMotion<'a> {
-    pub fn new(bridge: &'a Bridge, data: MotionData) -> Self {
-        CameraMotion { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<MotionData>>) -> Self {
+        CameraMotion {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &MotionData {
@@ -140,6 +200,26 @@ impl<'a> CameraMotion<'a> {
         }
     }
 
+    pub fn is_enabled(&self) -> bool {
+        self.data.enabled
+    }
+
+    /// Whether motion is currently detected, or [None] if the sensor has no
+    /// valid motion report.
+    pub fn is_motion_detected(&self) -> Option<bool> {
+        self.data.motion.motion_report.as_ref().map(|r| r.motion)
+    }
+
+    /// When the motion report last changed, or [None] if the sensor has no
+    /// valid motion report.
+    pub fn last_changed(&self) -> Option<&str> {
+        self.data
+            .motion
+            .motion_report
+            .as_ref()
+            .map(|r| r.changed.as_str())
+    }
+
     pub async fn send(
         &self,
         commands: &[MotionCommand],
@@ -150,7 +230,7 @@ impl<'a> CameraMotion<'a> {
 }
 
 /// Internal representation of a [Motion] or [CameraMotion].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct MotionData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -164,7 +244,7 @@ pub struct MotionData {
     pub sensitivity: Option<Sensitivity>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct MotionState {
     /// Motion is valid when `motion_report` property is present, invalid when absent.
     #[deprecated]
@@ -172,7 +252,7 @@ pub struct MotionState {
     pub motion_report: Option<MotionReport>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct MotionReport {
     /// Last time the value of this property is changed.
     pub changed: String,
@@ -180,7 +260,15 @@ pub struct MotionReport {
     pub motion: bool,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[cfg(feature = "chrono")]
+impl MotionReport {
+    /// The `changed` timestamp, parsed as UTC.
+    pub fn changed_at(&self) -> Result<DateTime<Utc>, chrono::ParseError> {
+        parse_timestamp(&self.changed)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Sensitivity {
     pub status: SetStatus,
     /// Sensitivity of the sensor. Value in the range `0` to `sensitivity_max`.
@@ -193,12 +281,15 @@ pub struct Sensitivity {
 #[derive(Debug)]
 pub struct Temperature<'a> {
     bridge: &'a Bridge,
-    data: TemperatureData,
+    data: Arc<TemperatureData>,
 }
 
 impl<'a> Temperature<'a> {
-    pub fn new(bridge: &'a Bridge, data: TemperatureData) -> Self {
-        Temperature { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<TemperatureData>>) -> Self {
+        Temperature {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &TemperatureData {
@@ -223,7 +314,7 @@ impl<'a> Temperature<'a> {
 }
 
 /// Internal representation of a [Temperature].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct TemperatureData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -245,7 +336,7 @@ impl TemperatureData {
     }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct TemperatureState {
     #[deprecated]
     pub temperature: f32,
@@ -254,23 +345,34 @@ pub struct TemperatureState {
     pub temperature_report: Option<TemperatureReport>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct TemperatureReport {
     /// Last time the value of this property is changed.
     pub changed: String,
     pub temperature: f32,
 }
 
+#[cfg(feature = "chrono")]
+impl TemperatureReport {
+    /// The `changed` timestamp, parsed as UTC.
+    pub fn changed_at(&self) -> Result<DateTime<Utc>, chrono::ParseError> {
+        parse_timestamp(&self.changed)
+    }
+}
+
 /// A light level detection device.
 #[derive(Debug)]
 pub struct LightLevel<'a> {
     bridge: &'a Bridge,
-    data: LightLevelData,
+    data: Arc<LightLevelData>,
 }
 
 impl<'a> LightLevel<'a> {
-    pub fn new(bridge: &'a Bridge, data: LightLevelData) -> Self {
-        LightLevel { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<LightLevelData>>) -> Self {
+        LightLevel {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &LightLevelData {
@@ -295,7 +397,7 @@ impl<'a> LightLevel<'a> {
 }
 
 /// Internal representation of a [LightLevel].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct LightLevelData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -317,7 +419,7 @@ impl LightLevelData {
     }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct LightLevelState {
     #[deprecated]
     pub light_level: usize,
@@ -326,7 +428,7 @@ pub struct LightLevelState {
     pub light_level_report: Option<LightLevelReport>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct LightLevelReport {
     /// Last time the value of this property is changed.
     pub changed: String,
@@ -337,16 +439,197 @@ pub struct LightLevelReport {
     pub light_level: usize,
 }
 
+#[cfg(feature = "chrono")]
+impl LightLevelReport {
+    /// The `changed` timestamp, parsed as UTC.
+    pub fn changed_at(&self) -> Result<DateTime<Utc>, chrono::ParseError> {
+        parse_timestamp(&self.changed)
+    }
+}
+
+/// Motion detection aggregated over the sensors in a [Room](crate::service::Room)
+/// or [Zone](crate::service::Zone).
+#[derive(Debug)]
+pub struct GroupedMotion<'a> {
+    bridge: &'a Bridge,
+    data: Arc<GroupedMotionData>,
+}
+
+impl<'a> GroupedMotion<'a> {
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<GroupedMotionData>>) -> Self {
+        GroupedMotion {
+            bridge,
+            data: data.into(),
+        }
+    }
+
+    pub fn data(&self) -> &GroupedMotionData {
+        &self.data
+    }
+
+    pub fn id(&self) -> &str {
+        &self.data.id
+    }
+
+    pub fn rid(&self) -> ResourceIdentifier {
+        self.data.rid()
+    }
+
+    /// The room or zone whose sensors are aggregated.
+    pub fn owner(&self) -> &ResourceIdentifier {
+        &self.data.owner
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.data.enabled
+    }
+
+    /// Whether any sensor in the group currently detects motion, or [None] if
+    /// there is no valid motion report.
+    pub fn is_motion_detected(&self) -> Option<bool> {
+        self.data.motion.motion_report.as_ref().map(|r| r.motion)
+    }
+
+    pub async fn send(
+        &self,
+        commands: &[BasicCommand],
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        let payload = merge_commands(commands);
+        self.bridge
+            .api
+            .put_grouped_motion(self.id(), &payload)
+            .await
+    }
+}
+
+/// Internal representation of a [GroupedMotion].
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct GroupedMotionData {
+    /// Unique identifier representing a specific resource instance.
+    pub id: String,
+    /// Clip v1 resource identifier.
+    pub id_v1: Option<String>,
+    /// Owner of the service, in case the owner service is deleted, the service also gets deleted.
+    pub owner: ResourceIdentifier,
+    /// Whether sensor is activated or not.
+    pub enabled: bool,
+    pub motion: GroupedMotionState,
+}
+
+impl GroupedMotionData {
+    pub fn rid(&self) -> ResourceIdentifier {
+        ResourceIdentifier {
+            rid: self.id.to_owned(),
+            rtype: ResourceType::GroupedMotion,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct GroupedMotionState {
+    pub motion_report: Option<MotionReport>,
+}
+
+/// Light level aggregated over the sensors in a [Room](crate::service::Room)
+/// or [Zone](crate::service::Zone).
+#[derive(Debug)]
+pub struct GroupedLightLevel<'a> {
+    bridge: &'a Bridge,
+    data: Arc<GroupedLightLevelData>,
+}
+
+impl<'a> GroupedLightLevel<'a> {
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<GroupedLightLevelData>>) -> Self {
+        GroupedLightLevel {
+            bridge,
+            data: data.into(),
+        }
+    }
+
+    pub fn data(&self) -> &GroupedLightLevelData {
+        &self.data
+    }
+
+    pub fn id(&self) -> &str {
+        &self.data.id
+    }
+
+    pub fn rid(&self) -> ResourceIdentifier {
+        self.data.rid()
+    }
+
+    /// The room or zone whose sensors are aggregated.
+    pub fn owner(&self) -> &ResourceIdentifier {
+        &self.data.owner
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.data.enabled
+    }
+
+    /// The aggregated light level, as described in [LightLevelReport::light_level],
+    /// or [None] if there is no valid report.
+    pub fn light_level(&self) -> Option<usize> {
+        self.data
+            .light
+            .light_level_report
+            .as_ref()
+            .map(|r| r.light_level)
+    }
+
+    pub async fn send(
+        &self,
+        commands: &[BasicCommand],
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        let payload = merge_commands(commands);
+        self.bridge
+            .api
+            .put_grouped_light_level(self.id(), &payload)
+            .await
+    }
+}
+
+/// Internal representation of a [GroupedLightLevel].
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct GroupedLightLevelData {
+    /// Unique identifier representing a specific resource instance.
+    pub id: String,
+    /// Clip v1 resource identifier.
+    pub id_v1: Option<String>,
+    /// Owner of the service, in case the owner service is deleted, the service also gets deleted.
+    pub owner: ResourceIdentifier,
+    /// Whether sensor is activated or not.
+    pub enabled: bool,
+    pub light: GroupedLightLevelState,
+}
+
+impl GroupedLightLevelData {
+    pub fn rid(&self) -> ResourceIdentifier {
+        ResourceIdentifier {
+            rid: self.id.to_owned(),
+            rtype: ResourceType::GroupedLightLevel,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct GroupedLightLevelState {
+    pub light_level_report: Option<LightLevelReport>,
+}
+
 /// A virtual device representing the location of the Hue Bridge.
 #[derive(Debug)]
 pub struct Geolocation<'a> {
     bridge: &'a Bridge,
-    data: GeolocationData,
+    data: Arc<GeolocationData>,
 }
 
 impl<'a> Geolocation<'a> {
-    pub fn new(bridge: &'a Bridge, data: GeolocationData) -> Self {
-        Geolocation { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<GeolocationData>>) -> Self {
+        Geolocation {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &GeolocationData {
@@ -361,6 +644,36 @@ impl<'a> Geolocation<'a> {
         self.data.rid()
     }
 
+    /// Whether the bridge location has been set. Sunset timeslots in a
+    /// [SmartScene](crate::service::SmartScene) only fire once it is.
+    pub fn is_configured(&self) -> bool {
+        self.data.is_configured
+    }
+
+    /// Today's local sunset time, or [None] if the geolocation is not configured.
+    pub fn sunset(&self) -> Option<TimeslotTime> {
+        self.data
+            .sun_today
+            .as_ref()
+            .and_then(|sun| TimeslotTime::parse(&sun.sunset_time))
+    }
+
+    /// Today's local sunset time as a [NaiveTime], or [None] if the
+    /// geolocation is not configured.
+    #[cfg(feature = "chrono")]
+    pub fn sunset_time(&self) -> Option<NaiveTime> {
+        let sunset = self.sunset()?;
+        NaiveTime::from_hms_opt(
+            sunset.hour().into(),
+            sunset.minute().into(),
+            sunset.second().into(),
+        )
+    }
+
+    pub fn day_type(&self) -> Option<&DayType> {
+        self.data.sun_today.as_ref().map(|sun| &sun.day_type)
+    }
+
     pub async fn send(
         &self,
         commands: &[GeolocationCommand],
@@ -371,7 +684,7 @@ impl<'a> Geolocation<'a> {
 }
 
 /// Internal representation of the device [Geolocation].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct GeolocationData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -392,13 +705,13 @@ impl GeolocationData {
     }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct SunToday {
     pub sunset_time: String,
     pub day_type: DayType,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum DayType {
     NormalDay,
@@ -410,12 +723,15 @@ pub enum DayType {
 #[derive(Debug)]
 pub struct GeofenceClient<'a> {
     bridge: &'a Bridge,
-    data: GeofenceClientData,
+    data: Arc<GeofenceClientData>,
 }
 
 impl<'a> GeofenceClient<'a> {
-    pub fn new(bridge: &'a Bridge, data: GeofenceClientData) -> Self {
-        GeofenceClient { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<GeofenceClientData>>) -> Self {
+        GeofenceClient {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &GeofenceClientData {
@@ -447,7 +763,7 @@ impl<'a> GeofenceClient<'a> {
 }
 
 /// Internal representation of a [GeofenceClient].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct GeofenceClientData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -488,12 +804,12 @@ impl GeofenceClientBuilder {
 /// A tamper detection device.
 #[derive(Debug)]
 pub struct Tamper {
-    data: TamperData,
+    data: Arc<TamperData>,
 }
 
 impl Tamper {
-    pub fn new(data: TamperData) -> Self {
-        Tamper { data }
+    pub fn new(data: impl Into<Arc<TamperData>>) -> Self {
+        Tamper { data: data.into() }
     }
 
     pub fn data(&self) -> &TamperData {
@@ -510,7 +826,7 @@ impl Tamper {
 }
 
 /// Internal representation of a [Tamper].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct TamperData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -530,7 +846,7 @@ impl TamperData {
     }
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct TamperReport {
     /// Last time the value of this property is changed.
     pub changed: String,
@@ -540,9 +856,172 @@ pub struct TamperReport {
     pub state: TamperStatus,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[cfg(feature = "chrono")]
+impl TamperReport {
+    /// The `changed` timestamp, parsed as UTC.
+    pub fn changed_at(&self) -> Result<DateTime<Utc>, chrono::ParseError> {
+        parse_timestamp(&self.changed)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum TamperStatus {
     Tampered,
     NotTampered,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::MockTransport;
+
+    fn geolocation(sun_today: Option<serde_json::Value>) -> GeolocationData {
+        serde_json::from_value(serde_json::json!({
+            "id": "geo1",
+            "is_configured": sun_today.is_some(),
+            "sun_today": sun_today,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn sunset_parses_sun_today() {
+        let bridge = MockTransport::new().bridge();
+        let data = geolocation(Some(serde_json::json!({
+            "sunset_time": "19:42:07",
+            "day_type": "normal_day",
+        })));
+        let geo = Geolocation::new(&bridge, data);
+
+        assert!(geo.is_configured());
+        let sunset = geo.sunset().unwrap();
+        assert_eq!(
+            (sunset.hour(), sunset.minute(), sunset.second()),
+            (19, 42, 7)
+        );
+        #[cfg(feature = "chrono")]
+        assert_eq!(geo.sunset_time(), NaiveTime::from_hms_opt(19, 42, 7));
+    }
+
+    #[test]
+    fn sunset_is_none_until_configured() {
+        let bridge = MockTransport::new().bridge();
+        let geo = Geolocation::new(&bridge, geolocation(None));
+
+        assert!(!geo.is_configured());
+        assert!(geo.sunset().is_none());
+        #[cfg(feature = "chrono")]
+        assert!(geo.sunset_time().is_none());
+    }
+
+    fn motion(motion_report: Option<serde_json::Value>) -> MotionData {
+        let mut motion = serde_json::json!({ "motion_valid": motion_report.is_some() });
+        if let Some(report) = motion_report {
+            motion["motion_report"] = report;
+        }
+        serde_json::from_value(serde_json::json!({
+            "id": "mo1",
+            "owner": { "rid": "d1", "rtype": "device" },
+            "enabled": true,
+            "motion": motion,
+        }))
+        .unwrap()
+    }
+
+    fn report(detected: bool) -> serde_json::Value {
+        serde_json::json!({ "changed": "2024-03-01T08:15:30.512Z", "motion": detected })
+    }
+
+    #[test]
+    fn motion_reads_its_report() {
+        let bridge = MockTransport::new().bridge();
+        let sensor = Motion::new(&bridge, motion(Some(report(true))));
+
+        assert!(sensor.is_enabled());
+        assert_eq!(sensor.is_motion_detected(), Some(true));
+        assert_eq!(sensor.last_changed(), Some("2024-03-01T08:15:30.512Z"));
+
+        let sensor = Motion::new(&bridge, motion(Some(report(false))));
+        assert_eq!(sensor.is_motion_detected(), Some(false));
+    }
+
+    #[test]
+    fn motion_without_a_report_is_unknown() {
+        let bridge = MockTransport::new().bridge();
+        let sensor = Motion::new(&bridge, motion(None));
+
+        assert!(sensor.is_enabled());
+        assert_eq!(sensor.is_motion_detected(), None);
+        assert_eq!(sensor.last_changed(), None);
+    }
+
+    #[test]
+    fn camera_motion_reads_its_report() {
+        let bridge = MockTransport::new().bridge();
+        let camera = CameraMotion::new(&bridge, motion(Some(report(true))));
+        assert!(camera.is_enabled());
+        assert_eq!(camera.is_motion_detected(), Some(true));
+        assert_eq!(camera.last_changed(), Some("2024-03-01T08:15:30.512Z"));
+
+        let camera = CameraMotion::new(&bridge, motion(None));
+        assert_eq!(camera.is_motion_detected(), None);
+        assert_eq!(camera.last_changed(), None);
+    }
+
+    fn grouped_motion() -> serde_json::Value {
+        serde_json::json!({
+            "type": "grouped_motion",
+            "id": "gm1",
+            "id_v1": "/sensors/42",
+            "owner": { "rid": "r1", "rtype": "room" },
+            "enabled": true,
+            "motion": {
+                "motion_report": { "changed": "2024-03-01T08:15:30.512Z", "motion": true },
+            },
+        })
+    }
+
+    fn grouped_light_level() -> serde_json::Value {
+        serde_json::json!({
+            "type": "grouped_light_level",
+            "id": "gll1",
+            "owner": { "rid": "z1", "rtype": "zone" },
+            "enabled": false,
+            "light": {
+                "light_level_report": { "changed": "2024-03-01T08:15:30.512Z", "light_level": 18_210 },
+            },
+        })
+    }
+
+    #[test]
+    fn grouped_sensors_deserialize() {
+        let motion: GroupedMotionData = serde_json::from_value(grouped_motion()).unwrap();
+        assert_eq!(motion.owner.rtype, ResourceType::Room);
+        assert!(motion.enabled);
+        assert!(motion.motion.motion_report.unwrap().motion);
+
+        let light: GroupedLightLevelData = serde_json::from_value(grouped_light_level()).unwrap();
+        assert_eq!(light.owner.rtype, ResourceType::Zone);
+        assert!(!light.enabled);
+        assert_eq!(light.light.light_level_report.unwrap().light_level, 18_210);
+    }
+
+    #[tokio::test]
+    async fn grouped_sensors_are_cached() {
+        let (_, bridge) = crate::service::fixtures::refreshed_bridge(vec![
+            grouped_motion(),
+            grouped_light_level(),
+        ])
+        .await;
+
+        assert_eq!(bridge.n_grouped_motions(), 1);
+        let motion = bridge.grouped_motion("gm1").unwrap();
+        assert_eq!(motion.owner().rid, "r1");
+        assert_eq!(motion.is_motion_detected(), Some(true));
+
+        let light = bridge.grouped_light_levels();
+        assert_eq!(light.len(), 1);
+        assert_eq!(light[0].light_level(), Some(18_210));
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{api::HueAPIError, service::CIEColor};
+use std::{collections::BTreeMap, sync::Arc};
+use webrtc_util::Conn;
+
+const PROTOCOL: &[u8] = b"HueStream";
+const VERSION: [u8; 2] = [0x02, 0x00];
+
+/// The most channels a single frame can carry.
+pub const MAX_CHANNELS: usize = 20;
+
+/// An open entertainment stream, as returned by
+/// [Bridge::initialize_streaming](crate::service::Bridge::initialize_streaming).
+///
+/// Set channel colors with [StreamSession::set_channel], then send them all
+/// with [StreamSession::flush]. The bridge forwards at most 25 updates per
+/// second to the lights, so flushing more often than every 40ms gains nothing.
+/// The bridge ends the stream after about 10 seconds without an update.
+pub struct StreamSession {
+    conn: Arc<dyn Conn + Send + Sync>,
+    config_id: String,
+    sequence: u8,
+    mode: StreamColorMode,
+    channels: BTreeMap<u8, [u16; 3]>,
+}
+
+/// How channel colors are encoded in an entertainment stream frame.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum StreamColorMode {
+    /// 16-bit red, green, and blue.
+    #[default]
+    Rgb,
+    /// 16-bit CIE x and y coordinates, and brightness.
+    XyBrightness,
+}
+
+impl StreamColorMode {
+    fn header_byte(&self) -> u8 {
+        match self {
+            StreamColorMode::Rgb => 0x00,
+            StreamColorMode::XyBrightness => 0x01,
+        }
+    }
+}
+
+impl std::fmt::Debug for StreamSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamSession")
+            .field("config_id", &self.config_id)
+            .field("sequence", &self.sequence)
+            .field("mode", &self.mode)
+            .field("channels", &self.channels)
+            .finish()
+    }
+}
+
+impl StreamSession {
+    pub(crate) fn new(conn: Arc<dyn Conn + Send + Sync>, config_id: impl Into<String>) -> Self {
+        StreamSession {
+            conn,
+            config_id: config_id.into(),
+            sequence: 0,
+            mode: StreamColorMode::Rgb,
+            channels: BTreeMap::new(),
+        }
+    }
+
+    /// The id of the [EntertainmentConfiguration](crate::service::EntertainmentConfiguration)
+    /// being streamed to.
+    pub fn config_id(&self) -> &str {
+        &self.config_id
+    }
+
+    /// The color mode of the next frame, set by the last channel update.
+    pub fn color_mode(&self) -> StreamColorMode {
+        self.mode
+    }
+
+    /// Sets the color of a channel for the next [StreamSession::flush]. The
+    /// color is kept for later flushes until changed. At most [MAX_CHANNELS]
+    /// channels can be sent at once.
+    ///
+    /// A frame carries a single [StreamColorMode], so switching from
+    /// [StreamSession::set_channel_xy] clears the other channels.
+    pub fn set_channel(&mut self, channel_id: u8, rgb: [u8; 3]) {
+        self.set_mode(StreamColorMode::Rgb);
+        self.channels
+            .insert(channel_id, rgb.map(|c| c as u16 * 257));
+    }
+
+    /// Sets the CIE color and `brightness` in \[`0.0`, `1.0`\] of a channel
+    /// for the next [StreamSession::flush], without converting through RGB.
+    ///
+    /// A frame carries a single [StreamColorMode], so switching from
+    /// [StreamSession::set_channel] clears the other channels.
+    pub fn set_channel_xy(&mut self, channel_id: u8, xy: CIEColor, brightness: f32) {
+        self.set_mode(StreamColorMode::XyBrightness);
+        self.channels.insert(
+            channel_id,
+            [xy.x, xy.y, brightness].map(|v| (v.clamp(0.0, 1.0) * 65535.0).round() as u16),
+        );
+    }
+
+    fn set_mode(&mut self, mode: StreamColorMode) {
+        if self.mode != mode {
+            self.mode = mode;
+            self.channels.clear();
+        }
+    }
+
+    /// Sends the current color of every channel to the bridge, failing with
+    /// [HueAPIError::InvalidInput] if more than [MAX_CHANNELS] are set.
+    pub async fn flush(&mut self) -> Result<(), HueAPIError> {
+        let channels = self
+            .channels
+            .iter()
+            .map(|(id, values)| (*id, *values))
+            .collect::<Vec<_>>();
+        let frame = encode_frame(&self.config_id, self.sequence, self.mode, &channels)?;
+        self.sequence = self.sequence.wrapping_add(1);
+        self.conn
+            .send(&frame)
+            .await
+            .map(|_| ())
+            .map_err(|e| HueAPIError::Streaming(format!("failed to send stream message: {e}")))
+    }
+}
+
+/// Encodes a HueStream v2 frame, with each channel's three values in 16 bits
+/// as described by `mode`.
+pub(crate) fn encode_frame(
+    config_id: &str,
+    sequence: u8,
+    mode: StreamColorMode,
+    channels: &[(u8, [u16; 3])],
+) -> Result<Vec<u8>, HueAPIError> {
+    if channels.len() > MAX_CHANNELS {
+        return Err(HueAPIError::InvalidInput(format!(
+            "a frame carries at most {MAX_CHANNELS} channels, not {}",
+            channels.len()
+        )));
+    }
+    let mut bytes = Vec::with_capacity(52 + channels.len() * 7);
+    bytes.extend(PROTOCOL);
+    bytes.extend(VERSION);
+    bytes.push(sequence);
+    bytes.extend([0x00, 0x00]); // reserved
+    bytes.push(mode.header_byte());
+    bytes.push(0x00); // reserved
+    bytes.extend(config_id.as_bytes());
+    for (id, values) in channels {
+        bytes.push(*id);
+        for value in values {
+            bytes.extend(value.to_be_bytes());
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG_ID: &str = "1a8d99cc-967b-44f2-9202-43f976c0fa6b";
+
+    /// The 52 byte header of a frame with `sequence` in `mode`.
+    fn header(sequence: u8, mode: u8) -> Vec<u8> {
+        let mut bytes = vec![
+            b'H', b'u', b'e', b'S', b't', b'r', b'e', b'a', b'm', // protocol
+            0x02, 0x00, // version 2.0
+            sequence, 0x00, 0x00, // sequence, reserved
+            mode, 0x00, // color mode, reserved
+        ];
+        bytes.extend(CONFIG_ID.as_bytes());
+        bytes
+    }
+
+    #[test]
+    fn header_carries_sequence_mode_and_config_id() {
+        let frame = encode_frame(CONFIG_ID, 7, StreamColorMode::Rgb, &[]).unwrap();
+        assert_eq!(frame.len(), 52);
+        assert_eq!(frame, header(7, 0x00));
+
+        let frame = encode_frame(CONFIG_ID, 255, StreamColorMode::XyBrightness, &[]).unwrap();
+        assert_eq!(frame, header(255, 0x01));
+    }
+
+    #[test]
+    fn rgb_channels_are_big_endian_triples() {
+        let channels = [(0, [0xffff, 0x0000, 0x0000]), (3, [0x1234, 0x8000, 0x00ff])];
+        let frame = encode_frame(CONFIG_ID, 1, StreamColorMode::Rgb, &channels).unwrap();
+
+        let mut expected = header(1, 0x00);
+        expected.extend([0x00, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00]);
+        expected.extend([0x03, 0x12, 0x34, 0x80, 0x00, 0x00, 0xff]);
+        assert_eq!(frame, expected);
+    }
+
+    #[test]
+    fn xy_channels_carry_coordinates_and_brightness() {
+        // x = 0.5, y = 0.25 and full brightness, scaled to 16 bits
+        let channels = [(1, [0x8000, 0x4000, 0xffff])];
+        let frame = encode_frame(CONFIG_ID, 2, StreamColorMode::XyBrightness, &channels).unwrap();
+
+        let mut expected = header(2, 0x01);
+        expected.extend([0x01, 0x80, 0x00, 0x40, 0x00, 0xff, 0xff]);
+        assert_eq!(frame, expected);
+    }
+
+    #[test]
+    fn frames_hold_at_most_max_channels() {
+        let channels = (0..=MAX_CHANNELS as u8)
+            .map(|id| (id, [0, 0, 0]))
+            .collect::<Vec<_>>();
+
+        let frame = encode_frame(
+            CONFIG_ID,
+            0,
+            StreamColorMode::Rgb,
+            &channels[..MAX_CHANNELS],
+        );
+        assert_eq!(frame.unwrap().len(), 52 + MAX_CHANNELS * 7);
+        assert!(matches!(
+            encode_frame(CONFIG_ID, 0, StreamColorMode::Rgb, &channels),
+            Err(HueAPIError::InvalidInput(_))
+        ));
+    }
+}
@@ -1,20 +1,28 @@
+#[cfg(feature = "chrono")]
+use crate::service::parse_timestamp;
 use crate::{
     api::HueAPIError,
     command::{merge_commands, HomeKitCommand, MatterCommand},
     service::{Bridge, ResourceIdentifier, ResourceType},
 };
-use serde::Deserialize;
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 /// An Apple HomeKit device.
 #[derive(Debug)]
 pub struct HomeKit<'a> {
     bridge: &'a Bridge,
-    data: HomeKitData,
+    data: Arc<HomeKitData>,
 }
 
 impl<'a> HomeKit<'a> {
-    pub fn new(bridge: &'a Bridge, data: HomeKitData) -> Self {
-        HomeKit { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<HomeKitData>>) -> Self {
+        HomeKit {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &HomeKitData {
@@ -39,7 +47,7 @@ impl<'a> HomeKit<'a> {
 }
 
 /// Internal representation of a [HomeKit].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct HomeKitData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -64,7 +72,7 @@ impl HomeKitData {
     }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum HomeKitStatus {
     Paired,
@@ -77,12 +85,15 @@ pub enum HomeKitStatus {
 #[derive(Debug)]
 pub struct Matter<'a> {
     bridge: &'a Bridge,
-    data: MatterData,
+    data: Arc<MatterData>,
 }
 
 impl<'a> Matter<'a> {
-    pub fn new(bridge: &'a Bridge, data: MatterData) -> Self {
-        Matter { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<MatterData>>) -> Self {
+        Matter {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &MatterData {
@@ -107,7 +118,7 @@ impl<'a> Matter<'a> {
 }
 
 /// Internal representation of the [Matter] interop interface.
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct MatterData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -132,12 +143,12 @@ impl MatterData {
 /// [Matter](https://csa-iot.org/all-solutions/matter/) devices.
 #[derive(Debug)]
 pub struct MatterFabric {
-    data: MatterFabricData,
+    data: Arc<MatterFabricData>,
 }
 
 impl MatterFabric {
-    pub fn new(data: MatterFabricData) -> Self {
-        MatterFabric { data }
+    pub fn new(data: impl Into<Arc<MatterFabricData>>) -> Self {
+        MatterFabric { data: data.into() }
     }
 
     pub fn data(&self) -> &MatterFabricData {
@@ -154,7 +165,7 @@ impl MatterFabric {
 }
 
 /// Internal representation of a [MatterFabric].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct MatterFabricData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -177,7 +188,15 @@ impl MatterFabricData {
     }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[cfg(feature = "chrono")]
+impl MatterFabricData {
+    /// The `creation_time` timestamp, parsed as UTC.
+    pub fn created_at(&self) -> Result<DateTime<Utc>, chrono::ParseError> {
+        parse_timestamp(&self.creation_time)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum MatterFabricStatus {
     Pending,
@@ -186,7 +205,7 @@ pub enum MatterFabricStatus {
     Paired,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct FabricData {
     pub label: String,
     /// Matter vendor id of entity that created the fabric association.
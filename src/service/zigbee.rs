@@ -4,17 +4,21 @@ use crate::{
     service::{Bridge, ResourceIdentifier, ResourceType, SetStatus},
 };
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 /// A virtual device representing a Zigbee connection service.
 #[derive(Debug)]
 pub struct ZigbeeConnectivity<'a> {
     bridge: &'a Bridge,
-    data: ZigbeeConnectivityData,
+    data: Arc<ZigbeeConnectivityData>,
 }
 
 impl<'a> ZigbeeConnectivity<'a> {
-    pub fn new(bridge: &'a Bridge, data: ZigbeeConnectivityData) -> Self {
-        ZigbeeConnectivity { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<ZigbeeConnectivityData>>) -> Self {
+        ZigbeeConnectivity {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &ZigbeeConnectivityData {
@@ -46,7 +50,7 @@ impl<'a> ZigbeeConnectivity<'a> {
 }
 
 /// Internal representation of a [ZigbeeConnectivity].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ZigbeeConnectivityData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -74,12 +78,12 @@ impl ZigbeeConnectivityData {
 /// A virtual device representing a Zigbee Greeen Power connectivity service.
 #[derive(Debug)]
 pub struct ZGPConnectivity {
-    data: ZGPConnectivityData,
+    data: Arc<ZGPConnectivityData>,
 }
 
 impl ZGPConnectivity {
-    pub fn new(data: ZGPConnectivityData) -> Self {
-        ZGPConnectivity { data }
+    pub fn new(data: impl Into<Arc<ZGPConnectivityData>>) -> Self {
+        ZGPConnectivity { data: data.into() }
     }
 
     pub fn data(&self) -> &ZGPConnectivityData {
@@ -100,7 +104,7 @@ impl ZGPConnectivity {
 }
 
 /// Internal representation of a [ZGPConnectivity].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ZGPConnectivityData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -126,12 +130,15 @@ impl ZGPConnectivityData {
 #[derive(Debug)]
 pub struct ZigbeeDeviceDiscovery<'a> {
     bridge: &'a Bridge,
-    data: ZigbeeDeviceDiscoveryData,
+    data: Arc<ZigbeeDeviceDiscoveryData>,
 }
 
 impl<'a> ZigbeeDeviceDiscovery<'a> {
-    pub fn new(bridge: &'a Bridge, data: ZigbeeDeviceDiscoveryData) -> Self {
-        ZigbeeDeviceDiscovery { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<ZigbeeDeviceDiscoveryData>>) -> Self {
+        ZigbeeDeviceDiscovery {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &ZigbeeDeviceDiscoveryData {
@@ -163,7 +170,7 @@ impl<'a> ZigbeeDeviceDiscovery<'a> {
 }
 
 /// Internal representation of a [ZigbeeDeviceDiscovery].
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ZigbeeDeviceDiscoveryData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -184,14 +191,14 @@ impl ZigbeeDeviceDiscoveryData {
     }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ZigbeeDeviceDiscoveryStatus {
     Active,
     Ready,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ZigbeeStatus {
     /// The device has been recently been available.
@@ -204,7 +211,7 @@ pub enum ZigbeeStatus {
     UnidirectionalIncoming,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ZigbeeChannelState {
     pub status: SetStatus,
     /// Current value of the zigbee channel.
@@ -4,17 +4,21 @@ use crate::{
     service::{Bridge, Device, Group, Light, ResourceIdentifier, ResourceType, Scene},
 };
 use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Duration};
 
 /// A virtual device that groups services.
 #[derive(Debug)]
 pub struct Zone<'a> {
     bridge: &'a Bridge,
-    pub data: ZoneData,
+    pub data: Arc<ZoneData>,
 }
 
 impl<'a> Zone<'a> {
-    pub fn new(bridge: &'a Bridge, data: ZoneData) -> Self {
-        Zone { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<ZoneData>>) -> Self {
+        Zone {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &ZoneData {
@@ -34,7 +38,7 @@ impl<'a> Zone<'a> {
     }
 
     pub fn archetype(&self) -> ZoneArchetype {
-        self.data.metadata.archetype
+        self.data.metadata.archetype.clone()
     }
 
     pub fn devices(&self) -> Vec<Device> {
@@ -68,18 +72,15 @@ impl<'a> Zone<'a> {
             .collect()
     }
 
+    /// The [Group] controlling all lights in the zone at once. The bridge
+    /// lists this `grouped_light` among the zone's services; it is [None] if
+    /// the zone has no lights, or the group is not yet cached.
     pub fn group(&self) -> Option<Group> {
         self.data
             .services
             .iter()
             .find(|s| s.rtype == ResourceType::Group)
-            .map(|gid| {
-                self.bridge
-                    .groups()
-                    .into_iter()
-                    .find(|g| g.rid() == *gid)
-                    .unwrap()
-            })
+            .and_then(|gid| self.bridge.group(&gid.rid))
     }
 
     pub async fn on(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
@@ -106,6 +107,61 @@ impl<'a> Zone<'a> {
         }
     }
 
+    /// Makes all lights perform a breathe cycle through the [Group::alert].
+    pub async fn alert(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        if let Some(group) = self.group() {
+            group.alert().await
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    /// Identifies all lights at once, see [Group::identify].
+    pub async fn identify(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        if let Some(group) = self.group() {
+            group.identify().await
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    /// Adds `child` to the zone's children, putting the full list since the
+    /// bridge replaces rather than appends. Does nothing if `child` is already
+    /// present.
+    ///
+    /// Children are read from the bridge cache, which is updated on success,
+    /// so repeated changes through a stale [Zone] build on each other.
+    pub async fn add_child(
+        &self,
+        child: ResourceIdentifier,
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.bridge
+            .update_children(&self.rid(), &self.data, |children| {
+                if children.contains(&child) {
+                    return false;
+                }
+                children.push(child);
+                true
+            })
+            .await
+    }
+
+    /// Removes `child` from the zone's children, putting the remaining list.
+    /// Does nothing if `child` is not present. As with [Zone::add_child],
+    /// children are read from and written back to the bridge cache.
+    pub async fn remove_child(
+        &self,
+        child: &ResourceIdentifier,
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.bridge
+            .update_children(&self.rid(), &self.data, |children| {
+                let len = children.len();
+                children.retain(|c| c != child);
+                children.len() != len
+            })
+            .await
+    }
+
     pub fn builder(name: impl Into<String>, archetype: ZoneArchetype) -> ZoneBuilder {
         ZoneBuilder::new(name, archetype)
     }
@@ -123,12 +179,15 @@ impl<'a> Zone<'a> {
 #[derive(Debug, Clone)]
 pub struct Room<'a> {
     bridge: &'a Bridge,
-    pub data: ZoneData,
+    pub data: Arc<ZoneData>,
 }
 
 impl<'a> Room<'a> {
-    pub fn new(bridge: &'a Bridge, data: ZoneData) -> Self {
-        Room { bridge, data }
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<ZoneData>>) -> Self {
+        Room {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &ZoneData {
@@ -151,7 +210,7 @@ impl<'a> Room<'a> {
     }
 
     pub fn archetype(&self) -> ZoneArchetype {
-        self.data.metadata.archetype
+        self.data.metadata.archetype.clone()
     }
 
     pub fn devices(&self) -> Vec<Device> {
@@ -185,18 +244,15 @@ impl<'a> Room<'a> {
             .collect()
     }
 
+    /// The [Group] controlling all lights in the room at once. The bridge
+    /// lists this `grouped_light` among the room's services; it is [None] if
+    /// the room has no lights, or the group is not yet cached.
     pub fn group(&self) -> Option<Group> {
         self.data
             .services
             .iter()
             .find(|s| s.rtype == ResourceType::Group)
-            .map(|gid| {
-                self.bridge
-                    .groups()
-                    .into_iter()
-                    .find(|g| g.rid() == *gid)
-                    .unwrap()
-            })
+            .and_then(|gid| self.bridge.group(&gid.rid))
     }
 
     pub async fn on(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
@@ -223,6 +279,92 @@ impl<'a> Room<'a> {
         }
     }
 
+    /// Makes all lights perform a breathe cycle through the [Group::alert].
+    pub async fn alert(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        if let Some(group) = self.group() {
+            group.alert().await
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    /// Identifies all lights at once, see [Group::identify].
+    pub async fn identify(&self) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        if let Some(group) = self.group() {
+            group.identify().await
+        } else {
+            Ok(vec![])
+        }
+    }
+
+    /// Identifies each [Light] in the room one after another, ordered by device
+    /// name and waiting `stagger` between each, to help locate and order lights
+    /// physically.
+    pub async fn identify_sequence(
+        &self,
+        stagger: Duration,
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        let mut lights = self
+            .lights()
+            .into_iter()
+            .map(|l| {
+                let name = self
+                    .bridge
+                    .device(&l.data().owner.rid)
+                    .map(|d| d.name().to_owned())
+                    .unwrap_or_default();
+                (name, l)
+            })
+            .collect::<Vec<_>>();
+        lights.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut rids = vec![];
+        for (i, (_, light)) in lights.iter().enumerate() {
+            if i > 0 {
+                tokio::time::sleep(stagger).await;
+            }
+            rids.extend(light.identify().await?.rids);
+        }
+        Ok(rids)
+    }
+
+    /// Adds `child` to the room's children, putting the full list since the
+    /// bridge replaces rather than appends. Does nothing if `child` is already
+    /// present.
+    ///
+    /// Children are read from the bridge cache, which is updated on success,
+    /// so repeated changes through a stale [Room] build on each other.
+    pub async fn add_child(
+        &self,
+        child: ResourceIdentifier,
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.bridge
+            .update_children(&self.rid(), &self.data, |children| {
+                if children.contains(&child) {
+                    return false;
+                }
+                children.push(child);
+                true
+            })
+            .await
+    }
+
+    /// Removes `child` from the room's children, putting the remaining list.
+    /// Does nothing if `child` is not present. As with [Room::add_child],
+    /// children are read from and written back to the bridge cache.
+    pub async fn remove_child(
+        &self,
+        child: &ResourceIdentifier,
+    ) -> Result<Vec<ResourceIdentifier>, HueAPIError> {
+        self.bridge
+            .update_children(&self.rid(), &self.data, |children| {
+                let len = children.len();
+                children.retain(|c| c != child);
+                children.len() != len
+            })
+            .await
+    }
+
     pub fn builder(name: impl Into<String>, archetype: ZoneArchetype) -> ZoneBuilder {
         ZoneBuilder::new(name, archetype)
     }
@@ -260,7 +402,7 @@ impl ZoneBuilder {
 }
 
 /// Internal representation of a [Zone] or [Room].
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ZoneData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -289,7 +431,7 @@ impl ZoneData {
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct ZoneMetadata {
     /// Human readable name of a resource.
     pub name: String,
@@ -297,7 +439,9 @@ pub struct ZoneMetadata {
     pub archetype: ZoneArchetype,
 }
 
-#[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+/// Possible archetypes of a [Room] or [Zone], covering every value in the v2
+/// API. Values added to the API later deserialize as [ZoneArchetype::Unknown].
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ZoneArchetype {
     Attic,
@@ -339,20 +483,28 @@ pub enum ZoneArchetype {
     TopFloor,
     Tv,
     Upstairs,
-    #[serde(other)]
+    /// The `other` archetype as reported by the bridge.
     Other,
+    /// An archetype not known to this crate. The original value is preserved so
+    /// that it survives being written back to the bridge.
+    #[serde(untagged)]
+    Unknown(String),
 }
 
 /// A virtual device representing the full tree of devices and services on the
 /// Hue Bridge.
 #[derive(Debug)]
-pub struct Home {
-    data: HomeData,
+pub struct Home<'a> {
+    bridge: &'a Bridge,
+    data: Arc<HomeData>,
 }
 
-impl Home {
-    pub fn new(data: HomeData) -> Self {
-        Home { data }
+impl<'a> Home<'a> {
+    pub fn new(bridge: &'a Bridge, data: impl Into<Arc<HomeData>>) -> Self {
+        Home {
+            bridge,
+            data: data.into(),
+        }
     }
 
     pub fn data(&self) -> &HomeData {
@@ -366,10 +518,33 @@ impl Home {
     pub fn rid(&self) -> ResourceIdentifier {
         self.data.rid()
     }
+
+    /// Top-level children of the home, typically the [Room]s on the bridge and
+    /// any [Device]s not assigned to a room.
+    pub fn children(&self) -> &[ResourceIdentifier] {
+        &self.data.children
+    }
+
+    pub fn rooms(&self) -> Vec<Room<'_>> {
+        self.bridge
+            .rooms()
+            .into_iter()
+            .filter(|r| self.data.children.contains(&r.rid()))
+            .collect()
+    }
+
+    /// The [Group] aggregating every light in the home.
+    pub fn grouped_light(&self) -> Option<Group<'_>> {
+        self.data
+            .services
+            .iter()
+            .find(|s| s.rtype == ResourceType::Group)
+            .and_then(|gid| self.bridge.group(&gid.rid))
+    }
 }
 
 /// Internal representation of a [Home].
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub struct HomeData {
     /// Unique identifier representing a specific resource instance.
     pub id: String,
@@ -394,3 +569,193 @@ impl HomeData {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::fixtures;
+    use reqwest::Method;
+    use serde_json::json;
+
+    const ARCHETYPES: [&str; 40] = [
+        "attic",
+        "balcony",
+        "barbecue",
+        "bathroom",
+        "bedroom",
+        "carport",
+        "closet",
+        "computer",
+        "dining",
+        "downstairs",
+        "driveway",
+        "front_door",
+        "garage",
+        "garden",
+        "guest_room",
+        "gym",
+        "hallway",
+        "home",
+        "kids_bedroom",
+        "kitchen",
+        "laundry_room",
+        "living_room",
+        "lounge",
+        "man_cave",
+        "music",
+        "nursery",
+        "office",
+        "pool",
+        "porch",
+        "reading",
+        "recreation",
+        "staircase",
+        "storage",
+        "studio",
+        "terrace",
+        "toilet",
+        "top_floor",
+        "tv",
+        "upstairs",
+        "other",
+    ];
+
+    #[test]
+    fn known_archetypes_round_trip() {
+        for name in ARCHETYPES {
+            let value = serde_json::Value::from(name);
+            let archetype: ZoneArchetype = serde_json::from_value(value.clone()).unwrap();
+            assert!(!matches!(archetype, ZoneArchetype::Unknown(_)), "{name}");
+            assert_eq!(serde_json::to_value(&archetype).unwrap(), value);
+        }
+        assert_eq!(
+            serde_json::from_value::<ZoneArchetype>("living_room".into()).unwrap(),
+            ZoneArchetype::LivingRoom
+        );
+    }
+
+    #[test]
+    fn unknown_archetype_round_trips() {
+        let archetype: ZoneArchetype = serde_json::from_value("sauna".into()).unwrap();
+
+        assert_eq!(archetype, ZoneArchetype::Unknown("sauna".to_owned()));
+        assert_eq!(serde_json::to_value(&archetype).unwrap(), "sauna");
+    }
+
+    fn light(id: &str) -> ResourceIdentifier {
+        ResourceIdentifier::new(id, ResourceType::Light)
+    }
+
+    fn children_written(mock: &crate::api::MockTransport, path: &str) -> Vec<serde_json::Value> {
+        mock.requests_to(reqwest::Method::PUT, path)
+            .into_iter()
+            .map(|r| r.body.unwrap()["children"].clone())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn add_then_remove_child_is_idempotent() {
+        use crate::service::fixtures;
+        use reqwest::Method;
+        use serde_json::json;
+
+        let (mock, bridge) = fixtures::refreshed_bridge(vec![fixtures::zone(
+            "z1",
+            &[fixtures::rid("light", "l1")],
+            "g1",
+        )])
+        .await;
+        mock.respond_data(
+            Method::PUT,
+            "/resource/zone/z1",
+            fixtures::written("zone", "z1"),
+        );
+        let zone = bridge.zone("z1").unwrap();
+
+        zone.add_child(light("l2")).await.unwrap();
+        zone.add_child(light("l2")).await.unwrap();
+        zone.remove_child(&light("l2")).await.unwrap();
+        zone.remove_child(&light("l2")).await.unwrap();
+
+        assert_eq!(
+            children_written(&mock, "/resource/zone/z1"),
+            vec![
+                json!([fixtures::rid("light", "l1"), fixtures::rid("light", "l2")]),
+                json!([fixtures::rid("light", "l1")]),
+            ]
+        );
+        assert_eq!(
+            bridge.zone("z1").unwrap().data().children,
+            vec![light("l1")]
+        );
+    }
+
+    #[tokio::test]
+    async fn room_children_build_on_earlier_changes() {
+        use crate::service::fixtures;
+        use reqwest::Method;
+        use serde_json::json;
+
+        let (mock, bridge) =
+            fixtures::refreshed_bridge(vec![fixtures::room("r1", &[], "g1")]).await;
+        mock.respond_data(
+            Method::PUT,
+            "/resource/room/r1",
+            fixtures::written("room", "r1"),
+        );
+        let room = bridge.room("r1").unwrap();
+
+        room.add_child(light("l1")).await.unwrap();
+        room.add_child(light("l2")).await.unwrap();
+
+        assert_eq!(
+            children_written(&mock, "/resource/room/r1"),
+            vec![
+                json!([fixtures::rid("light", "l1")]),
+                json!([fixtures::rid("light", "l1"), fixtures::rid("light", "l2")]),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn alert_breathes_through_the_grouped_light() {
+        let (mock, bridge) = fixtures::refreshed_bridge(vec![
+            fixtures::zone("z1", &[fixtures::rid("light", "l1")], "g1"),
+            fixtures::grouped_light("g1", fixtures::rid("zone", "z1"), true),
+            fixtures::room("r1", &[fixtures::rid("device", "d1")], "g2"),
+            fixtures::grouped_light("g2", fixtures::rid("room", "r1"), true),
+        ])
+        .await;
+        for group in ["g1", "g2"] {
+            mock.respond_data(
+                Method::PUT,
+                &format!("/resource/grouped_light/{group}"),
+                fixtures::written("grouped_light", group),
+            );
+        }
+
+        let zone = bridge.zone("z1").unwrap();
+        assert_eq!(zone.group().unwrap().id(), "g1");
+        zone.alert().await.unwrap();
+        bridge.room("r1").unwrap().alert().await.unwrap();
+
+        for group in ["g1", "g2"] {
+            let sent = mock.requests_to(Method::PUT, &format!("/resource/grouped_light/{group}"));
+            assert_eq!(
+                sent[0].body,
+                Some(json!({ "alert": { "action": "breathe" } }))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn alert_without_a_grouped_light_sends_nothing() {
+        let (mock, bridge) =
+            fixtures::refreshed_bridge(vec![fixtures::zone("z1", &[], "g1")]).await;
+
+        let zone = bridge.zone("z1").unwrap();
+        assert!(zone.group().is_none());
+        assert_eq!(zone.alert().await, Ok(vec![]));
+        assert_eq!(mock.requests().len(), 1);
+    }
+}